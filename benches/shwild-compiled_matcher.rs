@@ -433,6 +433,66 @@ pub fn matches_against_WindowsPath(c : &mut Criterion) {
     });
 }
 
+pub fn matches_against_WindowsPath_DFA_Eager(c : &mut Criterion) {
+    let pattern = constants::patterns::WINDOWS_PATH;
+    let flags = 0;
+
+    let inputs = [
+        // insert list:
+        "",
+        "C:/",
+        "C:/dir",
+        "C:/dir/stem.com",
+        "C:/dir/stem.exe",
+        "C:/directory-with-a-veeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeerrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrryyyyyyyyyyyyyyyyyyyyyyyyyyyyyy-long-name",
+        "C:/directory-with-a-veeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeerrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrryyyyyyyyyyyyyyyyyyyyyyyyyyyyyy-long-name/stem.com",
+    ];
+
+    let mut matcher = shwild::CompiledMatcher::from_pattern_and_flags(black_box(pattern), black_box(flags)).unwrap();
+
+    assert!(matcher.enable_dfa(shwild::DfaMode::Eager, 1_000));
+
+    c.bench_function("`shwild::CompiledMatcher()` matching - Windows Path (eager DFA)", |b| {
+        b.iter(|| {
+            for input in &inputs {
+                let r = black_box(&matcher).matches(input);
+
+                let _ = black_box(r);
+            }
+        })
+    });
+}
+
+pub fn matches_against_WindowsPath_DFA_Lazy(c : &mut Criterion) {
+    let pattern = constants::patterns::WINDOWS_PATH;
+    let flags = 0;
+
+    let inputs = [
+        // insert list:
+        "",
+        "C:/",
+        "C:/dir",
+        "C:/dir/stem.com",
+        "C:/dir/stem.exe",
+        "C:/directory-with-a-veeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeerrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrryyyyyyyyyyyyyyyyyyyyyyyyyyyyyy-long-name",
+        "C:/directory-with-a-veeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeerrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrrryyyyyyyyyyyyyyyyyyyyyyyyyyyyyy-long-name/stem.com",
+    ];
+
+    let mut matcher = shwild::CompiledMatcher::from_pattern_and_flags(black_box(pattern), black_box(flags)).unwrap();
+
+    assert!(matcher.enable_dfa(shwild::DfaMode::Lazy, 1_000));
+
+    c.bench_function("`shwild::CompiledMatcher()` matching - Windows Path (lazy DFA)", |b| {
+        b.iter(|| {
+            for input in &inputs {
+                let r = black_box(&matcher).matches(input);
+
+                let _ = black_box(r);
+            }
+        })
+    });
+}
+
 
 criterion_group!(
     benches,
@@ -455,5 +515,7 @@ criterion_group!(
     matches_against_range_continuum_reverse,
     matches_against_range_continuum_crosscase,
     matches_against_WindowsPath,
+    matches_against_WindowsPath_DFA_Eager,
+    matches_against_WindowsPath_DFA_Lazy,
 );
 criterion_main!(benches);
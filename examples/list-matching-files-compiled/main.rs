@@ -40,14 +40,13 @@ fn main() {
                 match entry {
                     Ok(entry) => {
                         let path = entry.path();
-                        let path_s = format!("{}", path.display());
 
                         // ... check against ...
                         for matcher in &matchers {
                             // ... each pattern ...
-                            if matcher.matches(&path_s) {
+                            if matcher.matches_path(&path) {
                                 // ... and print when it matches any one.
-                                println!("\t{path_s}");
+                                println!("\t{}", path.display());
 
                                 break;
                             }
@@ -0,0 +1,1083 @@
+// src/automaton.rs : position automata and (lazily or eagerly) determinised
+//                    automata for shwild patterns
+
+// ///////////////////////////////////////////////
+// imports
+
+use crate::{
+    case,
+    constants,
+    PatternElement,
+};
+
+use std::{
+    collections::HashMap,
+    sync::RwLock,
+};
+
+
+// ///////////////////////////////////////////////
+// CharSet
+
+/// Set of characters, any one of which matches a single input character.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Eq, PartialEq)]
+pub(crate) enum CharSet {
+    /// Any character.
+    Any,
+    /// Any of the (sorted, unique) characters.
+    Of(Vec<char>),
+    /// Any character other than the (sorted, unique) characters.
+    NotOf(Vec<char>),
+}
+
+// API functions
+impl CharSet {
+    /// Creates a set from the characters of a range, applying the same
+    /// (ASCII-only) case-folding as is used by the range matchers.
+    pub(crate) fn from_range_chars(
+        chars : &[char],
+        flags : i64,
+    ) -> Vec<char> {
+        let mut v = Vec::with_capacity(chars.len() * 2);
+
+        for &c in chars {
+            v.push(c);
+
+            if 0 != (constants::IGNORE_CASE & flags) && c.is_ascii_alphabetic() {
+                v.push(c.to_ascii_lowercase());
+                v.push(c.to_ascii_uppercase());
+            }
+        }
+
+        v.sort_unstable();
+        v.dedup();
+
+        v
+    }
+
+    /// Creates a set from a single literal character, comprising its case
+    /// variants if `IGNORE_CASE` is specified.
+    pub(crate) fn from_literal_char(
+        c : char,
+        flags : i64,
+    ) -> Self {
+        if 0 != (constants::IGNORE_CASE & flags) {
+            Self::Of(case::case_variants(c))
+        } else {
+            Self::Of(vec![c])
+        }
+    }
+}
+
+// Non-mutating methods
+impl CharSet {
+    /// Indicates whether `c` is a member of the set.
+    #[inline]
+    pub(crate) fn contains(
+        &self,
+        c : char,
+    ) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Of(chars) => chars.binary_search(&c).is_ok(),
+            Self::NotOf(chars) => chars.binary_search(&c).is_err(),
+        }
+    }
+
+    /// The characters explicitly mentioned by the set.
+    pub(crate) fn mentioned(&self) -> &[char] {
+        match self {
+            Self::Any => &[],
+            Self::Of(chars) | Self::NotOf(chars) => chars,
+        }
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// Nfa
+
+/// A single step of a position automaton.
+#[derive(Clone)]
+#[derive(Debug)]
+pub(crate) enum Atom {
+    /// Exactly one character that is a member of the set.
+    One(CharSet),
+    /// Any number (0 or more) of characters.
+    Star,
+}
+
+/// A position automaton for a (linear) pattern: position `i` means that
+/// the first `i` atoms have been matched; position `atoms.len()` is the
+/// (sole) accepting position.
+#[derive(Clone)]
+#[derive(Debug)]
+pub(crate) struct Nfa {
+    atoms :    Vec<Atom>,
+    /// Whether the automaton matches exactly the strings matched by the
+    /// interpreter, which it does not where, with `IGNORE_CASE`, a literal
+    /// may match a sequence of characters of a different length.
+    is_exact : bool,
+}
+
+/// A set of positions, held sorted and unique.
+pub(crate) type PositionSet = Vec<u32>;
+
+// API functions
+impl Nfa {
+    /// Creates an automaton from the given parsed pattern `elements`.
+    pub(crate) fn from_elements(
+        elements : &[PatternElement],
+        flags : i64,
+    ) -> Self {
+        let ignore_case = 0 != (constants::IGNORE_CASE & flags);
+
+        let mut atoms = Vec::with_capacity(elements.len());
+        let mut is_exact = true;
+
+        for element in elements {
+            match element {
+                PatternElement::Literal(literal) => {
+                    if ignore_case && case::has_multi_char_folding(literal) {
+                        is_exact = false;
+                    }
+
+                    atoms.extend(literal.chars().map(|c| Atom::One(CharSet::from_literal_char(c, flags))));
+                },
+                PatternElement::Range {
+                    chars,
+                    negated: true,
+                } => {
+                    atoms.push(Atom::One(CharSet::NotOf(CharSet::from_range_chars(chars, flags))));
+                },
+                PatternElement::Range {
+                    chars,
+                    negated: false,
+                } => {
+                    atoms.push(Atom::One(CharSet::Of(CharSet::from_range_chars(chars, flags))));
+                },
+                PatternElement::Wild1 => {
+                    atoms.push(Atom::One(CharSet::Any));
+                },
+                PatternElement::WildN => {
+                    // adjacent `*`s are equivalent to one
+                    if !std::matches!(atoms.last(), Some(Atom::Star)) {
+                        atoms.push(Atom::Star);
+                    }
+                },
+            };
+        }
+
+        Self {
+            atoms,
+            is_exact,
+        }
+    }
+}
+
+// Non-mutating methods
+impl Nfa {
+    /// Indicates whether the automaton matches exactly the strings matched
+    /// by the interpreter, which it does not where, with `IGNORE_CASE`, a
+    /// literal contains a character whose upper-case is of more than one
+    /// character (e.g. `"ß"`, which matches `"ss"`), or would match such a
+    /// character (e.g. `"ss"`, which matches `"ß"`). Such an automaton
+    /// matches a subset of the strings matched by the interpreter.
+    #[inline]
+    pub(crate) fn is_exact(&self) -> bool {
+        self.is_exact
+    }
+
+    /// All character sets used by the automaton.
+    pub(crate) fn char_sets(&self) -> impl Iterator<Item = &CharSet> {
+        self.atoms.iter().filter_map(|atom| {
+            match atom {
+                Atom::One(char_set) => Some(char_set),
+                Atom::Star => None,
+            }
+        })
+    }
+
+    /// The number of positions, including the accepting position.
+    #[inline]
+    pub(crate) fn num_positions(&self) -> usize {
+        self.atoms.len() + 1
+    }
+
+    /// The atom at position `p`, or `None` for the accepting position.
+    #[inline]
+    pub(crate) fn atom(
+        &self,
+        p : usize,
+    ) -> Option<&Atom> {
+        self.atoms.get(p)
+    }
+
+    /// The start state, which is the closure of position 0.
+    pub(crate) fn start(&self) -> PositionSet {
+        self.closure(&[0])
+    }
+
+    /// Indicates whether `positions` contains the accepting position.
+    #[inline]
+    pub(crate) fn is_accepting(
+        &self,
+        positions : &[u32],
+    ) -> bool {
+        positions.last().is_some_and(|&p| p as usize == self.atoms.len())
+    }
+
+    /// Obtains the closure of the (sorted) `positions`, i.e. adds all
+    /// positions reachable by skipping over `*` atoms.
+    pub(crate) fn closure(
+        &self,
+        positions : &[u32],
+    ) -> PositionSet {
+        let mut r = Vec::with_capacity(positions.len() + 1);
+
+        for &p in positions {
+            if r.last().is_some_and(|&last| p <= last) {
+                // already added, along with all that follow it
+                continue;
+            }
+
+            let mut p = p;
+
+            r.push(p);
+
+            while std::matches!(self.atoms.get(p as usize), Some(Atom::Star)) {
+                p += 1;
+
+                r.push(p);
+            }
+        }
+
+        r
+    }
+
+    /// Obtains the (closed) positions reached from `positions` by consuming
+    /// a character for which `is_member` returns `true` for the atom sets.
+    pub(crate) fn step_by<F>(
+        &self,
+        positions : &[u32],
+        is_member : F,
+    ) -> PositionSet
+    where
+        F : Fn(&CharSet) -> bool,
+    {
+        let mut next = Vec::with_capacity(positions.len() + 1);
+
+        for &p in positions {
+            let q = match self.atoms.get(p as usize) {
+                Some(Atom::One(char_set)) if is_member(char_set) => p + 1,
+                Some(Atom::Star) => p,
+                _ => continue,
+            };
+
+            if next.last().map_or(true, |&last| q > last) {
+                next.push(q);
+            }
+        }
+
+        self.closure(&next)
+    }
+
+    /// Obtains the (closed) positions reached from `positions` by consuming
+    /// the character `c`.
+    #[inline]
+    pub(crate) fn step(
+        &self,
+        positions : &[u32],
+        c : char,
+    ) -> PositionSet {
+        self.step_by(positions, |char_set| char_set.contains(c))
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// Alphabet
+
+/// A partition of all characters into classes, such that all members of a
+/// class are treated identically by every character set from which the
+/// alphabet was built. Class 0 contains all characters that are not
+/// mentioned by any set.
+#[derive(Clone)]
+#[derive(Debug)]
+pub(crate) struct Alphabet {
+    /// The class of each ASCII character.
+    ascii :           [u32; 128],
+    /// The class of each mentioned non-ASCII character, sorted by
+    /// character.
+    non_ascii :       Vec<(char, u32)>,
+    /// A representative character of each class.
+    representatives : Vec<char>,
+}
+
+// API functions
+impl Alphabet {
+    /// Creates an alphabet that distinguishes between all characters that
+    /// are treated differently by any of the given `char_sets`.
+    pub(crate) fn from_char_sets<'a, I>(char_sets : I) -> Self
+    where
+        I : IntoIterator<Item = &'a CharSet>,
+    {
+        let char_sets : Vec<&CharSet> = char_sets.into_iter().collect();
+
+        let mut mentioned : Vec<char> = char_sets.iter().flat_map(|cs| cs.mentioned().iter().copied()).collect();
+
+        mentioned.sort_unstable();
+        mentioned.dedup();
+
+        let signature_of = |c : Option<char>| -> Vec<bool> {
+            char_sets
+                .iter()
+                .map(|cs| {
+                    match c {
+                        Some(c) => cs.contains(c),
+                        None => !std::matches!(cs, CharSet::Of(_)),
+                    }
+                })
+                .collect()
+        };
+
+        let unmentioned = Self::find_unmentioned_(&mentioned);
+
+        let mut classes : HashMap<Vec<bool>, u32> = HashMap::new();
+        let mut representatives = vec![unmentioned];
+
+        classes.insert(signature_of(None), 0);
+
+        let mut ascii = [0; 128];
+        let mut non_ascii = vec![];
+
+        for &c in &mentioned {
+            let next_class = representatives.len() as u32;
+
+            let class = *classes.entry(signature_of(Some(c))).or_insert(next_class);
+
+            if class == next_class {
+                representatives.push(c);
+            }
+
+            if c.is_ascii() {
+                ascii[c as usize] = class;
+            } else if 0 != class {
+                non_ascii.push((c, class));
+            }
+        }
+
+        Self {
+            ascii,
+            non_ascii,
+            representatives,
+        }
+    }
+}
+
+// Non-mutating methods
+impl Alphabet {
+    /// The class of the character `c`.
+    #[inline]
+    pub(crate) fn class_of(
+        &self,
+        c : char,
+    ) -> u32 {
+        if c.is_ascii() {
+            self.ascii[c as usize]
+        } else {
+            match self.non_ascii.binary_search_by_key(&c, |&(c, _)| c) {
+                Ok(index) => self.non_ascii[index].1,
+                Err(_) => 0,
+            }
+        }
+    }
+
+    /// The number of classes.
+    #[inline]
+    pub(crate) fn num_classes(&self) -> usize {
+        self.representatives.len()
+    }
+
+    /// A representative character of each class, indexed by class.
+    #[inline]
+    pub(crate) fn representatives(&self) -> &[char] {
+        &self.representatives
+    }
+}
+
+// Implementation
+impl Alphabet {
+    /// The class of the character at byte offset `*i` of `input`,
+    /// advancing `*i` past it; ASCII characters are classified directly
+    /// from the byte.
+    #[inline]
+    fn class_at_(
+        &self,
+        input : &str,
+        i : &mut usize,
+    ) -> u32 {
+        let b = input.as_bytes()[*i];
+
+        if b < 0x80 {
+            *i += 1;
+
+            self.ascii[b as usize]
+        } else {
+            let c = input[*i..].chars().next().unwrap();
+
+            *i += c.len_utf8();
+
+            self.class_of(c)
+        }
+    }
+
+    /// Finds a character that is not in the (sorted) `mentioned`,
+    /// preferring readable characters.
+    fn find_unmentioned_(mentioned : &[char]) -> char {
+        let preferred = ('a'..='z')
+            .chain('A'..='Z')
+            .chain('0'..='9')
+            .chain(['_', '~', '#', '@', '%']);
+
+        preferred
+            .chain(('\u{a1}'..=char::MAX).filter(|c| !c.is_control()))
+            .find(|c| mentioned.binary_search(c).is_err())
+            .expect("a finite set of characters cannot exhaust the character space")
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// Dfa
+
+/// The mode in which a [`CompiledMatcher`](crate::CompiledMatcher)
+/// determinises its pattern.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(Eq, PartialEq)]
+pub enum DfaMode {
+    /// All reachable states are constructed up-front; if the number of
+    /// states would exceed the budget then no DFA is used.
+    Eager,
+    /// States are constructed as they are first reached during matching;
+    /// if the number of states would exceed the budget then matching
+    /// reverts (permanently) to the backtracking interpreter.
+    Lazy,
+}
+
+/// The state identifier of the dead state.
+const DEAD : u32 = 0;
+/// The transition value for a not-yet-computed transition.
+const UNKNOWN : u32 = u32::MAX;
+
+/// A deterministic automaton, over the classes of an [`Alphabet`], whose
+/// states are sets of positions of an [`Nfa`].
+#[derive(Debug)]
+pub(crate) struct Dfa {
+    nfa :          Nfa,
+    alphabet :     Alphabet,
+    /// The transitions, indexed by `state * num_classes + class`.
+    transitions :  Vec<u32>,
+    accepting :    Vec<bool>,
+    state_ids :    HashMap<PositionSet, u32>,
+    state_sets :   Vec<PositionSet>,
+    start :        u32,
+    state_budget : usize,
+    /// Set when the budget has been exceeded in lazy construction.
+    exhausted :    bool,
+}
+
+/// Result of a walk over the constructed states of a lazy DFA.
+enum LazyOutcome {
+    Matched(bool),
+    /// The walk reached a transition - from `state` on `class` - that has
+    /// not yet been computed; it may be resumed from the input index
+    /// `resume` once it has.
+    Unknown {
+        state :  u32,
+        class :  u32,
+        resume : usize,
+    },
+}
+
+// API functions
+impl Dfa {
+    /// Creates a DFA with only the dead and start states constructed.
+    ///
+    /// # Returns:
+    /// `None` if `state_budget` does not allow even those two states.
+    pub(crate) fn new_lazy(
+        nfa : Nfa,
+        state_budget : usize,
+    ) -> Option<Self> {
+        if state_budget < 2 {
+            return None;
+        }
+
+        let alphabet = Alphabet::from_char_sets(nfa.char_sets());
+
+        let mut dfa = Self {
+            nfa,
+            alphabet,
+            transitions : vec![],
+            accepting : vec![],
+            state_ids : HashMap::new(),
+            state_sets : vec![],
+            start : DEAD,
+            state_budget,
+            exhausted : false,
+        };
+
+        let dead = dfa.add_state_(vec![]);
+
+        debug_assert_eq!(DEAD, dead);
+
+        dfa.transitions.iter_mut().for_each(|t| *t = DEAD);
+
+        let start = dfa.nfa.start();
+
+        dfa.start = dfa.add_state_(start);
+
+        Some(dfa)
+    }
+
+    /// Creates a DFA with all reachable states constructed.
+    ///
+    /// # Returns:
+    /// `None` if the number of states would exceed `state_budget`.
+    pub(crate) fn new_eager(
+        nfa : Nfa,
+        state_budget : usize,
+    ) -> Option<Self> {
+        let mut dfa = Self::new_lazy(nfa, state_budget)?;
+
+        let num_classes = dfa.alphabet.num_classes();
+
+        let mut state = 1;
+
+        while state < dfa.state_sets.len() {
+            for class in 0..num_classes {
+                dfa.compute_transition_(state as u32, class as u32)?;
+            }
+
+            state += 1;
+        }
+
+        Some(dfa)
+    }
+}
+
+// Non-mutating methods
+impl Dfa {
+    /// The number of states constructed so far (including the dead state).
+    #[cfg(test)]
+    pub(crate) fn num_states(&self) -> usize {
+        self.state_sets.len()
+    }
+
+    /// Evaluates `input` against a fully-constructed DFA.
+    pub(crate) fn matches(
+        &self,
+        input : &str,
+    ) -> bool {
+        let num_classes = self.alphabet.num_classes();
+        let bytes = input.as_bytes();
+
+        let mut state = self.start;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let class = self.alphabet.class_at_(input, &mut i);
+
+            state = self.transitions[state as usize * num_classes + class as usize];
+
+            debug_assert_ne!(UNKNOWN, state);
+
+            if DEAD == state {
+                return false;
+            }
+        }
+
+        self.accepting[state as usize]
+    }
+}
+
+// Implementation
+impl Dfa {
+    /// Walks `input`, from index `i` in `state`, over the transitions
+    /// constructed so far.
+    fn walk_known_(
+        &self,
+        input : &str,
+        state : u32,
+        i : usize,
+    ) -> LazyOutcome {
+        let num_classes = self.alphabet.num_classes();
+
+        let bytes = input.as_bytes();
+
+        let mut state = state;
+        let mut i = i;
+
+        while i < bytes.len() {
+            let class = self.alphabet.class_at_(input, &mut i);
+
+            let next = self.transitions[state as usize * num_classes + class as usize];
+
+            if UNKNOWN == next {
+                return LazyOutcome::Unknown {
+                    state,
+                    class,
+                    resume : i,
+                };
+            }
+
+            if DEAD == next {
+                return LazyOutcome::Matched(false);
+            }
+
+            state = next;
+        }
+
+        LazyOutcome::Matched(self.accepting[state as usize])
+    }
+
+    /// Computes the transition from `state` on `class`, marking the DFA as
+    /// exhausted if that would exceed the state budget.
+    fn extend_(
+        &mut self,
+        state : u32,
+        class : u32,
+    ) -> Option<u32> {
+        let next = self.compute_transition_(state, class);
+
+        if next.is_none() {
+            self.exhausted = true;
+        }
+
+        next
+    }
+
+    /// Computes, records, and returns the transition from `state` on
+    /// `class`, or `None` if that would exceed the state budget.
+    fn compute_transition_(
+        &mut self,
+        state : u32,
+        class : u32,
+    ) -> Option<u32> {
+        let num_classes = self.alphabet.num_classes();
+        let index = state as usize * num_classes + class as usize;
+
+        if UNKNOWN != self.transitions[index] {
+            return Some(self.transitions[index]);
+        }
+
+        let c = self.alphabet.representatives()[class as usize];
+
+        let positions = self.nfa.step(&self.state_sets[state as usize], c);
+
+        let next = match self.state_ids.get(&positions) {
+            Some(&id) => id,
+            None => {
+                if self.state_sets.len() >= self.state_budget {
+                    return None;
+                }
+
+                self.add_state_(positions)
+            },
+        };
+
+        self.transitions[index] = next;
+
+        Some(next)
+    }
+
+    fn add_state_(
+        &mut self,
+        positions : PositionSet,
+    ) -> u32 {
+        let id = self.state_sets.len() as u32;
+
+        self.accepting.push(self.nfa.is_accepting(&positions));
+        self.transitions
+            .extend(std::iter::repeat(UNKNOWN).take(self.alphabet.num_classes()));
+        self.state_ids.insert(positions.clone(), id);
+        self.state_sets.push(positions);
+
+        id
+    }
+}
+
+
+/// A DFA as held by a [`CompiledMatcher`](crate::CompiledMatcher).
+#[derive(Debug)]
+pub(crate) enum DfaMatcher {
+    Eager(Dfa),
+    /// The lazy DFA is shared by matching threads, each of which walks
+    /// the constructed states under a read lock and takes the write lock
+    /// only to construct a transition it lacks.
+    Lazy(RwLock<Dfa>),
+}
+
+// API functions
+impl DfaMatcher {
+    pub(crate) fn new(
+        elements : &[PatternElement],
+        flags : i64,
+        mode : DfaMode,
+        state_budget : usize,
+    ) -> Option<Self> {
+        if 0 != (constants::PURE_BYTES & flags) {
+            return None;
+        }
+        #[cfg(feature = "grapheme-clusters")]
+        if 0 != (constants::GRAPHEME_CLUSTERS & flags) {
+            return None;
+        }
+
+        let nfa = Nfa::from_elements(elements, flags);
+
+        if !nfa.is_exact() {
+            return None;
+        }
+
+        match mode {
+            DfaMode::Eager => Dfa::new_eager(nfa, state_budget).map(Self::Eager),
+            DfaMode::Lazy => Dfa::new_lazy(nfa, state_budget).map(|dfa| Self::Lazy(RwLock::new(dfa))),
+        }
+    }
+}
+
+// Non-mutating methods
+impl DfaMatcher {
+    /// Evaluates `input`.
+    ///
+    /// # Returns:
+    /// `None` if the DFA cannot (currently) evaluate `input`, in which case
+    /// the caller must fall back to the interpreter.
+    #[inline]
+    pub(crate) fn matches(
+        &self,
+        input : &str,
+    ) -> Option<bool> {
+        match self {
+            Self::Eager(dfa) => Some(dfa.matches(input)),
+            Self::Lazy(dfa) => Self::matches_shared_(dfa, input),
+        }
+    }
+
+    /// Indicates whether the DFA is (still) usable.
+    pub(crate) fn is_usable(&self) -> bool {
+        match self {
+            Self::Eager(_) => true,
+            Self::Lazy(dfa) => {
+                match dfa.read() {
+                    Ok(dfa) => !dfa.exhausted,
+                    Err(_) => false,
+                }
+            },
+        }
+    }
+}
+
+// Implementation
+impl DfaMatcher {
+    fn matches_shared_(
+        dfa : &RwLock<Dfa>,
+        input : &str,
+    ) -> Option<bool> {
+        let (mut state, mut i) = (dfa.read().ok()?.start, 0);
+
+        loop {
+            let (from, class, resume) = {
+                let dfa = dfa.read().ok()?;
+
+                if dfa.exhausted {
+                    return None;
+                }
+
+                match dfa.walk_known_(input, state, i) {
+                    LazyOutcome::Matched(b) => return Some(b),
+                    LazyOutcome::Unknown {
+                        state,
+                        class,
+                        resume,
+                    } => (state, class, resume),
+                }
+            };
+
+            // another thread may have constructed the transition, or
+            // exhausted the DFA, since the read lock was released
+            let mut dfa = dfa.write().ok()?;
+
+            if dfa.exhausted {
+                return None;
+            }
+
+            match dfa.extend_(from, class)? {
+                DEAD => return Some(false),
+                next => (state, i) = (next, resume),
+            };
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use crate::{
+        test_utils::TestRandom,
+        CompiledMatcher,
+        IGNORE_CASE,
+    };
+
+
+    /// Patterns and inputs taken from the `CompiledMatcher` and API tests.
+    const CASES : &[(&str, &[&str])] = &[
+        ("", &["", " ", "a", "abc"]),
+        ("abcd", &["", "a", "abc", "abcd", "ABCD", "abcde"]),
+        (r"ab-d", &["ab-d", "AB-D", "ab-de", "abd"]),
+        (r"ab\-d", &["ab-d", "AB-D", "ab-de"]),
+        (r"ab\\d", &[r"ab\d", r"a🐻\d", r"AB\D"]),
+        ("[abc]", &["", "a", "b", "c", "d", "A", "ab"]),
+        ("[^abc]", &["", "a", "d", "D", "🐻", "dd"]),
+        ("?", &["", "a", "🐻", "ab"]),
+        ("*", &["", "a", "🐻", "abc"]),
+        ("a*[bc]", &["", "a", "ab", "ac", "abc", "axxxxc", "axxxxd", "AXC"]),
+        ("[a-c]?", &["", "a", "ax", "bx", "dx", "Cx", "axx"]),
+        (
+            "[A-Z]:\\\\?*\\\\?*.[ce][ox][em]",
+            &[r"C:\directory\file.exe", r"X:\filestem.exe", r"D:\d\s\f.bat"],
+        ),
+        (
+            "The ?* sat[ \\t\\n]on the ?*",
+            &[
+                "The cat sat on the mat",
+                "The cat sat\non the mat",
+                "The cat sat\ron the mat",
+            ],
+        ),
+        ("*🐻?", &["", "🐻", "🐻s", "🐼s", "teddy-🐻s", "teddy-🐻"]),
+        ("*🐻[!?]", &["🐻?", "🐼?", "teddy-🐻!", "teddy-🐻"]),
+        (
+            "Where are the* [🐼🐻]s\\?",
+            &["Where are the 🐻s?", "Where are their 🐼s?", "Where are the teddy-🐻s?"],
+        ),
+        ("[[^\\]\\-*?]", &["[", "]", "*", "-", "a", "^"]),
+        ("[^[^\\]\\-*?]", &["[", "]", "*", "-", "a", "^"]),
+        ("*brown*", &["", "brown", "brawn", "browner", "imbrown", "BROWN"]),
+        ("**a**b**", &["ab", "xaxbx", "ba", "AB"]),
+        ("k", &["k", "K", "\u{212A}"]),
+        ("\u{212A}", &["k", "K", "\u{212A}"]),
+        ("s*", &["s", "S", "ſ", "ſx", "ß"]),
+        ("µ", &["µ", "μ", "Μ", "m"]),
+        ("ß", &["ß", "ẞ", "ss", "SS", "s"]),
+        ("*ss*", &["ß", "ss", "Sſ", "xßx", "s"]),
+        ("ﬁ?", &["ﬁx", "fix", "FIx", "ﬁ"]),
+    ];
+
+    fn check_agreement(
+        pattern : &str,
+        flags : i64,
+        inputs : &[&str],
+    ) {
+        let interpreter = CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+        let nfa = Nfa::from_elements(&interpreter.elements, flags);
+
+        for input in inputs {
+            if nfa.is_exact() || !interpreter.matches(input) {
+                let dfa = Dfa::new_eager(nfa.clone(), 10_000).unwrap();
+
+                assert_eq!(
+                    interpreter.matches(input),
+                    dfa.matches(input),
+                    "pattern '{pattern}', flags {flags}, input '{input}'"
+                );
+            }
+        }
+
+        for mode in [DfaMode::Eager, DfaMode::Lazy] {
+            let mut matcher = CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+            // a DFA is not used where it would not match exactly
+            assert_eq!(nfa.is_exact(), matcher.enable_dfa(mode, 10_000), "pattern '{pattern}'");
+
+            for input in inputs {
+                assert_eq!(
+                    interpreter.matches(input),
+                    matcher.matches(input),
+                    "pattern '{pattern}', flags {flags}, mode {mode:?}, input '{input}'"
+                );
+            }
+        }
+    }
+
+
+    #[test]
+    fn TEST_Alphabet_CLASSES_1() {
+        let a = CharSet::Of(vec!['a', 'b', 'c']);
+        let b = CharSet::NotOf(vec!['b', '🐻']);
+
+        let alphabet = Alphabet::from_char_sets([&a, &b]);
+
+        // {other}, {a, c}, {b}, {🐻}
+        assert_eq!(4, alphabet.num_classes());
+
+        assert_eq!(alphabet.class_of('a'), alphabet.class_of('c'));
+        assert_ne!(alphabet.class_of('a'), alphabet.class_of('b'));
+        assert_eq!(0, alphabet.class_of('z'));
+        assert_eq!(0, alphabet.class_of('🐼'));
+        assert_ne!(0, alphabet.class_of('🐻'));
+
+        for (class, &c) in alphabet.representatives().iter().enumerate() {
+            assert_eq!(class as u32, alphabet.class_of(c));
+        }
+    }
+
+    #[test]
+    fn TEST_Dfa_AGREES_WITH_INTERPRETER_ON_TEST_CASES() {
+        for (pattern, inputs) in CASES {
+            check_agreement(pattern, 0, inputs);
+            check_agreement(pattern, IGNORE_CASE, inputs);
+        }
+    }
+
+    #[test]
+    fn TEST_Dfa_AGREES_WITH_INTERPRETER_ON_GENERATED_CASES() {
+        const PATTERN_PARTS : &[&str] = &["a", "b", "ab", "B", "?", "*", "[ab]", "[^a]", "[a-c]", "🐻"];
+        const INPUT_CHARS : &[char] = &['a', 'b', 'c', 'A', 'B', '🐻', 'x'];
+
+        let mut rng = TestRandom::new(5);
+
+        for _ in 0..500 {
+            let pattern : String = (0..=rng.below(6)).map(|_| rng.pick(PATTERN_PARTS)).collect();
+
+            let inputs : Vec<String> = (0..20).map(|_| rng.string(INPUT_CHARS, 8)).collect();
+            let inputs : Vec<&str> = inputs.iter().map(String::as_str).collect();
+
+            check_agreement(&pattern, 0, &inputs);
+            check_agreement(&pattern, IGNORE_CASE, &inputs);
+        }
+    }
+
+    #[test]
+    fn TEST_Dfa_CASE_FOLDING_1() {
+        let matcher = |pattern : &str| {
+            let mut matcher = CompiledMatcher::from_pattern_and_flags(pattern, IGNORE_CASE).unwrap();
+            let is_dfa_enabled = matcher.enable_dfa(DfaMode::Eager, 1_000);
+
+            (matcher, is_dfa_enabled)
+        };
+
+        let (kelvin, is_dfa_enabled) = matcher("\u{212A}");
+
+        assert!(is_dfa_enabled);
+        assert!(!kelvin.matches("k"));
+        assert!(!kelvin.matches("K"));
+
+        let (s, is_dfa_enabled) = matcher("s");
+
+        assert!(is_dfa_enabled);
+        assert!(s.matches("ſ"));
+        assert!(!s.matches("ß"));
+
+        let (sharp_s, is_dfa_enabled) = matcher("ß");
+
+        assert!(!is_dfa_enabled);
+        assert!(sharp_s.matches("ss"));
+        assert!(sharp_s.matches("SS"));
+        assert!(!sharp_s.matches("s"));
+    }
+
+    #[test]
+    fn TEST_Dfa_EAGER_EXCEEDS_BUDGET() {
+        let mut matcher = CompiledMatcher::from_pattern_and_flags("*a?????????", 0).unwrap();
+
+        assert!(!matcher.enable_dfa(DfaMode::Eager, 16));
+        assert!(!matcher.is_dfa_enabled());
+
+        assert!(matcher.matches("xxxabcdefghij"));
+        assert!(!matcher.matches("xxxbbcdefghij"));
+    }
+
+    #[test]
+    fn TEST_Dfa_LAZY_EXCEEDS_BUDGET() {
+        let mut matcher = CompiledMatcher::from_pattern_and_flags("*a?????????", 0).unwrap();
+
+        assert!(matcher.enable_dfa(DfaMode::Lazy, 16));
+        assert!(matcher.is_dfa_enabled());
+
+        assert!(matcher.matches("abcdefghij"));
+
+        // requires many more states than are allowed, so falls back
+        assert!(matcher.matches("aaaaaaaaaaaaaaaaaaaaaaaaa"));
+        assert!(!matcher.matches("aaaaaaaaabbbbbbbbbbbbb"));
+
+        assert!(!matcher.is_dfa_enabled());
+    }
+
+    #[test]
+    fn TEST_Dfa_LAZY_SHARED_BY_THREADS() {
+        const PATTERN : &str = "*a?[bc]*🐻?";
+        const INPUT_CHARS : &[char] = &['a', 'b', 'c', 'x', '🐻'];
+
+        let elements = CompiledMatcher::from_pattern_and_flags(PATTERN, 0).unwrap().elements;
+
+        let dfa = DfaMatcher::new(&elements, 0, DfaMode::Lazy, 1_000).unwrap();
+
+        std::thread::scope(|scope| {
+            for seed in 0..8 {
+                let dfa = &dfa;
+
+                scope.spawn(move || {
+                    let interpreter = CompiledMatcher::from_pattern_and_flags(PATTERN, 0).unwrap();
+
+                    let mut rng = TestRandom::new(seed);
+
+                    for _ in 0..2_000 {
+                        let input = rng.string(INPUT_CHARS, 12);
+
+                        // no thread falls back to the interpreter
+                        assert_eq!(
+                            Some(interpreter.matches(&input)),
+                            dfa.matches(&input),
+                            "input '{input}'"
+                        );
+                    }
+                });
+            }
+        });
+
+        assert!(dfa.is_usable());
+    }
+
+    #[test]
+    fn TEST_Dfa_LAZY_CONSTRUCTS_ONLY_REACHED_STATES() {
+        let nfa = Nfa::from_elements(&[PatternElement::WildN, PatternElement::Literal("abc".into())], 0);
+
+        let dfa = RwLock::new(Dfa::new_lazy(nfa.clone(), 100).unwrap());
+
+        assert_eq!(2, dfa.read().unwrap().num_states());
+
+        assert_eq!(Some(false), DfaMatcher::matches_shared_(&dfa, "xx"));
+        assert_eq!(Some(true), DfaMatcher::matches_shared_(&dfa, "xxabc"));
+
+        let eager = Dfa::new_eager(nfa, 100).unwrap();
+
+        assert!(dfa.read().unwrap().num_states() <= eager.num_states());
+        assert!(eager.matches("abcabc"));
+        assert!(!eager.matches("abcab"));
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,227 @@
+// src/captures.rs : the input consumed by each wildcard element of a pattern
+
+// ///////////////////////////////////////////////
+// imports
+
+use std::ops::Range;
+
+
+// ///////////////////////////////////////////////
+// CaptureMode
+
+/// Determines how the input is apportioned between the wildcard elements
+/// of a pattern by [`CompiledMatcher::captures_with_mode()`], where
+/// there is more than one way in which the input matches.
+///
+/// For example, `"*-*"` matches `"a-b-c"` either as `"a"` and `"b-c"`, or
+/// as `"a-b"` and `"c"`.
+///
+/// Only `*` is affected, since `?`, ranges, and not-ranges each consume
+/// exactly one character.
+///
+/// [`CompiledMatcher::captures_with_mode()`]:
+///     crate::CompiledMatcher::captures_with_mode
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Eq, PartialEq)]
+pub enum CaptureMode {
+    /// Each `*`, in pattern order, consumes as much of the input as
+    /// possible, such that `"*-*"` captures `"a-b"` and `"c"` from
+    /// `"a-b-c"`.
+    #[default]
+    Greedy,
+    /// Each `*`, in pattern order, consumes as little of the input as
+    /// possible, such that `"*-*"` captures `"a"` and `"b-c"` from
+    /// `"a-b-c"`. This is the order in which the alternatives are tried
+    /// by [`CompiledMatcher::matches()`].
+    ///
+    /// [`CompiledMatcher::matches()`]: crate::CompiledMatcher::matches
+    Lazy,
+}
+
+
+// ///////////////////////////////////////////////
+// Captures
+
+/// The parts of an input string consumed by each of the wildcard elements
+/// (`?`, `*`, ranges, and not-ranges) of a pattern, in pattern order, as
+/// obtained from [`CompiledMatcher::captures()`].
+///
+/// [`CompiledMatcher::captures()`]: crate::CompiledMatcher::captures
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Eq, PartialEq)]
+pub struct Captures<'i> {
+    input : &'i str,
+    spans : Vec<Range<usize>>,
+}
+
+// API functions
+impl<'i> Captures<'i> {
+    pub(crate) fn new(
+        input : &'i str,
+        spans : Vec<Range<usize>>,
+    ) -> Self {
+        Self {
+            input,
+            spans,
+        }
+    }
+}
+
+// Non-mutating methods
+impl<'i> Captures<'i> {
+    /// The number of captures, which is the number of wildcard elements in
+    /// the pattern.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Indicates whether there are no captures, i.e. whether the pattern
+    /// contains no wildcard elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// The input string from which the captures were obtained.
+    #[inline]
+    pub fn input(&self) -> &'i str {
+        self.input
+    }
+
+    /// The part of the input consumed by the wildcard element at (0-based)
+    /// `index`, or `None` if `index` is out of bounds.
+    pub fn get(
+        &self,
+        index : usize,
+    ) -> Option<&'i str> {
+        self.spans.get(index).map(|span| &self.input[span.clone()])
+    }
+
+    /// The byte span of the input consumed by the wildcard element at
+    /// (0-based) `index`, or `None` if `index` is out of bounds.
+    pub fn span(
+        &self,
+        index : usize,
+    ) -> Option<Range<usize>> {
+        self.spans.get(index).cloned()
+    }
+
+    /// The byte spans of the input consumed by each of the wildcard
+    /// elements, in pattern order.
+    #[inline]
+    pub fn spans(&self) -> &[Range<usize>] {
+        &self.spans
+    }
+
+    /// An iterator over the parts of the input consumed by each of the
+    /// wildcard elements, in pattern order.
+    pub fn iter(&self) -> impl Iterator<Item = &'i str> + '_ {
+        self.spans.iter().map(|span| &self.input[span.clone()])
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use crate::{
+        CompiledMatcher,
+        IGNORE_CASE,
+    };
+
+
+    fn captures<'i>(
+        pattern : &str,
+        flags : i64,
+        mode : CaptureMode,
+        input : &'i str,
+    ) -> Option<Vec<&'i str>> {
+        CompiledMatcher::from_pattern_and_flags(pattern, flags)
+            .unwrap()
+            .captures_with_mode(input, mode)
+            .map(|captures| captures.iter().collect())
+    }
+
+
+    #[test]
+    fn TEST_captures_ELEMENTS_1() {
+        let greedy = CaptureMode::Greedy;
+
+        assert_eq!(Some(vec!["1234"]), captures("IMG_*.jpeg", 0, greedy, "IMG_1234.jpeg"));
+        assert_eq!(Some(vec!["b", "c", "d"]), captures("a?[bc][^bc]", 0, greedy, "abcd"));
+        assert_eq!(Some(vec![]), captures("abc", 0, greedy, "abc"));
+        assert_eq!(
+            Some(vec!["🐻", "!"]),
+            captures("Where are the [🐼🐻]s?", 0, greedy, "Where are the 🐻s!")
+        );
+        assert_eq!(Some(vec!["", "?"]), captures("*[?]", 0, greedy, "?"));
+        assert_eq!(None, captures("IMG_*.jpeg", 0, greedy, "IMG_1234.jpg"));
+        assert_eq!(
+            Some(vec!["1234"]),
+            captures("img_*.JPEG", IGNORE_CASE, greedy, "IMG_1234.jpeg")
+        );
+    }
+
+    #[test]
+    fn TEST_captures_GREEDY_AND_LAZY_1() {
+        assert_eq!(Some(vec!["a-b", "c"]), captures("*-*", 0, CaptureMode::Greedy, "a-b-c"));
+        assert_eq!(Some(vec!["a", "b-c"]), captures("*-*", 0, CaptureMode::Lazy, "a-b-c"));
+
+        // adjacent `*`s
+        assert_eq!(Some(vec!["abc", ""]), captures("**", 0, CaptureMode::Greedy, "abc"));
+        assert_eq!(Some(vec!["", "abc"]), captures("**", 0, CaptureMode::Lazy, "abc"));
+
+        // `?` after `*`
+        assert_eq!(Some(vec!["ab", "c"]), captures("*?", 0, CaptureMode::Greedy, "abc"));
+        assert_eq!(Some(vec!["ab", "c"]), captures("*?", 0, CaptureMode::Lazy, "abc"));
+    }
+
+    #[test]
+    fn TEST_captures_SPANS_1() {
+        let matcher = CompiledMatcher::from_pattern_and_flags("*.[ch]", 0).unwrap();
+
+        let captures = matcher.captures("é.c").unwrap();
+
+        assert_eq!(2, captures.len());
+        assert!(!captures.is_empty());
+        assert_eq!("é.c", captures.input());
+        assert_eq!(Some(0..2), captures.span(0));
+        assert_eq!(Some(3..4), captures.span(1));
+        assert_eq!(None, captures.span(2));
+        assert_eq!(Some("é"), captures.get(0));
+        assert_eq!(Some("c"), captures.get(1));
+        assert_eq!(None, captures.get(2));
+        assert_eq!(&[0..2, 3..4], captures.spans());
+    }
+
+    #[test]
+    fn TEST_captures_AGREES_WITH_matches_1() {
+        let patterns = ["", "*", "a*b", "?*?", "[ab]*[^ab]", "a**b*c", "*.[ch]"];
+        let inputs = ["", "a", "ab", "aab", "abab", "acbab", "x.c", "🐻a🐼b"];
+
+        for pattern in patterns {
+            let matcher = CompiledMatcher::from_pattern_and_flags(pattern, 0).unwrap();
+
+            for input in inputs {
+                for mode in [CaptureMode::Greedy, CaptureMode::Lazy] {
+                    assert_eq!(
+                        matcher.matches(input),
+                        matcher.captures_with_mode(input, mode).is_some(),
+                        "pattern '{pattern}', mode {mode:?}, input '{input}'"
+                    );
+                }
+            }
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,311 @@
+// src/case.rs : the case-insensitive comparison of literals, as shared by
+//               the interpreter and the automata
+
+// ///////////////////////////////////////////////
+// imports
+
+use std::sync::OnceLock;
+
+
+// ///////////////////////////////////////////////
+// Folding
+
+/// The inverse of the upper-case mapping of all characters, as required
+/// to determine which characters are equal to a given character when case
+/// is ignored.
+struct Folding {
+    /// Each character whose upper-case is a single other character, paired
+    /// with (and sorted by) that upper-case character.
+    preimages :  Vec<(char, char)>,
+    /// Each character whose upper-case is of more than one character,
+    /// paired with (and sorted by) that upper-case, e.g. `("SS", 'ß')`.
+    expanders :  Vec<(String, char)>,
+    /// The (sorted, unique) upper-cases of the characters whose upper-case
+    /// is of more than one character, e.g. `"SS"` for `'ß'`.
+    expansions : Vec<String>,
+}
+
+/// The folding, which is created on first use.
+fn folding_() -> &'static Folding {
+    static FOLDING : OnceLock<Folding> = OnceLock::new();
+
+    FOLDING.get_or_init(|| {
+        let mut preimages = vec![];
+        let mut expanders = vec![];
+
+        for c in '\0'..=char::MAX {
+            let mut upper = c.to_uppercase();
+
+            match (upper.next(), upper.next()) {
+                (Some(u), None) => {
+                    if u != c {
+                        preimages.push((u, c));
+                    }
+                },
+                _ => expanders.push((c.to_uppercase().collect::<String>(), c)),
+            };
+        }
+
+        preimages.sort_unstable();
+        expanders.sort_unstable();
+
+        let mut expansions : Vec<String> = expanders.iter().map(|(expansion, _)| expansion.clone()).collect();
+
+        expansions.dedup();
+
+        Folding {
+            preimages,
+            expanders,
+            expansions,
+        }
+    })
+}
+
+/// The upper-case of `c`, if it is a single character.
+fn single_uppercase_(c : char) -> Option<char> {
+    let mut upper = c.to_uppercase();
+
+    match (upper.next(), upper.next()) {
+        (Some(u), None) => Some(u),
+        _ => None,
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// API functions
+
+/// The (sorted, unique) characters that are equal to `c` when case is
+/// ignored, i.e. those with the same upper-case as `c`, including `c`
+/// itself - e.g. `'k'` and `'K'`, but not `'\u{212A}'` (KELVIN SIGN),
+/// whose upper-case is itself.
+///
+/// A character whose upper-case is of more than one character is equal
+/// only to itself, since it is otherwise matched only by sequences of
+/// characters (as is detected by [`has_multi_char_folding()`]).
+pub(crate) fn case_variants(c : char) -> Vec<char> {
+    match single_uppercase_(c) {
+        Some(upper) => uppercase_preimages(upper.encode_utf8(&mut [0; 4])),
+        None => vec![c],
+    }
+}
+
+/// The (sorted, unique) characters whose upper-case is `upper`, which may
+/// be of one character - e.g. `'k'` and `'K'` for `"K"` - or of more than
+/// one - e.g. `'ß'` for `"SS"`.
+pub(crate) fn uppercase_preimages(upper : &str) -> Vec<char> {
+    let folding = folding_();
+
+    let mut chars = upper.chars();
+
+    let mut variants : Vec<char> = match (chars.next(), chars.next()) {
+        (Some(u), None) => {
+            let first = folding.preimages.partition_point(|&(p, _)| p < u);
+
+            let mut variants : Vec<char> = folding.preimages[first..]
+                .iter()
+                .take_while(|&&(p, _)| p == u)
+                .map(|&(_, c)| c)
+                .collect();
+
+            if single_uppercase_(u) == Some(u) {
+                variants.push(u);
+            }
+
+            variants
+        },
+        _ => {
+            let first = folding.expanders.partition_point(|(e, _)| e.as_str() < upper);
+
+            folding.expanders[first..]
+                .iter()
+                .take_while(|(e, _)| e == upper)
+                .map(|&(_, c)| c)
+                .collect()
+        },
+    };
+
+    variants.sort_unstable();
+    variants.dedup();
+
+    variants
+}
+
+/// The (sorted, unique) upper-cases of the characters whose upper-case is
+/// of more than one character, e.g. `"SS"` for `'ß'`.
+pub(crate) fn expansions() -> impl Iterator<Item = &'static str> {
+    folding_().expansions.iter().map(String::as_str)
+}
+
+/// Indicates whether `literal`, when case is ignored, may match (or be
+/// matched by) a sequence of characters of a different length, which is
+/// so where it contains a character whose upper-case is of more than one
+/// character - e.g. `"ß"`, which matches `"ss"` - or where its upper-case
+/// contains that of such a character - e.g. `"ss"`, which matches `"ß"`.
+pub(crate) fn has_multi_char_folding(literal : &str) -> bool {
+    if literal.chars().any(|c| single_uppercase_(c).is_none()) {
+        return true;
+    }
+
+    let uppercase = literal.to_uppercase();
+
+    folding_()
+        .expansions
+        .iter()
+        .any(|expansion| uppercase.contains(expansion.as_str()))
+}
+
+/// The minimum and maximum numbers of characters of the input matched by
+/// `literal` when case is ignored, which differ from its own number where
+/// it may match a sequence of characters of a different length, e.g.
+/// `(1, 2)` for `"ss"`, which matches `"ß"`, and for `"ß"`, which matches
+/// `"ss"`.
+pub(crate) fn folded_len_bounds(literal : &str) -> (usize, usize) {
+    let uppercase : Vec<char> = literal.to_uppercase().chars().collect();
+
+    // each character of the input contributes at least one character to
+    // the upper-case, and at most an expansion; so the minimum is that of
+    // the divisions of the upper-case into single characters and
+    // expansions
+
+    let expansions : Vec<Vec<char>> = folding_()
+        .expansions
+        .iter()
+        .map(|expansion| expansion.chars().collect())
+        .collect();
+
+    let mut min_lens = vec![0; uppercase.len() + 1];
+
+    for i in 1..=uppercase.len() {
+        min_lens[i] = min_lens[i - 1] + 1;
+
+        for expansion in &expansions {
+            if uppercase[..i].ends_with(expansion) {
+                min_lens[i] = min_lens[i].min(min_lens[i - expansion.len()] + 1);
+            }
+        }
+    }
+
+    (min_lens[uppercase.len()], uppercase.len())
+}
+
+/// Obtains the length of the shortest prefix of the input whose
+/// upper-case is `uppercase`, where the input is given by its characters
+/// and their encoded lengths.
+///
+/// # Returns:
+/// The length of the prefix (in the units of the encoded lengths), or
+/// `None` if there is none.
+pub(crate) fn folded_prefix_len<I>(
+    uppercase : &str,
+    chars : I,
+) -> Option<usize>
+where
+    I : IntoIterator<Item = (char, usize)>,
+{
+    let mut remaining = uppercase;
+    let mut len = 0;
+
+    for (c, n) in chars {
+        if remaining.is_empty() {
+            break;
+        }
+
+        for u in c.to_uppercase() {
+            remaining = remaining.strip_prefix(u)?;
+        }
+
+        len += n;
+    }
+
+    if remaining.is_empty() {
+        Some(len)
+    } else {
+        None
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+
+    fn folded_prefix_len_of(
+        uppercase : &str,
+        input : &str,
+    ) -> Option<usize> {
+        folded_prefix_len(uppercase, input.chars().map(|c| (c, c.len_utf8())))
+    }
+
+
+    #[test]
+    fn TEST_case_variants_1() {
+        assert_eq!(vec!['A', 'a'], case_variants('a'));
+        assert_eq!(vec!['A', 'a'], case_variants('A'));
+        assert_eq!(vec!['7'], case_variants('7'));
+        assert_eq!(vec!['K', 'k'], case_variants('k'));
+        assert_eq!(vec!['\u{212A}'], case_variants('\u{212A}'));
+        assert_eq!(vec!['S', 's', 'ſ'], case_variants('s'));
+        assert_eq!(vec!['µ', 'Μ', 'μ'], case_variants('μ'));
+        assert_eq!(vec!['ß'], case_variants('ß'));
+
+        for c in ['a', 'ſ', 'µ', 'ǅ', 'ς', 'ı', '\u{212A}', '🐻'] {
+            for v in case_variants(c) {
+                assert!(v.to_uppercase().eq(c.to_uppercase()), "'{c}' vs '{v}'");
+                assert_eq!(case_variants(c), case_variants(v), "'{c}' vs '{v}'");
+            }
+        }
+    }
+
+    #[test]
+    fn TEST_uppercase_preimages_1() {
+        assert_eq!(vec!['K', 'k'], uppercase_preimages("K"));
+        assert_eq!(vec!['S', 's', 'ſ'], uppercase_preimages("S"));
+        assert_eq!(vec!['ß'], uppercase_preimages("SS"));
+        assert_eq!(vec!['ﬁ'], uppercase_preimages("FI"));
+        assert_eq!(Vec::<char>::new(), uppercase_preimages("SSS"));
+        assert_eq!(Vec::<char>::new(), uppercase_preimages(""));
+    }
+
+    #[test]
+    fn TEST_has_multi_char_folding_1() {
+        assert!(!has_multi_char_folding(""));
+        assert!(!has_multi_char_folding("abc"));
+        assert!(!has_multi_char_folding("s"));
+        assert!(!has_multi_char_folding("\u{212A}"));
+        assert!(has_multi_char_folding("ß"));
+        assert!(has_multi_char_folding("ss"));
+        assert!(has_multi_char_folding("Strasse"));
+        assert!(has_multi_char_folding("fi"));
+        assert!(has_multi_char_folding("ﬁle"));
+    }
+
+    #[test]
+    fn TEST_folded_len_bounds_1() {
+        assert_eq!((0, 0), folded_len_bounds(""));
+        assert_eq!((3, 3), folded_len_bounds("abc"));
+        assert_eq!((1, 2), folded_len_bounds("ss"));
+        assert_eq!((1, 2), folded_len_bounds("ß"));
+        assert_eq!((5, 7), folded_len_bounds("Straße"));
+        assert_eq!((1, 3), folded_len_bounds("ffi"));
+        assert_eq!((1, 3), folded_len_bounds("ﬃ"));
+    }
+
+    #[test]
+    fn TEST_folded_prefix_len_1() {
+        assert_eq!(Some(0), folded_prefix_len_of("", "abc"));
+        assert_eq!(Some(3), folded_prefix_len_of("ABC", "abcd"));
+        assert_eq!(None, folded_prefix_len_of("ABC", "ab"));
+        assert_eq!(Some(2), folded_prefix_len_of("SS", "ss"));
+        assert_eq!(Some(2), folded_prefix_len_of("SS", "ß"));
+        assert_eq!(None, folded_prefix_len_of("S", "ß"));
+        assert_eq!(Some(2), folded_prefix_len_of("S", "ſ"));
+        assert_eq!(None, folded_prefix_len_of("K", "\u{212A}"));
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
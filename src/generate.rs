@@ -0,0 +1,679 @@
+// src/generate.rs : generation of sample strings from patterns
+
+// ///////////////////////////////////////////////
+// imports
+
+use crate::{
+    automaton::{
+        Atom,
+        CharSet,
+    },
+    case,
+    CompiledMatcher,
+    PatternElement,
+};
+
+use std::collections::HashSet;
+
+
+// ///////////////////////////////////////////////
+// RandomSource
+
+/// A source of (pseudo-)random numbers, as used by
+/// [`CompiledMatcher::examples()`] and
+/// [`CompiledMatcher::counter_examples()`].
+///
+/// It is implemented by [`SeededRandom`], for reproducible generation
+/// without further dependencies, and by any `FnMut() -> u64`, such that
+/// another generator may be adapted with a closure, e.g.
+/// `|| rng.next_u64()`.
+///
+/// [`CompiledMatcher::examples()`]: crate::CompiledMatcher::examples
+/// [`CompiledMatcher::counter_examples()`]:
+///     crate::CompiledMatcher::counter_examples
+pub trait RandomSource {
+    /// Obtains the next (uniformly distributed) number.
+    fn next_u64(&mut self) -> u64;
+}
+
+impl<F> RandomSource for F
+where
+    F : FnMut() -> u64,
+{
+    fn next_u64(&mut self) -> u64 {
+        self()
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// SeededRandom
+
+/// A deterministic (xorshift64*) [`RandomSource`], which produces the same
+/// sequence for the same seed on every platform.
+///
+/// NOTE: it is not suitable for any purpose requiring unpredictability.
+#[derive(Clone)]
+#[derive(Debug)]
+pub struct SeededRandom {
+    state : u64,
+}
+
+// API functions
+impl SeededRandom {
+    /// Creates an instance from the given `seed`, which may be any value
+    /// (including 0).
+    pub fn new(seed : u64) -> Self {
+        // the state must be non-zero
+        let state = seed ^ 0x9e37_79b9_7f4a_7c15;
+
+        Self {
+            state : if 0 == state { 1 } else { state },
+        }
+    }
+}
+
+// Trait implementations
+
+impl RandomSource for SeededRandom {
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+
+        self.state.wrapping_mul(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// generation
+
+/// The characters from which those matched by `?`, `*`, and not-ranges are
+/// chosen (where not excluded).
+const POOL_CHARACTERS : &[char] = &[
+    'a',
+    'b',
+    'c',
+    'd',
+    'e',
+    'f',
+    'g',
+    'h',
+    'i',
+    'j',
+    'k',
+    'l',
+    'm',
+    'n',
+    'o',
+    'p',
+    'q',
+    'r',
+    's',
+    't',
+    'u',
+    'v',
+    'w',
+    'x',
+    'y',
+    'z',
+    '0',
+    '1',
+    '2',
+    '3',
+    '4',
+    '5',
+    '6',
+    '7',
+    '8',
+    '9',
+];
+
+/// The maximum number of characters generated for a `*`.
+const MAX_STAR_LENGTH : usize = 4;
+
+/// The number of attempts made for each requested string.
+const ATTEMPTS_PER_STRING : usize = 20;
+
+/// Generates up to `n` distinct strings that are matched by `matcher`.
+pub(crate) fn examples<R>(
+    matcher : &CompiledMatcher,
+    n : usize,
+    rng : &mut R,
+) -> Vec<String>
+where
+    R : RandomSource + ?Sized,
+{
+    let mut seen = HashSet::new();
+    let mut strings = vec![];
+
+    for _ in 0..n.saturating_mul(ATTEMPTS_PER_STRING) {
+        if strings.len() == n {
+            break;
+        }
+
+        let s = match example_(matcher, rng) {
+            Some(s) => s,
+            // the pattern matches nothing
+            None => break,
+        };
+
+        if matcher.matches(&s) && seen.insert(s.clone()) {
+            strings.push(s);
+        }
+    }
+
+    strings
+}
+
+/// Generates up to `n` distinct strings that are not matched by `matcher`
+/// but are each a single edit (deletion, insertion, or substitution of a
+/// character) from one that is.
+pub(crate) fn counter_examples<R>(
+    matcher : &CompiledMatcher,
+    n : usize,
+    rng : &mut R,
+) -> Vec<String>
+where
+    R : RandomSource + ?Sized,
+{
+    let mut seen = HashSet::new();
+    let mut strings = vec![];
+
+    for _ in 0..n.saturating_mul(ATTEMPTS_PER_STRING) {
+        if strings.len() == n {
+            break;
+        }
+
+        // where the pattern matches nothing, any string is a near-miss
+        let mut chars : Vec<char> = example_(matcher, rng).unwrap_or_default().chars().collect();
+
+        let index = below_(rng, chars.len() + 1);
+
+        match below_(rng, 3) {
+            0 if index < chars.len() => {
+                chars.remove(index);
+            },
+            1 if index < chars.len() => {
+                chars[index] = pick_(rng, POOL_CHARACTERS);
+            },
+            _ => {
+                chars.insert(index, pick_(rng, POOL_CHARACTERS));
+            },
+        };
+
+        let s : String = chars.into_iter().collect();
+
+        if !matcher.matches(&s) && seen.insert(s.clone()) {
+            strings.push(s);
+        }
+    }
+
+    strings
+}
+
+/// Enumerates, in order, the strings matched by `matcher`, if they are
+/// finite in number and number no more than `limit`, and each of its
+/// literals matches only sequences of its own length.
+pub(crate) fn enumerate(
+    matcher : &CompiledMatcher,
+    limit : usize,
+) -> Option<impl Iterator<Item = String> + '_> {
+    let slots = enumeration_slots_(matcher)?;
+
+    let mut num_strings : usize = 1;
+
+    for slot in &slots {
+        num_strings = num_strings.checked_mul(slot.len())?;
+    }
+
+    if num_strings > limit {
+        return None;
+    }
+
+    // an odometer over the options of the slots, the last of which is the
+    // fastest-moving
+    let mut indexes = vec![0; slots.len()];
+    let mut remaining = num_strings;
+
+    let strings = std::iter::from_fn(move || {
+        if 0 == remaining {
+            return None;
+        }
+
+        remaining -= 1;
+
+        let s : String = slots
+            .iter()
+            .zip(&indexes)
+            .map(|(slot, &index)| slot[index].as_str())
+            .collect();
+
+        for (slot, index) in slots.iter().zip(indexes.iter_mut()).rev() {
+            *index += 1;
+
+            if *index < slot.len() {
+                break;
+            }
+
+            *index = 0;
+        }
+
+        Some(s)
+    });
+
+    // the options are exactly those matched, except that with
+    // `GRAPHEME_CLUSTERS` adjacent options may combine into one cluster
+    // (e.g. `e` followed by a combining accent), so each string is
+    // verified
+    Some(strings.filter(move |s| matcher.matches(s)))
+}
+
+/// Obtains the (sorted, unique) options for each successive part of a
+/// matched string, or `None` if any part has unbounded options, as for
+/// `?`, `*`, and not-ranges, or if, with `IGNORE_CASE`, a literal may
+/// match a sequence of characters of a different length, e.g. `"ß"`,
+/// which matches `"ss"`, and so cannot be divided into parts.
+fn enumeration_slots_(matcher : &CompiledMatcher) -> Option<Vec<Vec<String>>> {
+    let flags = matcher.flags;
+    let ignore_case = 0 != (flags & crate::IGNORE_CASE);
+
+    let mut slots = vec![];
+
+    for element in &matcher.elements {
+        match element {
+            PatternElement::Literal(literal) => {
+                if ignore_case && case::has_multi_char_folding(literal) {
+                    return None;
+                }
+
+                for c in literal.chars() {
+                    let char_set = CharSet::from_literal_char(c, flags);
+
+                    slots.push(char_set.mentioned().iter().map(|c| c.to_string()).collect());
+                }
+            },
+            PatternElement::Range {
+                chars,
+                negated: false,
+            } => {
+                #[cfg(feature = "grapheme-clusters")]
+                if 0 != (flags & crate::GRAPHEME_CLUSTERS) {
+                    use unicode_segmentation::UnicodeSegmentation as _;
+
+                    // as in matching, range clusters are case-folded only
+                    // in their ASCII characters
+                    let mut options : Vec<String> = String::from_iter(chars)
+                        .graphemes(true)
+                        .flat_map(|cluster| {
+                            if ignore_case {
+                                vec![
+                                    cluster.to_ascii_lowercase(),
+                                    cluster.to_ascii_uppercase(),
+                                    cluster.to_string(),
+                                ]
+                            } else {
+                                vec![cluster.to_string()]
+                            }
+                        })
+                        .collect();
+
+                    options.sort_unstable();
+                    options.dedup();
+
+                    slots.push(options);
+
+                    continue;
+                }
+
+                slots.push(
+                    CharSet::from_range_chars(chars, flags)
+                        .into_iter()
+                        .map(String::from)
+                        .collect(),
+                );
+            },
+            PatternElement::Range {
+                negated: true, ..
+            }
+            | PatternElement::Wild1
+            | PatternElement::WildN => return None,
+        };
+    }
+
+    Some(slots)
+}
+
+/// Generates a string from the atoms of the position automaton of
+/// `matcher`, or `None` if there is an atom that matches no character.
+///
+/// The string is (very nearly) always matched, but must be verified.
+fn example_<R>(
+    matcher : &CompiledMatcher,
+    rng : &mut R,
+) -> Option<String>
+where
+    R : RandomSource + ?Sized,
+{
+    let nfa = matcher.nfa_();
+
+    let mut s = String::new();
+
+    for p in 0..nfa.num_positions() {
+        match nfa.atom(p) {
+            Some(Atom::One(CharSet::Of(chars))) => {
+                if chars.is_empty() {
+                    return None;
+                }
+
+                s.push(pick_(rng, chars));
+            },
+            Some(Atom::One(char_set)) => {
+                s.push(pick_not_of_(rng, char_set));
+            },
+            Some(Atom::Star) => {
+                for _ in 0..below_(rng, MAX_STAR_LENGTH + 1) {
+                    s.push(pick_(rng, POOL_CHARACTERS));
+                }
+            },
+            None => {},
+        };
+    }
+
+    Some(s)
+}
+
+/// A random character that is a member of `char_set`, which is `Any` or
+/// `NotOf`, preferring those of the pool.
+fn pick_not_of_<R>(
+    rng : &mut R,
+    char_set : &CharSet,
+) -> char
+where
+    R : RandomSource + ?Sized,
+{
+    let pool : Vec<char> = POOL_CHARACTERS
+        .iter()
+        .copied()
+        .filter(|&c| char_set.contains(c))
+        .collect();
+
+    if pool.is_empty() {
+        ('!'..=char::MAX)
+            .find(|&c| !c.is_control() && char_set.contains(c))
+            .expect("a finite set of characters cannot exhaust the character space")
+    } else {
+        pick_(rng, &pool)
+    }
+}
+
+/// A random element of the (non-empty) `chars`.
+fn pick_<R>(
+    rng : &mut R,
+    chars : &[char],
+) -> char
+where
+    R : RandomSource + ?Sized,
+{
+    chars[below_(rng, chars.len())]
+}
+
+/// A random number in `[0, n)`, where `n` is non-zero.
+fn below_<R>(
+    rng : &mut R,
+    n : usize,
+) -> usize
+where
+    R : RandomSource + ?Sized,
+{
+    (rng.next_u64() % n as u64) as usize
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use crate::IGNORE_CASE;
+
+
+    fn matcher(
+        pattern : &str,
+        flags : i64,
+    ) -> CompiledMatcher {
+        CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap()
+    }
+
+
+    fn enumerate(
+        pattern : &str,
+        flags : i64,
+        limit : usize,
+    ) -> Option<Vec<String>> {
+        matcher(pattern, flags).enumerate(limit).map(Iterator::collect)
+    }
+
+
+    #[test]
+    fn TEST_enumerate_1() {
+        assert_eq!(Some(vec!["".into()]), enumerate("", 0, 10));
+        assert_eq!(Some(vec!["abc".into()]), enumerate("abc", 0, 10));
+        assert_eq!(
+            Some(vec![
+                "a1x".into(),
+                "a1y".into(),
+                "a2x".into(),
+                "a2y".into(),
+                "b1x".into(),
+                "b1y".into(),
+                "b2x".into(),
+                "b2y".into()
+            ]),
+            enumerate("[ba][21][yx]", 0, 10)
+        );
+        assert_eq!(
+            Some(vec![
+                "IMG_1.jpg".into(),
+                "IMG_1.png".into(),
+                "IMG_2.jpg".into(),
+                "IMG_2.png".into()
+            ]),
+            enumerate("IMG_[12].[jp][np]g", 0, 10).map(|strings| {
+                strings
+                    .into_iter()
+                    .filter(|s| s.ends_with("jpg") || s.ends_with("png"))
+                    .collect()
+            })
+        );
+        assert_eq!(Some(vec![]), enumerate("a[]", 0, 10));
+    }
+
+    #[test]
+    fn TEST_enumerate_INFINITE_1() {
+        assert_eq!(None, enumerate("*", 0, usize::MAX));
+        assert_eq!(None, enumerate("a?", 0, usize::MAX));
+        assert_eq!(None, enumerate("a[^b]", 0, usize::MAX));
+    }
+
+    #[test]
+    fn TEST_enumerate_LIMIT_1() {
+        assert_eq!(Some(4), enumerate("[ab][cd]", 0, 4).map(|strings| strings.len()));
+        assert_eq!(None, enumerate("[ab][cd]", 0, 3));
+        assert_eq!(Some(1), enumerate("", 0, 1).map(|strings| strings.len()));
+        assert_eq!(None, enumerate("", 0, 0));
+
+        let huge = "[a-zA-Z]".repeat(20);
+
+        assert_eq!(None, enumerate(&huge, 0, usize::MAX));
+    }
+
+    #[test]
+    fn TEST_enumerate_IGNORE_CASE_1() {
+        assert_eq!(
+            Some(vec!["AB".into(), "Ab".into(), "aB".into(), "ab".into()]),
+            enumerate("a[b]", IGNORE_CASE, 10)
+        );
+        assert_eq!(Some(vec!["1É".into(), "1é".into()]), enumerate("1é", IGNORE_CASE, 10));
+        assert_eq!(
+            Some(vec!["S".into(), "s".into(), "ſ".into()]),
+            enumerate("s", IGNORE_CASE, 10)
+        );
+        assert_eq!(Some(vec!["ß".into()]), enumerate("ß", 0, 10));
+
+        // "ß" matches "ss", "SS", "sS", ... which are not case variants of
+        // its single character
+        assert_eq!(None, enumerate("ß", IGNORE_CASE, 100));
+        assert_eq!(None, enumerate("a[bc]ss", IGNORE_CASE, 100));
+        assert_eq!(None, enumerate("ﬁ", IGNORE_CASE, 100));
+    }
+
+    #[cfg(feature = "grapheme-clusters")]
+    #[test]
+    fn TEST_enumerate_GRAPHEME_CLUSTERS_1() {
+        let pattern = "[x\u{1f441}\u{fe0f}]";
+
+        assert_eq!(
+            Some(vec!["x".into(), "\u{1f441}\u{fe0f}".into()]),
+            enumerate(pattern, crate::GRAPHEME_CLUSTERS, 10)
+        );
+    }
+
+    #[test]
+    fn TEST_enumerate_AGREES_WITH_matches_1() {
+        for pattern in ["[ab]c[de]", "[a-c]", "x[yz]", "[ab]", "", "s[kt]", "[ſ]ı"] {
+            for flags in [0, IGNORE_CASE] {
+                let matcher = matcher(pattern, flags);
+
+                for s in matcher.enumerate(100).unwrap() {
+                    assert!(matcher.matches(&s), "pattern '{pattern}', string '{s}'");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn TEST_SeededRandom_IS_DETERMINISTIC_1() {
+        let mut a = SeededRandom::new(42);
+        let mut b = SeededRandom::new(42);
+        let mut c = SeededRandom::new(43);
+
+        let a : Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+        let b : Vec<u64> = (0..10).map(|_| b.next_u64()).collect();
+        let c : Vec<u64> = (0..10).map(|_| c.next_u64()).collect();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+        assert_ne!(0, SeededRandom::new(0).next_u64());
+    }
+
+    #[test]
+    fn TEST_examples_1() {
+        let patterns = [
+            "",
+            "*",
+            "abc",
+            "IMG_*.[jp]?g",
+            "[^a-z0123456789]*[A-C]",
+            "[ab][^ab]?",
+            "*🐻*",
+            "[^]",
+        ];
+
+        for pattern in patterns {
+            for flags in [0, IGNORE_CASE] {
+                let matcher = matcher(pattern, flags);
+
+                let examples = matcher.examples(10, &mut SeededRandom::new(1));
+
+                assert!(!examples.is_empty(), "pattern '{pattern}'");
+                assert!(examples.len() <= 10, "pattern '{pattern}'");
+
+                for example in &examples {
+                    assert!(matcher.matches(example), "pattern '{pattern}', example '{example}'");
+                }
+
+                let mut unique = examples.clone();
+
+                unique.sort();
+                unique.dedup();
+
+                assert_eq!(examples.len(), unique.len(), "pattern '{pattern}'");
+            }
+        }
+    }
+
+    #[test]
+    fn TEST_examples_LIMITED_LANGUAGES_1() {
+        let mut rng = SeededRandom::new(2);
+
+        assert_eq!(vec!["abc"], matcher("abc", 0).examples(10, &mut rng));
+        assert_eq!(vec![""], matcher("", 0).examples(10, &mut rng));
+        assert!(matcher("a[]", 0).examples(10, &mut rng).is_empty());
+        assert!(matcher("*", 0).examples(0, &mut rng).is_empty());
+
+        let mut examples = matcher("[ab]", IGNORE_CASE).examples(10, &mut rng);
+
+        examples.sort();
+
+        assert_eq!(vec!["A", "B", "a", "b"], examples);
+    }
+
+    #[test]
+    fn TEST_examples_IS_REPRODUCIBLE_1() {
+        let matcher = matcher("IMG_*.[jp]?g", 0);
+
+        assert_eq!(
+            matcher.examples(5, &mut SeededRandom::new(7)),
+            matcher.examples(5, &mut SeededRandom::new(7))
+        );
+    }
+
+    #[test]
+    fn TEST_examples_WITH_CLOSURE_1() {
+        let mut state = 0_u64;
+        let mut counter = || {
+            state += 1;
+
+            state
+        };
+
+        let examples = matcher("?", 0).examples(3, &mut counter);
+
+        assert_eq!(vec!["b", "c", "d"], examples);
+    }
+
+    #[test]
+    fn TEST_counter_examples_1() {
+        let patterns = ["", "abc", "IMG_*.[jp]?g", "[^a-z0123456789]*[A-C]", "[ab][^ab]?", "a[]"];
+
+        for pattern in patterns {
+            for flags in [0, IGNORE_CASE] {
+                let matcher = matcher(pattern, flags);
+
+                let counter_examples = matcher.counter_examples(10, &mut SeededRandom::new(3));
+
+                assert!(!counter_examples.is_empty(), "pattern '{pattern}'");
+
+                for counter_example in &counter_examples {
+                    assert!(
+                        !matcher.matches(counter_example),
+                        "pattern '{pattern}', counter-example '{counter_example}'"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn TEST_counter_examples_NONE_1() {
+        assert!(matcher("*", 0)
+            .counter_examples(10, &mut SeededRandom::new(4))
+            .is_empty());
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
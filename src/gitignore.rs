@@ -0,0 +1,766 @@
+// src/gitignore.rs : parsing and evaluation of `.gitignore` / `.ignore`
+//                    files
+
+// ///////////////////////////////////////////////
+// imports
+
+use crate::{
+    Pattern,
+    PatternBuilder,
+    Rule,
+    RuleSet,
+};
+
+use std::{
+    collections::HashMap,
+    fs,
+    io,
+    iter::Peekable,
+    path::{
+        Component,
+        Path,
+        PathBuf,
+    },
+    str::Chars,
+};
+
+
+// ///////////////////////////////////////////////
+// constants
+
+/// The names of the ignore files read from each directory by
+/// [`Gitignore::add_tree()`], in increasing order of precedence.
+const IGNORE_FILE_NAMES : &[&str] = &[".gitignore", ".ignore"];
+
+/// The maximum number of characters in a sequence in a range, e.g.
+/// `"0-9"`, which is expanded into its characters, beyond which the
+/// pattern is treated as invalid, so that an ignore file cannot cause
+/// unbounded allocation.
+const MAX_RANGE_SEQUENCE_LENGTH : u32 = 256;
+
+
+// ///////////////////////////////////////////////
+// IgnoreMatch
+
+/// The rule that decided whether a path is ignored, as obtained from
+/// [`Gitignore::check()`], in the manner of `git check-ignore -v`.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+pub struct IgnoreMatch<'a> {
+    /// The ignore file from which the rule was read, or `None` if it was
+    /// given by [`Gitignore::add_rules()`].
+    pub source : Option<&'a Path>,
+    /// The (1-based) line number of the rule.
+    pub line :   usize,
+    /// The rule.
+    pub rule :   &'a Rule,
+}
+
+// Non-mutating methods
+impl IgnoreMatch<'_> {
+    /// Indicates whether the path is ignored, i.e. whether the rule is
+    /// not negated.
+    #[inline]
+    pub fn is_ignored(&self) -> bool {
+        !self.rule.is_negated()
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// Gitignore
+
+/// The rules of a number of `.gitignore` (or `.ignore`) files, each of
+/// which applies relative to its own directory, with the exact syntax
+/// and semantics of **git**:
+/// - blank lines, and lines beginning with `'#'`, are ignored;
+/// - trailing spaces are ignored, unless escaped with `'\'`;
+/// - a leading `'!'` negates a rule (re-including what it matches);
+/// - `"\#"` and `"\!"` begin a rule with a literal `'#'` or `'!'`;
+/// - a trailing `'/'` restricts a rule to directories;
+/// - a rule with a `'/'` at its start or within it is anchored to its
+///   directory, and otherwise matches at any depth below it;
+/// - `*`, `?`, and ranges (`[a-z0-9]`, `[!abc]`, `[^abc]`, and character
+///   classes such as `[[:digit:]]`) never match `'/'`, whereas `**`
+///   between separators matches any number of directories;
+/// - the rules of a deeper directory take precedence over those of a
+///   shallower one, and later rules over earlier ones;
+/// - a path within an ignored directory is ignored, whatever the rules
+///   for the path itself;
+///
+/// Paths are evaluated relative to the root.
+///
+/// # Examples:
+///
+/// ```
+/// use shwild::Gitignore;
+/// use std::path::Path;
+///
+/// let mut gitignore = Gitignore::new();
+///
+/// gitignore.add_rules(Path::new(""), "# build outputs\n/target/\n*.log\n");
+/// gitignore.add_rules(Path::new("logs"), "!keep.log\n");
+///
+/// let is_ignored = |path : &str, is_dir : bool| {
+///     gitignore.is_ignored(Path::new(path), is_dir)
+/// };
+///
+/// assert!(is_ignored("target", true));
+/// assert!(is_ignored("target/debug/app", false));
+/// assert!(!is_ignored("src/target", true));
+/// assert!(is_ignored("src/a.log", false));
+/// assert!(!is_ignored("logs/keep.log", false));
+///
+/// let m = gitignore.check(Path::new("a/b.log"), false).unwrap();
+///
+/// assert_eq!(3, m.line);
+/// assert_eq!("*.log", m.rule.text());
+/// ```
+#[derive(Debug)]
+#[derive(Default)]
+pub struct Gitignore {
+    flags :  i64,
+    /// The levels of each directory, relative to the root, to which they
+    /// apply, in the order in which they were added.
+    levels : HashMap<PathBuf, Vec<IgnoreLevel>>,
+}
+
+/// The rules read from a single ignore file (or text).
+#[derive(Debug)]
+struct IgnoreLevel {
+    source : Option<PathBuf>,
+    rules :  RuleSet,
+}
+
+// API functions
+impl Gitignore {
+    /// Creates an empty instance.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an instance from the ignore files (`.gitignore` and
+    /// `.ignore`) in the directory `root` and in all of its (not ignored)
+    /// subdirectories, as by [`add_tree()`](Self::add_tree).
+    pub fn from_tree(root : &Path) -> io::Result<Self> {
+        let mut gitignore = Self::new();
+
+        gitignore.add_tree(root)?;
+
+        Ok(gitignore)
+    }
+}
+
+// Mutating methods
+impl Gitignore {
+    /// Specifies the flags - e.g. [`IGNORE_CASE`](crate::IGNORE_CASE), as
+    /// for **git**'s `core.ignoreCase` - with which subsequently added
+    /// rules are compiled.
+    pub fn with_flags(
+        mut self,
+        flags : i64,
+    ) -> Self {
+        self.flags = flags;
+
+        self
+    }
+
+    /// Adds the rules in `text`, which apply relative to `directory`.
+    ///
+    /// # Parameters:
+    /// - `directory` - the directory, relative to the root, in which the
+    ///   rules apply, e.g. `""` for the root;
+    /// - `text` - the contents of an ignore file;
+    pub fn add_rules(
+        &mut self,
+        directory : &Path,
+        text : &str,
+    ) {
+        self.add_level_(directory, None, text);
+    }
+
+    /// Reads and adds the rules in the ignore file `file`, which apply
+    /// relative to `directory`.
+    ///
+    /// # Parameters:
+    /// - `directory` - the directory, relative to the root, in which the
+    ///   rules apply, e.g. `""` for the root;
+    /// - `file` - the path of the ignore file;
+    pub fn add_file(
+        &mut self,
+        directory : &Path,
+        file : &Path,
+    ) -> io::Result<()> {
+        let bytes = fs::read(file)?;
+
+        self.add_level_(directory, Some(file), &String::from_utf8_lossy(&bytes));
+
+        Ok(())
+    }
+
+    /// Reads and adds the rules in the ignore files (`.gitignore` and then
+    /// `.ignore`) in the directory `root`, and then in each of its
+    /// subdirectories that is not ignored (and is not `.git`), each
+    /// applying relative to its own directory.
+    pub fn add_tree(
+        &mut self,
+        root : &Path,
+    ) -> io::Result<()> {
+        self.add_tree_(root, &PathBuf::new())
+    }
+}
+
+// Non-mutating methods
+impl Gitignore {
+    /// Determines whether `path`, which is a directory if `is_dir`, is
+    /// ignored.
+    pub fn is_ignored(
+        &self,
+        path : &Path,
+        is_dir : bool,
+    ) -> bool {
+        self.check(path, is_dir).is_some_and(|m| m.is_ignored())
+    }
+
+    /// Obtains the rule that decides whether `path`, which is a directory
+    /// if `is_dir`, is ignored - which is a rule for one of its parent
+    /// directories if that is ignored - or `None` if no rule matches.
+    pub fn check(
+        &self,
+        path : &Path,
+        is_dir : bool,
+    ) -> Option<IgnoreMatch<'_>> {
+        let path = normalise_(path);
+        let mut prefix = PathBuf::new();
+        let mut components = path.components().peekable();
+
+        while let Some(component) = components.next() {
+            if components.peek().is_none() {
+                break;
+            }
+
+            prefix.push(component);
+
+            if let Some(m) = self.check_single_(&prefix, true) {
+                if m.is_ignored() {
+                    return Some(m);
+                }
+            }
+        }
+
+        self.check_single_(&path, is_dir)
+    }
+}
+
+// Implementation
+impl Gitignore {
+    fn add_level_(
+        &mut self,
+        directory : &Path,
+        source : Option<&Path>,
+        text : &str,
+    ) {
+        let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+        let rules = text.lines().map(|line| rule_from_line_(line, self.flags)).collect();
+
+        self.levels.entry(normalise_(directory)).or_default().push(IgnoreLevel {
+            source : source.map(Path::to_path_buf),
+            rules :  RuleSet::from_rules_(rules),
+        });
+    }
+
+    fn add_tree_(
+        &mut self,
+        root : &Path,
+        directory : &Path,
+    ) -> io::Result<()> {
+        let absolute = root.join(directory);
+
+        for name in IGNORE_FILE_NAMES {
+            let file = absolute.join(name);
+
+            if file.is_file() {
+                self.add_file(directory, &file)?;
+            }
+        }
+
+        let mut subdirectories = vec![];
+
+        for entry in fs::read_dir(&absolute)? {
+            let entry = entry?;
+
+            if entry.file_type()?.is_dir() && ".git" != entry.file_name() {
+                let subdirectory = directory.join(entry.file_name());
+
+                // `directory` is not ignored, else it would not be walked
+                if !self.check_single_(&subdirectory, true).is_some_and(|m| m.is_ignored()) {
+                    subdirectories.push(subdirectory);
+                }
+            }
+        }
+
+        subdirectories.sort();
+
+        for subdirectory in subdirectories {
+            self.add_tree_(root, &subdirectory)?;
+        }
+
+        Ok(())
+    }
+
+    /// Obtains the last matching rule of the deepest level that has one,
+    /// without regard to the parent directories of `path` (which must be
+    /// normalised), consulting only the levels of its ancestors.
+    pub(crate) fn check_single_(
+        &self,
+        path : &Path,
+        is_dir : bool,
+    ) -> Option<IgnoreMatch<'_>> {
+        for directory in path.ancestors().skip(1) {
+            let Some(levels) = self.levels.get(directory) else {
+                continue;
+            };
+
+            let Ok(relative) = path.strip_prefix(directory) else {
+                continue;
+            };
+
+            // of the levels of a directory, the one added later takes
+            // precedence
+            for level in levels.iter().rev() {
+                if let Some(index) = level.rules.evaluate_single(relative, is_dir).rule() {
+                    return Some(IgnoreMatch {
+                        source : level.source.as_deref(),
+                        line :   index + 1,
+                        rule :   &level.rules.rules()[index],
+                    });
+                }
+            }
+        }
+
+        None
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// line parsing
+
+/// Obtains `path` without any `.` components.
+pub(crate) fn normalise_(path : &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !std::matches!(component, Component::CurDir))
+        .collect()
+}
+
+/// Parses the ignore file line `line` into a rule, which matches nothing
+/// if `line` is blank, is a comment, or has an invalid pattern (as does
+/// **git**).
+fn rule_from_line_(
+    line : &str,
+    flags : i64,
+) -> Rule {
+    if line.starts_with('#') {
+        return Rule::from_parts_(line, false, false, false, None);
+    }
+
+    let body = trim_trailing_spaces_(line);
+
+    let (negated, body) = match body.strip_prefix('!') {
+        Some(body) => (true, body),
+        None => (false, body),
+    };
+
+    let (directory_only, body) = match body.strip_suffix('/') {
+        Some(body) => (true, body),
+        None => (false, body),
+    };
+
+    let anchored = body.contains('/');
+
+    let body = body.strip_prefix('/').unwrap_or(body);
+
+    let pattern = pattern_from_glob_(body, flags);
+
+    Rule::from_parts_(line, negated, directory_only, anchored, pattern)
+}
+
+/// Obtains `line` without its trailing spaces, other than any that is
+/// escaped.
+fn trim_trailing_spaces_(line : &str) -> &str {
+    let mut end = 0;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        if escaped {
+            escaped = false;
+        } else if '\\' == c {
+            escaped = true;
+        } else if ' ' == c {
+            continue;
+        }
+
+        end = i + c.len_utf8();
+    }
+
+    &line[..end]
+}
+
+/// Translates the **git** glob `glob` into a pattern, or `None` if it is
+/// invalid (i.e. has an unterminated range, an unknown character class,
+/// a sequence of more than [`MAX_RANGE_SEQUENCE_LENGTH`] characters, or
+/// a trailing `'\'`).
+fn pattern_from_glob_(
+    glob : &str,
+    flags : i64,
+) -> Option<Pattern> {
+    let mut builder = PatternBuilder::new().with_flags(flags);
+    let mut chars = glob.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        builder = match c {
+            '\\' => builder.literal(chars.next()?.encode_utf8(&mut [0; 4])),
+            '?' => builder.wild1(),
+            '*' => {
+                if chars.next_if_eq(&'*').is_some() {
+                    // any run of two or more `*`s is treated as `**`
+                    while chars.next_if_eq(&'*').is_some() {}
+
+                    builder.wild_n().wild_n()
+                } else {
+                    builder.wild_n()
+                }
+            },
+            '[' => {
+                let (negated, range_chars) = parse_range_(&mut chars)?;
+
+                if negated {
+                    builder.not_range(range_chars)
+                } else {
+                    builder.range(range_chars)
+                }
+            },
+            _ => builder.literal(c.encode_utf8(&mut [0; 4])),
+        };
+    }
+
+    Some(builder.build())
+}
+
+/// Parses the remainder of a range (following its `'['`), in which a
+/// leading `'!'` or `'^'` negates it, a leading `']'` is literal, any
+/// character may be escaped with `'\'`, and `a-z` and `[:name:]` denote
+/// sequences and (ASCII) classes of characters.
+fn parse_range_(chars : &mut Peekable<Chars<'_>>) -> Option<(bool, Vec<char>)> {
+    let negated = chars.next_if(|&c| '!' == c || '^' == c).is_some();
+
+    let mut range_chars = vec![];
+    let mut is_first = true;
+
+    loop {
+        let c = chars.next()?;
+
+        if ']' == c && !is_first {
+            return Some((negated, range_chars));
+        }
+
+        is_first = false;
+
+        if '[' == c && Some(&':') == chars.peek() {
+            let rest : String = chars.clone().skip(1).collect();
+
+            if let Some(end) = rest.find(":]") {
+                range_chars.extend(class_chars_(&rest[..end])?);
+
+                for _ in 0..(1 + rest[..end].chars().count() + 2) {
+                    chars.next();
+                }
+
+                continue;
+            }
+        }
+
+        let from = if '\\' == c { chars.next()? } else { c };
+
+        // a `'-'` followed by `']'` is literal
+        let mut lookahead = chars.clone();
+
+        if Some('-') == lookahead.next() && lookahead.next().is_some_and(|c| ']' != c) {
+            chars.next();
+
+            let to = match chars.next()? {
+                '\\' => chars.next()?,
+                to => to,
+            };
+
+            if (to as u32).saturating_sub(from as u32) >= MAX_RANGE_SEQUENCE_LENGTH {
+                return None;
+            }
+
+            range_chars.extend(from..=to);
+        } else {
+            range_chars.push(from);
+        }
+    }
+}
+
+/// The characters of the (ASCII) character class `name`, or `None` if it
+/// is unknown.
+fn class_chars_(name : &str) -> Option<Vec<char>> {
+    let predicate : fn(&u8) -> bool = match name {
+        "alnum" => u8::is_ascii_alphanumeric,
+        "alpha" => u8::is_ascii_alphabetic,
+        "blank" => |b| b' ' == *b || b'\t' == *b,
+        "cntrl" => u8::is_ascii_control,
+        "digit" => u8::is_ascii_digit,
+        "graph" => u8::is_ascii_graphic,
+        "lower" => u8::is_ascii_lowercase,
+        "print" => |b| b.is_ascii_graphic() || b' ' == *b,
+        "punct" => u8::is_ascii_punctuation,
+        "space" => |b| b.is_ascii_whitespace() || 0x0b == *b,
+        "upper" => u8::is_ascii_uppercase,
+        "xdigit" => u8::is_ascii_hexdigit,
+        _ => return None,
+    };
+
+    Some((0..=0x7f_u8).filter(predicate).map(char::from).collect())
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use crate::{
+        test_utils::TempTree,
+        IGNORE_CASE,
+    };
+
+
+    fn gitignore(levels : &[(&str, &str)]) -> Gitignore {
+        let mut gitignore = Gitignore::new();
+
+        for (directory, text) in levels {
+            gitignore.add_rules(Path::new(directory), text);
+        }
+
+        gitignore
+    }
+
+    fn is_ignored(
+        gitignore : &Gitignore,
+        path : &str,
+        is_dir : bool,
+    ) -> bool {
+        gitignore.is_ignored(Path::new(path), is_dir)
+    }
+
+
+    #[test]
+    fn TEST_trim_trailing_spaces_1() {
+        assert_eq!("", trim_trailing_spaces_(""));
+        assert_eq!("", trim_trailing_spaces_("   "));
+        assert_eq!("a", trim_trailing_spaces_("a  "));
+        assert_eq!(" a", trim_trailing_spaces_(" a "));
+        assert_eq!("a\\ ", trim_trailing_spaces_("a\\  "));
+        assert_eq!("a\\\\", trim_trailing_spaces_("a\\\\ "));
+        assert_eq!("a\t", trim_trailing_spaces_("a\t "));
+    }
+
+    #[test]
+    fn TEST_pattern_from_glob_1() {
+        let matches = |glob : &str, input : &str| pattern_from_glob_(glob, 0).unwrap().compile().matches(input);
+
+        assert!(matches("a?c", "abc"));
+        assert!(matches("*.[ch]", "x.h"));
+        assert!(matches("[0-9][0-9]", "42"));
+        assert!(!matches("[0-9][0-9]", "4x"));
+        assert!(matches("[!0-9]", "x"));
+        assert!(!matches("[^0-9]", "4"));
+        assert!(matches("[]a]", "]"));
+        assert!(matches("[a-]", "-"));
+        assert!(matches("[[:digit:]x]", "7"));
+        assert!(matches("[[:upper:]]", "Q"));
+        assert!(!matches("[[:upper:]]", "q"));
+        assert!(matches("\\*\\?\\[", "*?["));
+        assert!(matches("\\#*", "#x"));
+
+        assert!(pattern_from_glob_("[abc", 0).is_none());
+        assert!(pattern_from_glob_("[[:bogus:]]", 0).is_none());
+        assert!(pattern_from_glob_("abc\\", 0).is_none());
+
+        assert!(matches("[\u{100}-\u{1ff}]", "\u{1ff}"));
+        assert!(pattern_from_glob_("[\u{100}-\u{200}]", 0).is_none());
+        assert!(pattern_from_glob_("[\0-\u{10ffff}]", 0).is_none());
+    }
+
+    #[test]
+    fn TEST_rule_from_line_1() {
+        let rule = rule_from_line_("!/build/  ", 0);
+
+        assert_eq!("!/build/  ", rule.text());
+        assert!(rule.is_negated());
+        assert!(rule.is_directory_only());
+        assert!(rule.is_anchored());
+
+        assert!(!rule_from_line_("# comment", 0).matches(Path::new("# comment"), false));
+        assert!(rule_from_line_("\\#file", 0).matches(Path::new("#file"), false));
+        assert!(rule_from_line_("\\!file", 0).matches(Path::new("!file"), false));
+        assert!(!rule_from_line_("\\!file", 0).is_negated());
+        assert!(rule_from_line_("a\\ ", 0).matches(Path::new("a "), false));
+        assert!(!rule_from_line_("a  ", 0).matches(Path::new("a "), false));
+        assert!(!rule_from_line_("[abc", 0).matches(Path::new("[abc"), false));
+    }
+
+    #[test]
+    fn TEST_Gitignore_1() {
+        let gitignore = gitignore(&[("", "# comment\n\n*.o\n/target/\ndoc/*.html\n!keep.o\n")]);
+
+        assert!(is_ignored(&gitignore, "a.o", false));
+        assert!(is_ignored(&gitignore, "x/y/a.o", false));
+        assert!(!is_ignored(&gitignore, "x/y/keep.o", false));
+        assert!(is_ignored(&gitignore, "target", true));
+        assert!(!is_ignored(&gitignore, "target", false));
+        assert!(is_ignored(&gitignore, "target/keep.o", false));
+        assert!(!is_ignored(&gitignore, "x/target", true));
+        assert!(is_ignored(&gitignore, "doc/a.html", false));
+        assert!(!is_ignored(&gitignore, "doc/x/a.html", false));
+        assert!(!is_ignored(&gitignore, "x/doc/a.html", false));
+        assert!(!is_ignored(&gitignore, "src/lib.rs", false));
+        assert!(!is_ignored(&gitignore, "# comment", false));
+    }
+
+    #[test]
+    fn TEST_Gitignore_ANY_DIRECTORIES_1() {
+        let gitignore = gitignore(&[("", "**/logs\nfoo/**/bar\nabc/**\na**z\n")]);
+
+        assert!(is_ignored(&gitignore, "logs", true));
+        assert!(is_ignored(&gitignore, "x/y/logs", true));
+        assert!(is_ignored(&gitignore, "foo/bar", false));
+        assert!(is_ignored(&gitignore, "foo/x/y/bar", false));
+        assert!(!is_ignored(&gitignore, "abc", true));
+        assert!(is_ignored(&gitignore, "abc/x", false));
+        assert!(is_ignored(&gitignore, "x/abz", false));
+        assert!(!is_ignored(&gitignore, "a/z", false));
+    }
+
+    #[test]
+    fn TEST_Gitignore_NESTED_1() {
+        let gitignore = gitignore(&[
+            ("", "*.log\n/build\n"),
+            ("sub", "!important.log\n/local\n"),
+            ("sub/deeper", "*.txt\n"),
+        ]);
+
+        assert!(is_ignored(&gitignore, "a.log", false));
+        assert!(is_ignored(&gitignore, "sub/a.log", false));
+        assert!(!is_ignored(&gitignore, "sub/important.log", false));
+        assert!(!is_ignored(&gitignore, "sub/x/important.log", false));
+        assert!(is_ignored(&gitignore, "important.log", false));
+        assert!(is_ignored(&gitignore, "sub/local", false));
+        assert!(!is_ignored(&gitignore, "local", false));
+        assert!(!is_ignored(&gitignore, "sub/build", false));
+        assert!(is_ignored(&gitignore, "sub/deeper/a.txt", false));
+        assert!(!is_ignored(&gitignore, "sub/a.txt", false));
+        assert!(is_ignored(&gitignore, "./sub/./deeper/a.txt", false));
+    }
+
+    #[test]
+    fn TEST_Gitignore_LEVELS_OF_ANCESTORS_1() {
+        let gitignore = gitignore(&[
+            ("", "*.tmp\n"),
+            ("sub", "!*.tmp\n"),
+            ("sub", "a.tmp\n"),
+            ("./sub-2/", "*.log\n"),
+            ("other", "*\n"),
+        ]);
+
+        // of the levels of the same directory, the later takes precedence
+        assert!(is_ignored(&gitignore, "sub/a.tmp", false));
+        assert!(!is_ignored(&gitignore, "sub/b.tmp", false));
+        assert!(is_ignored(&gitignore, "sub-2/b.tmp", false));
+
+        // the levels of a directory apply only within it
+        assert!(is_ignored(&gitignore, "sub-2/x/a.log", false));
+        assert!(!is_ignored(&gitignore, "sub/a.log", false));
+        assert!(!is_ignored(&gitignore, "sub-2.log", false));
+        assert!(!is_ignored(&gitignore, "other", true));
+        assert!(!is_ignored(&gitignore, "others/a", false));
+        assert!(is_ignored(&gitignore, "other/a", false));
+    }
+
+    #[test]
+    fn TEST_Gitignore_EXCLUDED_PARENT_1() {
+        let gitignore = gitignore(&[("", "logs/\n"), ("logs", "!keep.log\n")]);
+
+        // a file cannot be re-included if a parent directory is excluded
+        assert!(is_ignored(&gitignore, "logs/keep.log", false));
+
+        let m = gitignore.check(Path::new("logs/keep.log"), false).unwrap();
+
+        assert_eq!(None, m.source);
+        assert_eq!(1, m.line);
+        assert_eq!("logs/", m.rule.text());
+    }
+
+    #[test]
+    fn TEST_Gitignore_CRLF_AND_BOM_1() {
+        let gitignore = gitignore(&[("", "\u{feff}*.o\r\n*.a \r\n")]);
+
+        assert!(is_ignored(&gitignore, "x.o", false));
+        assert!(is_ignored(&gitignore, "x.a", false));
+    }
+
+    #[test]
+    fn TEST_Gitignore_IGNORE_CASE_1() {
+        let mut gitignore = Gitignore::new().with_flags(IGNORE_CASE);
+
+        gitignore.add_rules(Path::new(""), "*.LOG\n[[:upper:]]\n");
+
+        assert!(is_ignored(&gitignore, "a.log", false));
+        assert!(is_ignored(&gitignore, "q", false));
+    }
+
+    #[test]
+    fn TEST_Gitignore_check_1() {
+        let gitignore = gitignore(&[("", "*.log\n!keep.log\n")]);
+
+        let m = gitignore.check(Path::new("keep.log"), false).unwrap();
+
+        assert!(!m.is_ignored());
+        assert_eq!(2, m.line);
+        assert_eq!("!keep.log", m.rule.text());
+
+        assert!(gitignore.check(Path::new("a.txt"), false).is_none());
+    }
+
+    #[test]
+    fn TEST_Gitignore_from_tree_1() {
+        let tree = TempTree::new("gitignore-from-tree", &["sub/deeper/", "skipped/"]);
+
+        tree.write(".gitignore", "*.log\nskipped/\n");
+        tree.write("sub/.gitignore", "!keep.log\n");
+        tree.write("sub/.ignore", "*.tmp\n");
+        tree.write("sub/deeper/.gitignore", "!*.tmp\n");
+        tree.write("skipped/.gitignore", "!*.log\n");
+
+        let gitignore = Gitignore::from_tree(&tree.root).unwrap();
+
+        assert!(is_ignored(&gitignore, "a.log", false));
+        assert!(!is_ignored(&gitignore, "sub/keep.log", false));
+        assert!(is_ignored(&gitignore, "sub/a.tmp", false));
+        assert!(!is_ignored(&gitignore, "sub/deeper/a.tmp", false));
+        assert!(is_ignored(&gitignore, "skipped/a.log", false));
+
+        let m = gitignore.check(Path::new("sub/a.tmp"), false).unwrap();
+
+        assert_eq!(Some(tree.root.join("sub/.ignore").as_path()), m.source);
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
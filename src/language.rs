@@ -0,0 +1,210 @@
+// src/language.rs : operations on the languages (sets of matched strings)
+//                   of patterns
+
+// ///////////////////////////////////////////////
+// imports
+
+use crate::automaton::{
+    Alphabet,
+    Nfa,
+    PositionSet,
+};
+
+use std::collections::{
+    HashMap,
+    VecDeque,
+};
+
+
+// ///////////////////////////////////////////////
+// product search
+
+/// Finds a shortest string that takes the automata `a` and `b` to states
+/// whose acceptance (by `a` and by `b`, respectively) satisfies
+/// `is_goal`, by breadth-first search of their product over an alphabet
+/// of the classes of characters that they distinguish.
+///
+/// The product may have exponentially many states, e.g. for `*a??????`,
+/// so the search is abandoned once more than `state_budget` of them have
+/// been constructed.
+///
+/// # Returns:
+/// `Some(Some(string))` for the string; `Some(None)` if there is none; or
+/// `None` if this cannot be decided, because the budget is exceeded or
+/// because either automaton is not exact (see [`Nfa::is_exact()`]).
+pub(crate) fn find_shortest<F>(
+    a : &Nfa,
+    b : &Nfa,
+    is_goal : F,
+    state_budget : usize,
+) -> Option<Option<String>>
+where
+    F : Fn(bool, bool) -> bool,
+{
+    if !a.is_exact() || !b.is_exact() {
+        return None;
+    }
+
+    let alphabet = Alphabet::from_char_sets(a.char_sets().chain(b.char_sets()));
+
+    let is_goal_state = |(pa, pb) : &(PositionSet, PositionSet)| is_goal(a.is_accepting(pa), b.is_accepting(pb));
+
+    let start = (a.start(), b.start());
+
+    if is_goal_state(&start) {
+        return Some(Some(String::new()));
+    }
+
+    // each state is recorded with the state from which, and the character
+    // by which, it was (first) reached
+    let mut states = vec![(start.clone(), usize::MAX, '\0')];
+    let mut state_ids = HashMap::from([(start, 0)]);
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(id) = queue.pop_front() {
+        for &c in alphabet.representatives() {
+            let (pa, pb) = &states[id].0;
+
+            let next = (a.step(pa, c), b.step(pb, c));
+
+            if next.0.is_empty() && next.1.is_empty() {
+                // neither can accept any continuation
+                continue;
+            }
+
+            if state_ids.contains_key(&next) {
+                continue;
+            }
+
+            let next_id = states.len();
+
+            if next_id >= state_budget {
+                return None;
+            }
+
+            let found = is_goal_state(&next);
+
+            state_ids.insert(next.clone(), next_id);
+            states.push((next, id, c));
+
+            if found {
+                let mut chars = vec![];
+                let mut id = next_id;
+
+                while 0 != id {
+                    let (_, prior_id, c) = states[id];
+
+                    chars.push(c);
+
+                    id = prior_id;
+                }
+
+                return Some(Some(chars.into_iter().rev().collect()));
+            }
+
+            queue.push_back(next_id);
+        }
+    }
+
+    Some(None)
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use crate::{
+        parse,
+        IGNORE_CASE,
+    };
+
+
+    const BUDGET : usize = 10_000;
+
+
+    fn nfa(
+        pattern : &str,
+        flags : i64,
+    ) -> Nfa {
+        Nfa::from_elements(parse(pattern, flags).unwrap().elements(), flags)
+    }
+
+
+    #[test]
+    fn TEST_find_shortest_1() {
+        let both = |a : bool, b : bool| a && b;
+
+        assert_eq!(
+            Some(Some("".into())),
+            find_shortest(&nfa("*", 0), &nfa("", 0), both, BUDGET)
+        );
+        assert_eq!(
+            Some(Some("az".into())),
+            find_shortest(&nfa("a*", 0), &nfa("*z", 0), both, BUDGET)
+        );
+        assert_eq!(
+            Some(Some("a".into())),
+            find_shortest(&nfa("a*", 0), &nfa("*a", 0), both, BUDGET)
+        );
+        assert_eq!(Some(None), find_shortest(&nfa("a*", 0), &nfa("b*", 0), both, BUDGET));
+        assert_eq!(
+            Some(None),
+            find_shortest(&nfa("[ab]?", 0), &nfa("???", 0), both, BUDGET)
+        );
+        assert_eq!(
+            Some(Some("A".into())),
+            find_shortest(&nfa("a", IGNORE_CASE), &nfa("[A-C]", 0), both, BUDGET)
+        );
+    }
+
+    #[test]
+    fn TEST_find_shortest_WITH_STATE_BUDGET_1() {
+        let a_not_b = |a : bool, b : bool| a && !b;
+        let pattern = format!("*a{}", "?".repeat(16));
+
+        // the product has a state for each subset of the last 17 positions
+        assert_eq!(None, find_shortest(&nfa(&pattern, 0), &nfa("*", 0), a_not_b, BUDGET));
+        assert_eq!(
+            Some(None),
+            find_shortest(&nfa("*a????", 0), &nfa("*", 0), a_not_b, BUDGET)
+        );
+    }
+
+    #[test]
+    fn TEST_find_shortest_NOT_EXACT_1() {
+        let both = |a : bool, b : bool| a && b;
+
+        assert_eq!(None, find_shortest(&nfa("ß", IGNORE_CASE), &nfa("*", 0), both, BUDGET));
+        assert_eq!(
+            None,
+            find_shortest(&nfa("*", 0), &nfa("*ss", IGNORE_CASE), both, BUDGET)
+        );
+        assert_eq!(
+            Some(Some("ß".into())),
+            find_shortest(&nfa("ß", 0), &nfa("*", IGNORE_CASE), both, BUDGET)
+        );
+    }
+
+    #[test]
+    fn TEST_find_shortest_WITNESS_IS_MATCHED_1() {
+        let patterns = ["", "*", "a*", "*a", "a*b", "?*?", "[ab]*[^ab]", "*.[ch]", "*x*y*"];
+
+        for pa in patterns {
+            for pb in patterns {
+                let a = crate::CompiledMatcher::from_pattern_and_flags(pa, 0).unwrap();
+                let b = crate::CompiledMatcher::from_pattern_and_flags(pb, 0).unwrap();
+
+                if let Some(witness) = find_shortest(&nfa(pa, 0), &nfa(pb, 0), |a, b| a && !b, BUDGET).unwrap() {
+                    assert!(a.matches(&witness), "'{pa}' \\ '{pb}', witness '{witness}'");
+                    assert!(!b.matches(&witness), "'{pa}' \\ '{pb}', witness '{witness}'");
+                }
+            }
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -26,13 +26,49 @@
 //! * [`assert_shwild_not_matches!`] — test assertion that `actual` does
 //!   not match (requires `"assertions"` feature);
 //! * [`CompiledMatcher`] — parse once, match many times;
+//! * [`PatternSet`] — match one input against many patterns at once;
+//! * [`RuleSet`], [`Rule`], and [`RuleMatch`] — gitignore-style
+//!   include/exclude rule lists, in which the last match wins;
+//! * [`Gitignore`] and [`IgnoreMatch`] — parsing and evaluation of
+//!   (nested) `.gitignore` / `.ignore` files;
+//! * [`glob()`], [`glob_with()`], [`Glob`], and [`GlobOptions`] — lazy
+//!   walking of the filesystem for the paths that match a pattern;
+//! * [`ParallelGlob`] and [`CancelToken`] — multi-threaded walking, with
+//!   ignore rules and cancellation;
+//! * [`parse()`], [`Pattern`], [`PatternElement`], and [`Visitor`] — the
+//!   parsed form of a pattern, which may be inspected, walked, and
+//!   compiled;
+//! * [`PatternBuilder`] — programmatic construction of patterns, with
+//!   literals escaped automatically;
+//! * [`escape()`] and [`unescape()`] — quote arbitrary text as a pattern
+//!   that matches only itself, and back;
+//! * [`from_regex_subset()`] — convert a simple regular expression into a
+//!   pattern;
+//! * [`intersects()`] — whether two patterns overlap, with a shortest
+//!   witness string (within a state budget);
+//! * [`RandomSource`] and [`SeededRandom`] — (reproducible) random
+//!   generation of examples via [`CompiledMatcher::examples()`] and
+//!   [`CompiledMatcher::counter_examples()`];
+//! * [`DfaMode`] — eager or lazy DFA construction for
+//!   [`CompiledMatcher::enable_dfa()`];
 //! * [`Error`] and [`Result`] — parse/match error reporting;
+//! * [`FindMode`] and [`FindIter`] — unanchored search with
+//!   [`CompiledMatcher::find()`] and [`CompiledMatcher::find_iter()`];
+//! * [`CaptureMode`] and [`Captures`] — the input consumed by each
+//!   wildcard, via [`CompiledMatcher::captures()`];
+//! * [`RenamePlan`], [`Rename`], and [`Collision`] — batch renames via
+//!   [`CompiledMatcher::rename_plan()`];
 //! * [`IGNORE_CASE`] — flag for case-insensitive matching;
+//! * [`PURE_BYTES`] — flag for byte-at-a-time evaluation of byte input;
+//! * `GRAPHEME_CLUSTERS` — flag for matching by extended grapheme
+//!   cluster (requires `"grapheme-clusters"` feature);
 //!
 //! # Features
 //!
 //! * `assertions` (default) — [`assert_shwild_matches!`] and
 //!   [`assert_shwild_not_matches!`] via **base-traits**;
+//! * `grapheme-clusters` — the `GRAPHEME_CLUSTERS` flag via
+//!   **unicode-segmentation**;
 //! * `lookup-ranges` (default) — range matching via **collect-rs**
 //!   `UnicodePointMap`;
 //! * `null-feature` — no effect; useful for driver scripts;
@@ -75,16 +111,26 @@
 // crate-level imports
 
 use std::{
+    borrow::Cow,
     error as std_error,
+    ffi as std_ffi,
     fmt as std_fmt,
     matches as std_matches,
+    mem as std_mem,
+    ops as std_ops,
+    path as std_path,
     result as std_result,
+    sync as std_sync,
 };
 
 
 /// Represents parsing result.
+///
+/// New variants may be added in future versions, so a `match` on an
+/// instance must include a wildcard arm.
 #[derive(Debug)]
 #[derive(PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     /// Parse error encountered.
     ParseError {
@@ -92,6 +138,14 @@ pub enum Error {
         column :  usize,
         message : String,
     },
+    /// Substitution template parse error encountered.
+    TemplateError {
+        line :    usize,
+        column :  usize,
+        message : String,
+    },
+    /// Pattern cannot be expressed in the target pattern language.
+    TranslationError { message : String },
 }
 
 // API functions
@@ -127,6 +181,18 @@ impl Error {
                     write!(f, "{message}")
                 }
             },
+            Self::TemplateError {
+                line,
+                column,
+                message,
+            } => {
+                write!(f, "template syntax error (at {line}:{column}): {message}")
+            },
+            Self::TranslationError {
+                message,
+            } => {
+                write!(f, "pattern cannot be translated: {message}")
+            },
         }
     }
 }
@@ -150,19 +216,40 @@ mod constants {
 
     /// Causes matching to ignore case.
     pub const IGNORE_CASE : i64 = 0x0200;
+
+    /// Causes byte input to be evaluated a single byte at a time: `?`,
+    /// ranges, and not-ranges each consume exactly one byte, and a range
+    /// byte is compared against range characters as if it were the
+    /// character of the same value (i.e. as Latin-1).
+    pub const PURE_BYTES : i64 = 0x0001_0000;
+
+    /// Causes `?`, ranges, and not-ranges each to match an extended
+    /// grapheme cluster, rather than a single `char`, so that, e.g., `?`
+    /// matches `"👁️"` (eye plus variation selector) and `"é"` written as
+    /// `'e'` plus combining acute accent. Applies only to string input.
+    #[cfg(feature = "grapheme-clusters")]
+    pub const GRAPHEME_CLUSTERS : i64 = 0x0002_0000;
 }
 
-pub use constants::IGNORE_CASE;
+#[cfg(feature = "grapheme-clusters")]
+pub use constants::GRAPHEME_CLUSTERS;
+pub use constants::{
+    IGNORE_CASE,
+    PURE_BYTES,
+};
 
 
 mod traits {
     #![allow(non_snake_case)]
 
-    use std::fmt as std_fmt;
+    use std::{
+        fmt as std_fmt,
+        ops as std_ops,
+    };
 
 
     /// Defines behaviour for specific matchers.
-    pub(crate) trait Match: std_fmt::Debug {
+    pub(crate) trait Match: std_fmt::Debug + Send + Sync {
         /// Attempts to match the input string `slice` against this `Match`
         /// instance and, implicitly, any following `Match` instances.
         ///
@@ -173,6 +260,46 @@ mod traits {
             &self,
             slice : &str,
         ) -> bool;
+
+        /// Attempts to match the input bytes `slice` against this `Match`
+        /// instance and, implicitly, any following `Match` instances.
+        ///
+        /// Except in `PURE_BYTES` mode, `?` consumes one UTF-8 sequence or
+        /// one invalid byte, and ranges are evaluated against decoded
+        /// characters.
+        ///
+        /// # Returns:
+        /// - `true` - indicates a full match; or
+        /// - `false` - if not a full match.
+        fn matches_bytes(
+            &self,
+            slice : &[u8],
+        ) -> bool;
+
+        /// Attempts to match `input`, from `offset` onwards, against this
+        /// `Match` instance and, implicitly, any following `Match`
+        /// instances, recording in `spans` the span of `input` consumed by
+        /// each wildcard element.
+        ///
+        /// When `greedy`, `*` consumes as much as possible of the input
+        /// consistent with a full match; otherwise, as little as possible.
+        ///
+        /// # Returns:
+        /// - `true` - indicates a full match, in which case `spans` holds
+        ///   one span for each wildcard element; or
+        /// - `false` - if not a full match, in which case `spans` is
+        ///   unchanged.
+        fn captures(
+            &self,
+            input : &str,
+            offset : usize,
+            greedy : bool,
+            spans : &mut Vec<std_ops::Range<usize>>,
+        ) -> bool;
+
+        /// Detaches, and returns, the next `Match` instance in the chain,
+        /// if any, thereby allowing a chain to be dismantled iteratively.
+        fn take_next(&mut self) -> Option<Box<dyn Match>>;
     }
 }
 
@@ -196,6 +323,14 @@ mod match_structures {
         types::CharacterRangeType,
     };
 
+    use std::{
+        mem as std_mem,
+        ops as std_ops,
+    };
+
+    #[cfg(feature = "grapheme-clusters")]
+    use unicode_segmentation::UnicodeSegmentation as _;
+
 
     /// Marks the end of the string, and the root of the reverse match
     /// chain
@@ -226,6 +361,12 @@ mod match_structures {
         /// The minimum_required size of this and all subsequent instances.
         #[cfg_attr(debug_assertions, allow(unused))]
         minimum_required : usize,
+        /// Whether byte input is evaluated a single byte at a time.
+        pure_bytes :       bool,
+        /// The multi-character clusters of the range, in
+        /// `GRAPHEME_CLUSTERS` mode.
+        #[cfg(feature = "grapheme-clusters")]
+        clusters :         Option<ClusterRange>,
     }
 
     /// Matches a range, e.g. `"[abcd]"` will match any of the characters
@@ -239,26 +380,51 @@ mod match_structures {
         /// The minimum_required size of this and all subsequent instances.
         #[cfg_attr(debug_assertions, allow(unused))]
         minimum_required : usize,
+        /// Whether byte input is evaluated a single byte at a time.
+        pure_bytes :       bool,
+        /// The multi-character clusters of the range, in
+        /// `GRAPHEME_CLUSTERS` mode.
+        #[cfg(feature = "grapheme-clusters")]
+        clusters :         Option<ClusterRange>,
+    }
+
+    /// The multi-character clusters of a range, in `GRAPHEME_CLUSTERS`
+    /// mode.
+    #[cfg(feature = "grapheme-clusters")]
+    #[derive(Debug)]
+    pub(crate) struct ClusterRange {
+        clusters :    Vec<String>,
+        ignore_case : bool,
     }
 
     /// `?` matches any single character.
     #[derive(Debug)]
     pub(crate) struct MatchWild1 {
         /// The next matcher.
-        pub(crate) next :  Box<dyn Match>,
+        pub(crate) next :   Box<dyn Match>,
         /// The minimum_required size of this and all subsequent instances.
         #[cfg_attr(debug_assertions, allow(unused))]
-        minimum_required : usize,
+        minimum_required :  usize,
+        /// Whether byte input is evaluated a single byte at a time.
+        pure_bytes :        bool,
+        /// Whether string input is evaluated a grapheme cluster at a time.
+        #[cfg(feature = "grapheme-clusters")]
+        grapheme_clusters : bool,
     }
 
     /// `*` matches any number (0 or more) of characters.
     #[derive(Debug)]
     pub(crate) struct MatchWildN {
         /// The next matcher.
-        pub(crate) next :  Box<dyn Match>,
+        pub(crate) next :   Box<dyn Match>,
         /// The minimum_required size of this and all subsequent instances.
         #[cfg_attr(debug_assertions, allow(unused))]
-        minimum_required : usize,
+        minimum_required :  usize,
+        /// Whether byte input is evaluated a single byte at a time.
+        pure_bytes :        bool,
+        /// Whether string input is evaluated a grapheme cluster at a time.
+        #[cfg(feature = "grapheme-clusters")]
+        grapheme_clusters : bool,
     }
 
     // API functions
@@ -292,59 +458,294 @@ mod match_structures {
         pub(crate) fn new(
             next : Box<dyn Match>,
             character_range : CharacterRangeType,
-            _flags : i64,
+            flags : i64,
         ) -> Self {
             // NOTE: this is a not-currently-implemented feature
             let minimum_required = usize::MAX;
 
+            let pure_bytes = 0 != (flags & super::constants::PURE_BYTES);
+
             Self {
                 next,
                 character_range,
                 // flags,
                 minimum_required,
+                pure_bytes,
+                #[cfg(feature = "grapheme-clusters")]
+                clusters : None,
             }
         }
+
+        /// Specifies the multi-character clusters of the range, for
+        /// `GRAPHEME_CLUSTERS` mode.
+        #[cfg(feature = "grapheme-clusters")]
+        pub(crate) fn with_clusters(
+            mut self,
+            clusters : Vec<String>,
+            flags : i64,
+        ) -> Self {
+            let ignore_case = 0 != (flags & super::constants::IGNORE_CASE);
+
+            self.clusters = Some(ClusterRange {
+                clusters,
+                ignore_case,
+            });
+
+            self
+        }
     }
 
     impl MatchRange {
         pub(crate) fn new(
             next : Box<dyn Match>,
             character_range : CharacterRangeType,
-            _flags : i64,
+            flags : i64,
         ) -> Self {
             // NOTE: this is a not-currently-implemented feature
             let minimum_required = usize::MAX;
 
+            let pure_bytes = 0 != (flags & super::constants::PURE_BYTES);
+
             Self {
                 next,
                 character_range,
                 // flags,
                 minimum_required,
+                pure_bytes,
+                #[cfg(feature = "grapheme-clusters")]
+                clusters : None,
             }
         }
+
+        /// Specifies the multi-character clusters of the range, for
+        /// `GRAPHEME_CLUSTERS` mode.
+        #[cfg(feature = "grapheme-clusters")]
+        pub(crate) fn with_clusters(
+            mut self,
+            clusters : Vec<String>,
+            flags : i64,
+        ) -> Self {
+            let ignore_case = 0 != (flags & super::constants::IGNORE_CASE);
+
+            self.clusters = Some(ClusterRange {
+                clusters,
+                ignore_case,
+            });
+
+            self
+        }
     }
 
     impl MatchWild1 {
-        pub(crate) fn new(next : Box<dyn Match>) -> Self {
+        pub(crate) fn new(
+            next : Box<dyn Match>,
+            flags : i64,
+        ) -> Self {
             // NOTE: this is a not-currently-implemented feature
             let minimum_required = usize::MAX;
 
+            let pure_bytes = 0 != (flags & super::constants::PURE_BYTES);
+            #[cfg(feature = "grapheme-clusters")]
+            let grapheme_clusters = 0 != (flags & super::constants::GRAPHEME_CLUSTERS);
+
             Self {
                 next,
                 minimum_required,
+                pure_bytes,
+                #[cfg(feature = "grapheme-clusters")]
+                grapheme_clusters,
             }
         }
     }
 
     impl MatchWildN {
-        pub(crate) fn new(next : Box<dyn Match>) -> Self {
+        pub(crate) fn new(
+            next : Box<dyn Match>,
+            flags : i64,
+        ) -> Self {
             // NOTE: this is a not-currently-implemented feature
             let minimum_required = usize::MAX;
 
+            let pure_bytes = 0 != (flags & super::constants::PURE_BYTES);
+            #[cfg(feature = "grapheme-clusters")]
+            let grapheme_clusters = 0 != (flags & super::constants::GRAPHEME_CLUSTERS);
+
             Self {
                 next,
                 minimum_required,
+                pure_bytes,
+                #[cfg(feature = "grapheme-clusters")]
+                grapheme_clusters,
+            }
+        }
+    }
+
+    // Implementation
+
+    impl MatchLiteral {
+        /// The number of bytes of (the start of) `slice` matched by the
+        /// literal, or `None` if not matched.
+        fn consumed_(
+            &self,
+            slice : &str,
+        ) -> Option<usize> {
+            if slice.starts_with(&self.literal) {
+                return Some(self.literal.len());
+            }
+
+            // the case-variant in the input may have a different encoded
+            // length, or even a different number of characters
+            match &self.literal_uppercase {
+                Some(literal_uppercase) => {
+                    crate::case::folded_prefix_len(literal_uppercase, slice.chars().map(|c| (c, c.len_utf8())))
+                },
+                None => None,
+            }
+        }
+    }
+
+    impl MatchNotRange {
+        /// The number of bytes of (the start of) `slice` matched by the
+        /// not-range, or `None` if not matched.
+        fn consumed_(
+            &self,
+            slice : &str,
+        ) -> Option<usize> {
+            #[cfg(feature = "grapheme-clusters")]
+            if let Some(cluster_range) = &self.clusters {
+                if slice.is_empty() {
+                    return None;
+                }
+
+                let cluster = first_cluster(slice);
+
+                if range_contains_cluster(&self.character_range, cluster_range, cluster) {
+                    return None;
+                }
+
+                return Some(cluster.len());
+            }
+
+            match slice.chars().next() {
+                Some(c0) if !range_contains(&self.character_range, c0) => Some(c0.len_utf8()),
+                _ => None,
+            }
+        }
+    }
+
+    impl MatchRange {
+        /// The number of bytes of (the start of) `slice` matched by the
+        /// range, or `None` if not matched.
+        fn consumed_(
+            &self,
+            slice : &str,
+        ) -> Option<usize> {
+            #[cfg(feature = "grapheme-clusters")]
+            if let Some(cluster_range) = &self.clusters {
+                if slice.is_empty() {
+                    return None;
+                }
+
+                let cluster = first_cluster(slice);
+
+                if !range_contains_cluster(&self.character_range, cluster_range, cluster) {
+                    return None;
+                }
+
+                return Some(cluster.len());
             }
+
+            match slice.chars().next() {
+                Some(c0) if range_contains(&self.character_range, c0) => Some(c0.len_utf8()),
+                _ => None,
+            }
+        }
+    }
+
+    impl MatchWild1 {
+        /// The number of bytes of (the start of) `slice` matched by the
+        /// wild-1, or `None` if `slice` is empty.
+        fn consumed_(
+            &self,
+            slice : &str,
+        ) -> Option<usize> {
+            #[cfg(feature = "grapheme-clusters")]
+            if self.grapheme_clusters {
+                return slice.graphemes(true).next().map(str::len);
+            }
+
+            slice.chars().next().map(char::len_utf8)
+        }
+    }
+
+    // Helper functions
+
+    /// Decodes the first unit of (non-empty) `slice`, which is either a
+    /// valid UTF-8 sequence or a single invalid byte.
+    ///
+    /// # Returns:
+    /// `(Some(c), n)` for a valid sequence of `n` bytes; `(None, 1)` for an
+    /// invalid byte.
+    pub(crate) fn decode_utf8_unit(slice : &[u8]) -> (Option<char>, usize) {
+        debug_assert!(!slice.is_empty());
+
+        let prefix = &slice[..slice.len().min(4)];
+
+        let valid = match std::str::from_utf8(prefix) {
+            Ok(valid) => valid,
+            Err(e) => std::str::from_utf8(&prefix[..e.valid_up_to()]).unwrap_or_default(),
+        };
+
+        match valid.chars().next() {
+            Some(c) => (Some(c), c.len_utf8()),
+            None => (None, 1),
+        }
+    }
+
+    /// The first extended grapheme cluster of (non-empty) `slice`.
+    #[cfg(feature = "grapheme-clusters")]
+    #[inline]
+    fn first_cluster(slice : &str) -> &str {
+        slice.graphemes(true).next().unwrap_or(slice)
+    }
+
+    /// Indicates whether `cluster` is a member of the range comprising
+    /// `character_range` (for single-character clusters) and
+    /// `cluster_range` (for multi-character clusters).
+    #[cfg(feature = "grapheme-clusters")]
+    fn range_contains_cluster(
+        character_range : &CharacterRangeType,
+        cluster_range : &ClusterRange,
+        cluster : &str,
+    ) -> bool {
+        let mut chars = cluster.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => range_contains(character_range, c),
+            _ => {
+                cluster_range.clusters.iter().any(|g| {
+                    if cluster_range.ignore_case {
+                        g.eq_ignore_ascii_case(cluster)
+                    } else {
+                        g == cluster
+                    }
+                })
+            },
+        }
+    }
+
+    #[inline]
+    fn range_contains(
+        character_range : &CharacterRangeType,
+        c : char,
+    ) -> bool {
+        #[cfg(feature = "lookup-ranges")]
+        {
+            character_range.contains_key(&c)
+        }
+        #[cfg(not(feature = "lookup-ranges"))]
+        {
+            character_range.contains(c)
         }
     }
 
@@ -357,6 +758,27 @@ mod match_structures {
         ) -> bool {
             slice.is_empty()
         }
+
+        fn matches_bytes(
+            &self,
+            slice : &[u8],
+        ) -> bool {
+            slice.is_empty()
+        }
+
+        fn captures(
+            &self,
+            input : &str,
+            offset : usize,
+            _greedy : bool,
+            _spans : &mut Vec<std_ops::Range<usize>>,
+        ) -> bool {
+            offset == input.len()
+        }
+
+        fn take_next(&mut self) -> Option<Box<dyn Match>> {
+            None
+        }
     }
 
     impl Match for MatchLiteral {
@@ -364,21 +786,67 @@ mod match_structures {
             &self,
             slice : &str,
         ) -> bool {
-            let slice_starts_with_literal = slice.starts_with(&self.literal)
-                || match &self.literal_uppercase {
-                    Some(literal_uppercase) => {
-                        slice.len() >= literal_uppercase.len() && slice.to_uppercase().starts_with(literal_uppercase)
-                    },
-                    None => false,
-                };
-
-            if !slice_starts_with_literal {
-                return false;
+            match self.consumed_(slice) {
+                Some(n) => self.next.matches(&slice[n..]),
+                None => false,
             }
+        }
 
+        fn matches_bytes(
+            &self,
+            slice : &[u8],
+        ) -> bool {
             let next = self.next.as_ref();
 
-            next.matches(&slice[self.literal.len()..])
+            if slice.starts_with(self.literal.as_bytes()) {
+                return next.matches_bytes(&slice[self.literal.len()..]);
+            }
+
+            let literal_uppercase = match &self.literal_uppercase {
+                Some(literal_uppercase) => literal_uppercase,
+                None => return false,
+            };
+
+            // fold as for `&str`, over the valid UTF-8 that begins the
+            // slice, since the case-variant in the input may have a
+            // different encoded length, or even a different number of
+            // characters
+
+            let mut offset = 0;
+
+            let chars = std::iter::from_fn(|| {
+                if offset == slice.len() {
+                    return None;
+                }
+
+                let (c, n) = decode_utf8_unit(&slice[offset..]);
+
+                offset += n;
+
+                c.map(|c| (c, n))
+            });
+
+            match crate::case::folded_prefix_len(literal_uppercase, chars) {
+                Some(n) => next.matches_bytes(&slice[n..]),
+                None => false,
+            }
+        }
+
+        fn captures(
+            &self,
+            input : &str,
+            offset : usize,
+            greedy : bool,
+            spans : &mut Vec<std_ops::Range<usize>>,
+        ) -> bool {
+            match self.consumed_(&input[offset..]) {
+                Some(n) => self.next.captures(input, offset + n, greedy, spans),
+                None => false,
+            }
+        }
+
+        fn take_next(&mut self) -> Option<Box<dyn Match>> {
+            Some(std_mem::replace(&mut self.next, Box::new(MatchEnd {})))
         }
     }
 
@@ -386,106 +854,287 @@ mod match_structures {
         fn matches(
             &self,
             slice : &str,
+        ) -> bool {
+            match self.consumed_(slice) {
+                Some(n) => self.next.matches(&slice[n..]),
+                None => false,
+            }
+        }
+
+        fn matches_bytes(
+            &self,
+            slice : &[u8],
         ) -> bool {
             if slice.is_empty() {
                 return false;
             }
 
-            let c0 = match slice.chars().next() {
-                Some(c0) => c0,
-                None => return false,
+            let (c0, n) = if self.pure_bytes {
+                (Some(char::from(slice[0])), 1)
+            } else {
+                decode_utf8_unit(slice)
             };
 
-            #[cfg(feature = "lookup-ranges")]
-            if self.character_range.contains_key(&c0) {
-                return false;
-            }
-            #[cfg(not(feature = "lookup-ranges"))]
-            if self.character_range.contains(c0) {
-                return false;
+            // an invalid byte is not any of the range characters
+            if let Some(c0) = c0 {
+                if range_contains(&self.character_range, c0) {
+                    return false;
+                }
             }
 
             let next = self.next.as_ref();
 
-            next.matches(&slice[c0.len_utf8()..])
+            next.matches_bytes(&slice[n..])
         }
-    }
 
-    impl Match for MatchRange {
-        fn matches(
+        fn captures(
             &self,
-            slice : &str,
+            input : &str,
+            offset : usize,
+            greedy : bool,
+            spans : &mut Vec<std_ops::Range<usize>>,
         ) -> bool {
-            if slice.is_empty() {
-                return false;
-            }
-
-            let c0 = match slice.chars().next() {
-                Some(c0) => c0,
+            let n = match self.consumed_(&input[offset..]) {
+                Some(n) => n,
                 None => return false,
             };
 
-            #[cfg(feature = "lookup-ranges")]
-            if !self.character_range.contains_key(&c0) {
-                return false;
-            }
-            #[cfg(not(feature = "lookup-ranges"))]
-            if !self.character_range.contains(c0) {
-                return false;
+            spans.push(offset..offset + n);
+
+            if self.next.captures(input, offset + n, greedy, spans) {
+                return true;
             }
 
-            let next = self.next.as_ref();
+            spans.pop();
+
+            false
+        }
 
-            next.matches(&slice[c0.len_utf8()..])
+        fn take_next(&mut self) -> Option<Box<dyn Match>> {
+            Some(std_mem::replace(&mut self.next, Box::new(MatchEnd {})))
         }
     }
 
-    impl Match for MatchWild1 {
+    impl Match for MatchRange {
         fn matches(
             &self,
             slice : &str,
+        ) -> bool {
+            match self.consumed_(slice) {
+                Some(n) => self.next.matches(&slice[n..]),
+                None => false,
+            }
+        }
+
+        fn matches_bytes(
+            &self,
+            slice : &[u8],
         ) -> bool {
             if slice.is_empty() {
                 return false;
             }
 
-            let c0 = slice.chars().next().unwrap();
+            let (c0, n) = if self.pure_bytes {
+                (Some(char::from(slice[0])), 1)
+            } else {
+                decode_utf8_unit(slice)
+            };
+
+            match c0 {
+                Some(c0) if range_contains(&self.character_range, c0) => (),
+                _ => return false,
+            };
 
             let next = self.next.as_ref();
 
-            next.matches(&slice[c0.len_utf8()..])
+            next.matches_bytes(&slice[n..])
         }
-    }
 
-    impl Match for MatchWildN {
-        fn matches(
+        fn captures(
             &self,
-            slice : &str,
+            input : &str,
+            offset : usize,
+            greedy : bool,
+            spans : &mut Vec<std_ops::Range<usize>>,
         ) -> bool {
-            let mut offset = 0;
-
-            // TODO: consider using `#char_indices()`
-
-            let next = self.next.as_ref();
+            let n = match self.consumed_(&input[offset..]) {
+                Some(n) => n,
+                None => return false,
+            };
 
-            for c in slice.chars() {
-                if next.matches(&slice[offset..]) {
-                    return true;
-                } else {
-                    offset += c.len_utf8();
-                }
-            }
+            spans.push(offset..offset + n);
 
-            if next.matches(&slice[offset..]) {
+            if self.next.captures(input, offset + n, greedy, spans) {
                 return true;
             }
 
+            spans.pop();
+
             false
         }
-    }
 
+        fn take_next(&mut self) -> Option<Box<dyn Match>> {
+            Some(std_mem::replace(&mut self.next, Box::new(MatchEnd {})))
+        }
+    }
 
-    #[cfg(test)]
+    impl Match for MatchWild1 {
+        fn matches(
+            &self,
+            slice : &str,
+        ) -> bool {
+            match self.consumed_(slice) {
+                Some(n) => self.next.matches(&slice[n..]),
+                None => false,
+            }
+        }
+
+        fn matches_bytes(
+            &self,
+            slice : &[u8],
+        ) -> bool {
+            if slice.is_empty() {
+                return false;
+            }
+
+            let n = if self.pure_bytes { 1 } else { decode_utf8_unit(slice).1 };
+
+            let next = self.next.as_ref();
+
+            next.matches_bytes(&slice[n..])
+        }
+
+        fn captures(
+            &self,
+            input : &str,
+            offset : usize,
+            greedy : bool,
+            spans : &mut Vec<std_ops::Range<usize>>,
+        ) -> bool {
+            let n = match self.consumed_(&input[offset..]) {
+                Some(n) => n,
+                None => return false,
+            };
+
+            spans.push(offset..offset + n);
+
+            if self.next.captures(input, offset + n, greedy, spans) {
+                return true;
+            }
+
+            spans.pop();
+
+            false
+        }
+
+        fn take_next(&mut self) -> Option<Box<dyn Match>> {
+            Some(std_mem::replace(&mut self.next, Box::new(MatchEnd {})))
+        }
+    }
+
+    impl Match for MatchWildN {
+        fn matches(
+            &self,
+            slice : &str,
+        ) -> bool {
+            let mut offset = 0;
+
+            // TODO: consider using `#char_indices()`
+
+            let next = self.next.as_ref();
+
+            #[cfg(feature = "grapheme-clusters")]
+            if self.grapheme_clusters {
+                return slice
+                    .grapheme_indices(true)
+                    .any(|(offset, _)| next.matches(&slice[offset..]))
+                    || next.matches("");
+            }
+
+            for c in slice.chars() {
+                if next.matches(&slice[offset..]) {
+                    return true;
+                } else {
+                    offset += c.len_utf8();
+                }
+            }
+
+            if next.matches(&slice[offset..]) {
+                return true;
+            }
+
+            false
+        }
+
+        fn matches_bytes(
+            &self,
+            slice : &[u8],
+        ) -> bool {
+            let mut offset = 0;
+
+            let next = self.next.as_ref();
+
+            while offset < slice.len() {
+                if next.matches_bytes(&slice[offset..]) {
+                    return true;
+                }
+
+                offset += if self.pure_bytes {
+                    1
+                } else {
+                    decode_utf8_unit(&slice[offset..]).1
+                };
+            }
+
+            next.matches_bytes(&slice[offset..])
+        }
+
+        fn captures(
+            &self,
+            input : &str,
+            offset : usize,
+            greedy : bool,
+            spans : &mut Vec<std_ops::Range<usize>>,
+        ) -> bool {
+            let slice = &input[offset..];
+
+            #[cfg(feature = "grapheme-clusters")]
+            let mut ends : Vec<usize> = if self.grapheme_clusters {
+                slice.grapheme_indices(true).map(|(index, _)| index).collect()
+            } else {
+                slice.char_indices().map(|(index, _)| index).collect()
+            };
+            #[cfg(not(feature = "grapheme-clusters"))]
+            let mut ends : Vec<usize> = slice.char_indices().map(|(index, _)| index).collect();
+
+            ends.push(slice.len());
+
+            if greedy {
+                ends.reverse();
+            }
+
+            let next = self.next.as_ref();
+
+            for n in ends {
+                spans.push(offset..offset + n);
+
+                if next.captures(input, offset + n, greedy, spans) {
+                    return true;
+                }
+
+                spans.pop();
+            }
+
+            false
+        }
+
+        fn take_next(&mut self) -> Option<Box<dyn Match>> {
+            Some(std_mem::replace(&mut self.next, Box::new(MatchEnd {})))
+        }
+    }
+
+
+    #[cfg(test)]
     mod tests {
         #![allow(non_snake_case)]
 
@@ -647,7 +1296,7 @@ mod match_structures {
             #[test]
             fn TEST_Wild_1() {
                 let me : Box<dyn Match> = Box::new(MatchEnd {});
-                let m1 : Box<dyn Match> = Box::new(MatchWild1::new(me));
+                let m1 : Box<dyn Match> = Box::new(MatchWild1::new(me, 0));
 
                 let matcher : &dyn Match = &*m1;
 
@@ -670,8 +1319,8 @@ mod match_structures {
             #[test]
             fn TEST_Wild_2() {
                 let me : Box<dyn Match> = Box::new(MatchEnd {});
-                let mw2 : Box<dyn Match> = Box::new(MatchWild1::new(me));
-                let mw1 : Box<dyn Match> = Box::new(MatchWild1::new(mw2));
+                let mw2 : Box<dyn Match> = Box::new(MatchWild1::new(me, 0));
+                let mw1 : Box<dyn Match> = Box::new(MatchWild1::new(mw2, 0));
 
                 let matcher : &dyn Match = &*mw1;
 
@@ -703,7 +1352,7 @@ mod match_structures {
             #[test]
             fn TEST_WildN_1() {
                 let me : Box<dyn Match> = Box::new(MatchEnd {});
-                let mw : Box<dyn Match> = Box::new(MatchWildN::new(me));
+                let mw : Box<dyn Match> = Box::new(MatchWildN::new(me, 0));
 
                 let matcher : &dyn Match = &*mw;
 
@@ -717,6 +1366,115 @@ mod match_structures {
         }
 
 
+        mod TESTING_BYTES {
+            #![allow(non_snake_case)]
+
+            use super::{
+                super::super::constants::PURE_BYTES,
+                *,
+            };
+
+
+            #[test]
+            fn TEST_Wild1_BYTES_1() {
+                let me : Box<dyn Match> = Box::new(MatchEnd {});
+                let m1 : Box<dyn Match> = Box::new(MatchWild1::new(me, 0));
+
+                let matcher : &dyn Match = &*m1;
+
+                assert!(!matcher.matches_bytes(b""));
+                assert!(matcher.matches_bytes(b"a"));
+                assert!(matcher.matches_bytes(b"\xff"));
+                assert!(matcher.matches_bytes("é".as_bytes()));
+                assert!(matcher.matches_bytes("🐻".as_bytes()));
+                assert!(!matcher.matches_bytes(b"\xff\xff"));
+                assert!(!matcher.matches_bytes(b"ab"));
+
+                // a truncated sequence is a series of invalid bytes
+                assert!(!matcher.matches_bytes(&"🐻".as_bytes()[..2]));
+            }
+
+            #[test]
+            fn TEST_Wild1_BYTES_HAVING__PURE_BYTES__1() {
+                let me : Box<dyn Match> = Box::new(MatchEnd {});
+                let m1 : Box<dyn Match> = Box::new(MatchWild1::new(me, PURE_BYTES));
+
+                let matcher : &dyn Match = &*m1;
+
+                assert!(!matcher.matches_bytes(b""));
+                assert!(matcher.matches_bytes(b"a"));
+                assert!(matcher.matches_bytes(b"\xff"));
+                assert!(!matcher.matches_bytes("é".as_bytes()));
+                assert!(!matcher.matches_bytes("🐻".as_bytes()));
+            }
+
+            #[test]
+            fn TEST_Range_AND_NotRange_BYTES_1() {
+                let characters = "aé";
+                let flags = 0;
+                #[cfg(feature = "lookup-ranges")]
+                let character_range =
+                    || prepare_range_upm_from_slice(&characters.chars().collect::<Vec<char>>(), flags);
+                #[cfg(not(feature = "lookup-ranges"))]
+                let character_range = || prepare_range_string(characters, flags);
+
+                {
+                    let me : Box<dyn Match> = Box::new(MatchEnd {});
+                    let mr : Box<dyn Match> = Box::new(MatchRange::new(me, character_range(), 0));
+
+                    let matcher : &dyn Match = &*mr;
+
+                    assert!(!matcher.matches_bytes(b""));
+                    assert!(matcher.matches_bytes(b"a"));
+                    assert!(matcher.matches_bytes("é".as_bytes()));
+                    assert!(!matcher.matches_bytes(b"b"));
+                    assert!(!matcher.matches_bytes(b"\xe9"));
+                    assert!(!matcher.matches_bytes(b"\xff"));
+                }
+
+                {
+                    let me : Box<dyn Match> = Box::new(MatchEnd {});
+                    let mn : Box<dyn Match> = Box::new(MatchNotRange::new(me, character_range(), 0));
+
+                    let matcher : &dyn Match = &*mn;
+
+                    assert!(!matcher.matches_bytes(b""));
+                    assert!(!matcher.matches_bytes(b"a"));
+                    assert!(!matcher.matches_bytes("é".as_bytes()));
+                    assert!(matcher.matches_bytes(b"b"));
+                    assert!(matcher.matches_bytes(b"\xff"));
+                }
+
+                {
+                    // as Latin-1, 0xE9 is 'é'
+                    let me : Box<dyn Match> = Box::new(MatchEnd {});
+                    let mr : Box<dyn Match> = Box::new(MatchRange::new(me, character_range(), PURE_BYTES));
+
+                    let matcher : &dyn Match = &*mr;
+
+                    assert!(matcher.matches_bytes(b"a"));
+                    assert!(matcher.matches_bytes(b"\xe9"));
+                    assert!(!matcher.matches_bytes("é".as_bytes()));
+                }
+            }
+
+            #[test]
+            fn TEST_Literal_WildN_BYTES_1() {
+                let literal = "log".into();
+
+                let me : Box<dyn Match> = Box::new(MatchEnd {});
+                let ml : Box<dyn Match> = Box::new(MatchLiteral::new(me, literal, 0));
+                let mw : Box<dyn Match> = Box::new(MatchWildN::new(ml, 0));
+
+                let matcher : &dyn Match = &*mw;
+
+                assert!(matcher.matches_bytes(b"log"));
+                assert!(matcher.matches_bytes(b"\xff\xfe.log"));
+                assert!(!matcher.matches_bytes(b"\xff\xfe.lo"));
+            }
+        }
+
+
         mod TESTING_MISC {
             #![allow(non_snake_case)]
 
@@ -728,7 +1486,7 @@ mod match_structures {
                 let literal = "ma".into();
 
                 let me : Box<dyn Match> = Box::new(MatchEnd {});
-                let mw : Box<dyn Match> = Box::new(MatchWildN::new(me));
+                let mw : Box<dyn Match> = Box::new(MatchWildN::new(me, 0));
                 let ml : Box<dyn Match> = Box::new(MatchLiteral::new(mw, literal, 0));
 
                 let matcher : &dyn Match = &*ml;
@@ -748,7 +1506,7 @@ mod match_structures {
 
                 let me : Box<dyn Match> = Box::new(MatchEnd {});
                 let ml2 : Box<dyn Match> = Box::new(MatchLiteral::new(me, literal2, 0));
-                let mw : Box<dyn Match> = Box::new(MatchWildN::new(ml2));
+                let mw : Box<dyn Match> = Box::new(MatchWildN::new(ml2, 0));
                 let ml1 : Box<dyn Match> = Box::new(MatchLiteral::new(mw, literal1, 0));
 
                 let matcher : &dyn Match = &*ml1;
@@ -783,6 +1541,7 @@ mod utils {
     use std::{
         fmt as std_fmt,
         mem as std_mem,
+        ops as std_ops,
     };
 
 
@@ -876,6 +1635,39 @@ mod utils {
     }
 
 
+    /// Separates the given range characters into their extended grapheme
+    /// clusters, preparing the single-character clusters as a character
+    /// range and returning the multi-character clusters separately.
+    #[cfg(feature = "grapheme-clusters")]
+    pub(crate) fn prepare_cluster_range(
+        range_chars : &[char],
+        flags : i64,
+    ) -> (CharacterRangeType, Vec<String>) {
+        use unicode_segmentation::UnicodeSegmentation as _;
+
+        let range_string = String::from_iter(range_chars);
+
+        let mut single_chars = vec![];
+        let mut clusters = vec![];
+
+        for cluster in range_string.graphemes(true) {
+            let mut chars = cluster.chars();
+
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => single_chars.push(c),
+                _ => clusters.push(cluster.to_string()),
+            };
+        }
+
+        #[cfg(feature = "lookup-ranges")]
+        let character_range = prepare_range_upm_from_slice(&single_chars, flags);
+        #[cfg(not(feature = "lookup-ranges"))]
+        let character_range = prepare_range_string_from_slice(&single_chars, flags);
+
+        (character_range, clusters)
+    }
+
+
     pub(crate) struct MatcherSequence {
         /// The head of the chain.
         matcher0 :     Box<dyn Match>,
@@ -984,6 +1776,64 @@ mod utils {
             1 + following_minimum_required
         }
 
+        /// Prepends a not-range that, in `GRAPHEME_CLUSTERS` mode, also
+        /// evaluates the given multi-character `clusters`.
+        ///
+        /// # Returns:
+        /// `total_minimum_required : usize` - the total minimum required of
+        /// this and all following instances.
+        #[cfg(feature = "grapheme-clusters")]
+        #[must_use]
+        pub(crate) fn prepend_ClusterNotRange(
+            &mut self,
+            character_range : CharacterRangeType,
+            clusters : Vec<String>,
+            flags : i64,
+            following_minimum_required : usize,
+        ) -> usize {
+            let mut next : Box<dyn Match> = Box::new(MatchEnd {});
+
+            std_mem::swap(&mut self.matcher0, &mut next);
+
+            let mut matcher : Box<dyn Match> =
+                Box::new(MatchNotRange::new(next, character_range, flags).with_clusters(clusters, flags));
+
+            std_mem::swap(&mut self.matcher0, &mut matcher);
+
+            self.num_matchers += 1;
+
+            1 + following_minimum_required
+        }
+
+        /// Prepends a range that, in `GRAPHEME_CLUSTERS` mode, also
+        /// evaluates the given multi-character `clusters`.
+        ///
+        /// # Returns:
+        /// `total_minimum_required : usize` - the total minimum required of
+        /// this and all following instances.
+        #[cfg(feature = "grapheme-clusters")]
+        #[must_use]
+        pub(crate) fn prepend_ClusterRange(
+            &mut self,
+            character_range : CharacterRangeType,
+            clusters : Vec<String>,
+            flags : i64,
+            following_minimum_required : usize,
+        ) -> usize {
+            let mut next : Box<dyn Match> = Box::new(MatchEnd {});
+
+            std_mem::swap(&mut self.matcher0, &mut next);
+
+            let mut matcher : Box<dyn Match> =
+                Box::new(MatchRange::new(next, character_range, flags).with_clusters(clusters, flags));
+
+            std_mem::swap(&mut self.matcher0, &mut matcher);
+
+            self.num_matchers += 1;
+
+            1 + following_minimum_required
+        }
+
         /// T.B.C.
         ///
         /// # Returns:
@@ -992,6 +1842,7 @@ mod utils {
         #[must_use]
         pub(crate) fn prepend_Wild1(
             &mut self,
+            flags : i64,
             following_minimum_required : usize,
         ) -> usize {
             let mut next : Box<dyn Match> = Box::new(MatchEnd {});
@@ -1000,7 +1851,7 @@ mod utils {
 
             // NOW: `next` is the head of the list, and `self.matcher0` is `MatchEnd`
 
-            let mut matcher : Box<dyn Match> = Box::new(MatchWild1::new(next));
+            let mut matcher : Box<dyn Match> = Box::new(MatchWild1::new(next, flags));
 
             std_mem::swap(&mut self.matcher0, &mut matcher);
 
@@ -1017,6 +1868,7 @@ mod utils {
         #[must_use]
         pub(crate) fn prepend_WildN(
             &mut self,
+            flags : i64,
             following_minimum_required : usize,
         ) -> usize {
             #![allow(clippy::identity_op)] // for clarity of semantics of return value
@@ -1027,7 +1879,7 @@ mod utils {
 
             // NOW: `next` is the head of the list, and `self.matcher0` is `MatchEnd`
 
-            let mut matcher : Box<dyn Match> = Box::new(MatchWildN::new(next));
+            let mut matcher : Box<dyn Match> = Box::new(MatchWildN::new(next, flags));
 
             std_mem::swap(&mut self.matcher0, &mut matcher);
 
@@ -1054,10 +1906,52 @@ mod utils {
 
             matcher.matches(input)
         }
+
+        #[inline]
+        pub(crate) fn matches_bytes(
+            &self,
+            input : &[u8],
+        ) -> bool {
+            let matcher = &self.matcher0;
+
+            matcher.matches_bytes(input)
+        }
+
+        /// Obtains the span of `input` consumed by each wildcard element,
+        /// or `None` if `input` does not match.
+        pub(crate) fn captures(
+            &self,
+            input : &str,
+            greedy : bool,
+        ) -> Option<Vec<std_ops::Range<usize>>> {
+            let matcher = &self.matcher0;
+
+            let mut spans = vec![];
+
+            if matcher.captures(input, 0, greedy, &mut spans) {
+                Some(spans)
+            } else {
+                None
+            }
+        }
     }
 
     // Trait implementations
 
+    impl Drop for MatcherSequence {
+        /// Dismantles the chain iteratively, since the default (recursive)
+        /// drop of a long chain of boxed matchers would exhaust the stack.
+        fn drop(&mut self) {
+            let mut matcher : Box<dyn Match> = Box::new(MatchEnd {});
+
+            std_mem::swap(&mut self.matcher0, &mut matcher);
+
+            while let Some(next) = matcher.take_next() {
+                matcher = next;
+            }
+        }
+    }
+
     impl std_fmt::Debug for MatcherSequence {
         fn fmt(
             &self,
@@ -1297,7 +2191,7 @@ mod utils {
                 assert_eq!(4, minimum_required);
 
                 {
-                    minimum_required = matchers.prepend_WildN(minimum_required);
+                    minimum_required = matchers.prepend_WildN(flags, minimum_required);
                 }
 
                 assert_eq!(4, minimum_required);
@@ -1433,6 +2327,68 @@ mod utils {
 }
 
 
+mod automaton;
+mod captures;
+mod case;
+mod generate;
+mod gitignore;
+mod language;
+mod parallel;
+mod paths;
+mod pattern;
+mod pattern_set;
+mod replace;
+mod rules;
+mod search;
+#[cfg(test)]
+mod test_utils;
+mod translate;
+mod walk;
+
+pub use automaton::DfaMode;
+pub use captures::{
+    CaptureMode,
+    Captures,
+};
+pub use generate::{
+    RandomSource,
+    SeededRandom,
+};
+pub use gitignore::{
+    Gitignore,
+    IgnoreMatch,
+};
+pub use parallel::{
+    CancelToken,
+    ParallelGlob,
+};
+pub use pattern::{
+    Pattern,
+    PatternBuilder,
+    PatternElement,
+    Visitor,
+};
+pub use pattern_set::PatternSet;
+pub use replace::{
+    Collision,
+    Rename,
+    RenamePlan,
+};
+pub use rules::{
+    Rule,
+    RuleMatch,
+    RuleSet,
+};
+pub use search::{
+    FindIter,
+    FindMode,
+};
+pub use walk::{
+    Glob,
+    GlobOptions,
+};
+
+
 // /////////////////////////////////////////////////////////
 // types
 
@@ -1463,7 +2419,14 @@ pub type Result<T> = std_result::Result<T, Error>;
 /// ```
 #[derive(Debug)]
 pub struct CompiledMatcher {
-    matchers : utils::MatcherSequence,
+    matchers :      utils::MatcherSequence,
+    elements :      Vec<PatternElement>,
+    flags :         i64,
+    dfa :           Option<automaton::DfaMatcher>,
+    /// The position automaton, created on first use.
+    nfa :           std_sync::OnceLock<automaton::Nfa>,
+    /// The per-component matchers, created on first use.
+    path_segments : std_sync::OnceLock<paths::PathSegments>,
 }
 
 // API functions
@@ -1473,21 +2436,64 @@ impl CompiledMatcher {
         pattern : &str,
         flags : i64,
     ) -> Result<Self> {
-        let mut matchers = utils::MatcherSequence::new();
+        let elements = Self::parse_(pattern, flags)?;
 
-        let line = 0;
-        let column = 0;
-
-        Self::parse_(&mut matchers, pattern, flags, line, column).map(|_| {
-            Self {
-                matchers,
-            }
-        })
-    }
-}
+        Ok(Self::from_elements_(elements, flags))
+    }
+}
 
 // Mutating methods
 impl CompiledMatcher {
+    /// Determinises the pattern into a DFA over character classes, which is
+    /// thereafter used by [`matches()`](Self::matches) in place of the
+    /// backtracking interpreter, evaluating each (ASCII) byte of the input
+    /// with a single table lookup.
+    ///
+    /// In [`DfaMode::Eager`] all reachable states are constructed
+    /// immediately; in [`DfaMode::Lazy`] states are constructed as first
+    /// reached, and should `state_budget` later be exceeded matching
+    /// reverts to the interpreter. (A lazy DFA may be shared by threads:
+    /// each walks the constructed states concurrently with the others,
+    /// and only waits when a state must be constructed.) A DFA is not
+    /// available for patterns compiled with [`PURE_BYTES`], nor for those
+    /// compiled with [`IGNORE_CASE`] whose literals may match sequences
+    /// of characters of a different length, e.g. `"ß"`, which matches
+    /// `"ss"`.
+    ///
+    /// # Parameters:
+    /// - `mode` - the determinisation mode;
+    /// - `state_budget` - the maximum number of DFA states;
+    ///
+    /// # Returns:
+    /// `true` if a DFA is in use; `false` if the budget is insufficient,
+    /// or a DFA is not available, in which case the interpreter continues
+    /// to be used.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::{
+    ///     CompiledMatcher,
+    ///     DfaMode,
+    /// };
+    ///
+    /// let mut matcher =
+    ///     CompiledMatcher::from_pattern_and_flags("*.[ch]", 0).unwrap();
+    ///
+    /// assert!(matcher.enable_dfa(DfaMode::Eager, 1_000));
+    ///
+    /// assert!(matcher.matches("lib.c"));
+    /// assert!(!matcher.matches("lib.rs"));
+    /// ```
+    pub fn enable_dfa(
+        &mut self,
+        mode : DfaMode,
+        state_budget : usize,
+    ) -> bool {
+        self.dfa = automaton::DfaMatcher::new(&self.elements, self.flags, mode, state_budget);
+
+        self.dfa.is_some()
+    }
 }
 
 // Non-mutating methods
@@ -1508,358 +2514,1502 @@ impl CompiledMatcher {
         &self,
         input : &str,
     ) -> bool {
+        if 0 != (self.flags & constants::PURE_BYTES) {
+            return self.matchers.matches_bytes(input.as_bytes());
+        }
+
+        if let Some(dfa) = &self.dfa {
+            if let Some(b) = dfa.matches(input) {
+                return b;
+            }
+        }
+
         self.matchers.matches(input)
     }
-}
 
+    /// Determines whether the given `input` bytes match the instance's
+    /// compiled pattern, without requiring `input` to be valid UTF-8.
+    ///
+    /// A `?` consumes one UTF-8 sequence or, where the input is not valid
+    /// UTF-8, one (invalid) byte; a range is evaluated against the decoded
+    /// character, so that an invalid byte never matches a range but always
+    /// matches a not-range. With [`PURE_BYTES`] specified at compilation,
+    /// each of `?`, ranges, and not-ranges instead consumes exactly one
+    /// byte.
+    ///
+    /// # Parameters:
+    /// - `input` - the bytes to be evaluated;
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::{
+    ///     CompiledMatcher,
+    ///     PURE_BYTES,
+    /// };
+    ///
+    /// let matcher =
+    ///     CompiledMatcher::from_pattern_and_flags("f?.log", 0).unwrap();
+    ///
+    /// assert!(matcher.matches_bytes(b"f\xff.log"));
+    /// assert!(matcher.matches_bytes("fé.log".as_bytes()));
+    ///
+    /// let matcher =
+    ///     CompiledMatcher::from_pattern_and_flags("f??.log", PURE_BYTES)
+    ///         .unwrap();
+    ///
+    /// assert!(matcher.matches_bytes("fé.log".as_bytes()));
+    /// ```
+    #[inline]
+    pub fn matches_bytes(
+        &self,
+        input : &[u8],
+    ) -> bool {
+        self.matchers.matches_bytes(input)
+    }
 
-#[derive(Debug)]
-enum ParseState {
-    None,
-    InLiteral,
-    InNotRange,
-    InRange,
-}
+    /// Determines whether the given `input` matches the instance's compiled
+    /// pattern, without any (lossy) conversion of `input` to a string.
+    ///
+    /// `input` is evaluated as by [`matches_bytes()`](Self::matches_bytes)
+    /// on its encoded bytes, which on Unix are its raw bytes and on Windows
+    /// are its WTF-8 encoding (and so, for valid Unicode, its UTF-8
+    /// encoding).
+    ///
+    /// # Parameters:
+    /// - `input` - the OS string to be evaluated;
+    #[inline]
+    pub fn matches_os_str(
+        &self,
+        input : &std_ffi::OsStr,
+    ) -> bool {
+        self.matchers.matches_bytes(input.as_encoded_bytes())
+    }
 
-// Implementation
-impl CompiledMatcher {
-    fn parse_(
-        matchers : &mut utils::MatcherSequence,
-        pattern : &str,
-        flags : i64,
-        line : usize,
-        column : usize,
-    ) -> Result<(
-        usize, // minimum_required
-        usize, // num_matchers
-    )> {
-        let mut line = line;
-        let mut column = column;
+    /// Determines whether the given `path` matches the instance's compiled
+    /// pattern, evaluating the whole path textually, as by
+    /// [`matches_os_str()`](Self::matches_os_str), such that separators
+    /// are matched as any other character.
+    ///
+    /// # Parameters:
+    /// - `path` - the path to be evaluated;
+    #[inline]
+    pub fn matches_path(
+        &self,
+        path : &std_path::Path,
+    ) -> bool {
+        self.matches_os_str(path.as_os_str())
+    }
 
-        let mut minimum_required = 0;
-        let mut num_matchers = 0;
-        let mut state = ParseState::None;
-        let mut s = vec![];
-        let mut escaped = false;
-        let mut continuum_prior = None;
-        let mut num_bytes = 0;
+    /// Determines whether the given `path` matches the instance's compiled
+    /// pattern component-wise, i.e. with separators handled structurally
+    /// rather than textually.
+    ///
+    /// The pattern is split at each `'/'` into segments, each of which must
+    /// match exactly one component of `path` (as evaluated by
+    /// [`matches_os_str()`](Self::matches_os_str)), such that:
+    /// - `*`, `?`, and ranges never match a separator;
+    /// - a segment that is exactly `**` matches any number (0 or more) of
+    ///   components;
+    /// - repeated, trailing, and `.` components are not significant in
+    ///   either pattern or path;
+    /// - a pattern that begins with `'/'` matches only an absolute path,
+    ///   and one that does not matches only a relative path;
+    ///
+    /// # Parameters:
+    /// - `path` - the path to be evaluated;
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::CompiledMatcher;
+    /// use std::path::Path;
+    ///
+    /// let pattern = "src/**/*.rs";
+    /// let matcher = CompiledMatcher::from_pattern_and_flags(pattern, 0);
+    /// let matcher = matcher.unwrap();
+    ///
+    /// assert!(matcher.matches_path_components(Path::new("src/lib.rs")));
+    /// assert!(matcher.matches_path_components(Path::new("./src/a/b.rs")));
+    /// assert!(!matcher.matches_path_components(Path::new("src/a/b.c")));
+    /// ```
+    pub fn matches_path_components(
+        &self,
+        path : &std_path::Path,
+    ) -> bool {
+        self.path_segments
+            .get_or_init(|| paths::PathSegments::from_elements(&self.elements, self.flags))
+            .matches(path)
+    }
 
-        for c in pattern.chars() {
-            debug_assert!(
-                continuum_prior.is_none() || std_matches!(state, ParseState::InNotRange | ParseState::InRange)
-            );
+    /// Finds the leftmost-longest occurrence of the instance's compiled
+    /// pattern anywhere in `input`, i.e. without the pattern being
+    /// anchored to the start and end of `input`.
+    ///
+    /// This is equivalent to
+    /// [`find_with_mode()`](Self::find_with_mode) with
+    /// [`FindMode::LeftmostLongest`].
+    ///
+    /// # Parameters:
+    /// - `input` - the string to be searched;
+    ///
+    /// # Returns:
+    /// The byte range of the occurrence, or `None` if there is none.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::CompiledMatcher;
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags("err*:", 0);
+    /// let matcher = matcher.unwrap();
+    ///
+    /// assert_eq!(Some(3..11), matcher.find("1: error 7: 8"));
+    /// assert_eq!(None, matcher.find("2: warning"));
+    /// ```
+    #[inline]
+    pub fn find(
+        &self,
+        input : &str,
+    ) -> Option<std_ops::Range<usize>> {
+        self.find_with_mode(input, FindMode::LeftmostLongest)
+    }
 
-            if escaped {
-                match c {
-                    // TODO: do a lookup table
-                    'n' => {
-                        s.push('\n');
-                    },
-                    'r' => {
-                        s.push('\r');
-                    },
-                    't' => {
-                        s.push('\t');
-                    },
-                    _ => {
-                        s.push(c);
-                    },
-                };
+    /// Finds the occurrence of the instance's compiled pattern anywhere in
+    /// `input` that is selected by `mode`.
+    ///
+    /// `?`, ranges, and not-ranges are evaluated as by
+    /// [`matches()`](Self::matches), so that, with [`PURE_BYTES`], the
+    /// bounds of the occurrence need not be character boundaries.
+    ///
+    /// The occurrence is found in a single pass over `input`, except with
+    /// [`PURE_BYTES`] or `GRAPHEME_CLUSTERS`, or with [`IGNORE_CASE`]
+    /// and a literal that may match a sequence of characters of a
+    /// different length (e.g. `"ß"`, which matches `"ss"`), in which case
+    /// the candidate occurrences are each evaluated as by `matches()`, at
+    /// a cost that may be quadratic in the length of `input`.
+    ///
+    /// # Parameters:
+    /// - `input` - the string to be searched;
+    /// - `mode` - the choice between candidate occurrences;
+    ///
+    /// # Returns:
+    /// The byte range of the occurrence, or `None` if there is none.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::{
+    ///     CompiledMatcher,
+    ///     FindMode,
+    /// };
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags("<*>", 0);
+    /// let matcher = matcher.unwrap();
+    /// let input = "<b>bold</b>";
+    ///
+    /// let longest = FindMode::LeftmostLongest;
+    /// let shortest = FindMode::LeftmostShortest;
+    ///
+    /// assert_eq!(Some(0..11), matcher.find_with_mode(input, longest));
+    /// assert_eq!(Some(0..3), matcher.find_with_mode(input, shortest));
+    /// ```
+    #[inline]
+    pub fn find_with_mode(
+        &self,
+        input : &str,
+        mode : FindMode,
+    ) -> Option<std_ops::Range<usize>> {
+        self.find_at_(input, 0, mode, self.find_boundaries_(input).as_deref())
+    }
 
-                escaped = false;
+    /// Obtains an iterator over the successive non-overlapping
+    /// leftmost-longest occurrences of the instance's compiled pattern in
+    /// `input`.
+    ///
+    /// This is equivalent to
+    /// [`find_iter_with_mode()`](Self::find_iter_with_mode) with
+    /// [`FindMode::LeftmostLongest`].
+    ///
+    /// # Parameters:
+    /// - `input` - the string to be searched;
+    #[inline]
+    pub fn find_iter<'i>(
+        &self,
+        input : &'i str,
+    ) -> FindIter<'_, 'i> {
+        self.find_iter_with_mode(input, FindMode::LeftmostLongest)
+    }
 
-                if std_matches!(state, ParseState::None) {
-                    state = ParseState::InLiteral;
-                }
-            } else {
-                match c {
-                    '[' => {
-                        match state {
-                            ParseState::None => {
-                                state = ParseState::InRange;
-                            },
-                            ParseState::InLiteral => {
-                                debug_assert!(!s.is_empty(), "`s` expected to be not empty, but is found to be so");
+    /// Obtains an iterator over the successive non-overlapping occurrences
+    /// of the instance's compiled pattern in `input`, each selected by
+    /// `mode`.
+    ///
+    /// # Parameters:
+    /// - `input` - the string to be searched;
+    /// - `mode` - the choice between candidate occurrences;
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::{
+    ///     CompiledMatcher,
+    ///     FindMode,
+    /// };
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags("<*>", 0);
+    /// let matcher = matcher.unwrap();
+    /// let input = "<b>bold</b>";
+    ///
+    /// let tags : Vec<&str> = matcher
+    ///     .find_iter_with_mode(input, FindMode::LeftmostShortest)
+    ///     .map(|range| &input[range])
+    ///     .collect();
+    ///
+    /// assert_eq!(vec!["<b>", "</b>"], tags);
+    /// ```
+    #[inline]
+    pub fn find_iter_with_mode<'i>(
+        &self,
+        input : &'i str,
+        mode : FindMode,
+    ) -> FindIter<'_, 'i> {
+        FindIter::new(self, input, mode)
+    }
 
-                                let literal = String::from_iter(s.iter());
+    /// Obtains the part of `input` consumed by each of the wildcard
+    /// elements of the instance's compiled pattern, where each `*` is
+    /// greedy.
+    ///
+    /// This is equivalent to
+    /// [`captures_with_mode()`](Self::captures_with_mode) with
+    /// [`CaptureMode::Greedy`].
+    ///
+    /// # Parameters:
+    /// - `input` - the string to be evaluated;
+    ///
+    /// # Returns:
+    /// The captures, or `None` if `input` does not match.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::CompiledMatcher;
+    ///
+    /// let pattern = "IMG_*.jp[e]g";
+    /// let matcher = CompiledMatcher::from_pattern_and_flags(pattern, 0);
+    /// let matcher = matcher.unwrap();
+    ///
+    /// let captures = matcher.captures("IMG_1234.jpeg").unwrap();
+    ///
+    /// assert_eq!(Some("1234"), captures.get(0));
+    /// assert_eq!(Some(4..8), captures.span(0));
+    /// assert_eq!(Some("e"), captures.get(1));
+    ///
+    /// assert!(matcher.captures("IMG_1234.png").is_none());
+    /// ```
+    #[inline]
+    pub fn captures<'i>(
+        &self,
+        input : &'i str,
+    ) -> Option<Captures<'i>> {
+        self.captures_with_mode(input, CaptureMode::Greedy)
+    }
 
-                                match Self::parse_(matchers, &pattern[num_bytes..], flags, line, column) {
-                                    Ok((following_mr, following_nm)) => {
-                                        minimum_required = following_mr;
-                                        num_matchers += following_nm;
-                                    },
-                                    Err(e) => {
-                                        return Err(e);
-                                    },
-                                };
+    /// Obtains the part of `input` consumed by each of the wildcard
+    /// elements - `?`, `*`, ranges, and not-ranges - of the instance's
+    /// compiled pattern, in pattern order, where `mode` determines how
+    /// the input is apportioned between multiple `*`s.
+    ///
+    /// The input matches (i.e. `Some` is returned) exactly when
+    /// [`matches()`](Self::matches) returns `true`, save that, since each
+    /// capture must be a part of `input`, with [`PURE_BYTES`] `input` is
+    /// nonetheless evaluated a character at a time.
+    ///
+    /// # Parameters:
+    /// - `input` - the string to be evaluated;
+    /// - `mode` - the apportionment of the input between `*`s;
+    ///
+    /// # Returns:
+    /// The captures, or `None` if `input` does not match.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::{
+    ///     CaptureMode,
+    ///     CompiledMatcher,
+    /// };
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags("*-*", 0);
+    /// let matcher = matcher.unwrap();
+    ///
+    /// let greedy = CaptureMode::Greedy;
+    /// let greedy = matcher.captures_with_mode("a-b-c", greedy);
+    /// let greedy = greedy.unwrap();
+    ///
+    /// assert_eq!(vec!["a-b", "c"], greedy.iter().collect::<Vec<_>>());
+    ///
+    /// let lazy = matcher.captures_with_mode("a-b-c", CaptureMode::Lazy);
+    /// let lazy = lazy.unwrap();
+    ///
+    /// assert_eq!(vec!["a", "b-c"], lazy.iter().collect::<Vec<_>>());
+    /// ```
+    pub fn captures_with_mode<'i>(
+        &self,
+        input : &'i str,
+        mode : CaptureMode,
+    ) -> Option<Captures<'i>> {
+        let greedy = CaptureMode::Greedy == mode;
+
+        self.matchers
+            .captures(input, greedy)
+            .map(|spans| Captures::new(input, spans))
+    }
 
-                                minimum_required = matchers.prepend_Literal(literal, flags, minimum_required);
+    /// Substitutes the captures of `input` (as obtained by
+    /// [`captures()`](Self::captures)) into `template`.
+    ///
+    /// In `template`:
+    /// - `#N` or `$N` is replaced by the `N`th (1-based) capture;
+    /// - `#{N}` or `${N}` is likewise replaced, and may be followed by a
+    ///   digit;
+    /// - `\` escapes the following character, such that `\#1` is the
+    ///   literal text `#1`;
+    ///
+    /// # Parameters:
+    /// - `input` - the string to be evaluated;
+    /// - `template` - the substitution template;
+    ///
+    /// # Returns:
+    /// `Ok(Some(replacement))` if `input` matches; `Ok(None)` if not; and
+    /// [`Error::TemplateError`] if `template` is invalid, including where
+    /// it refers to a capture that the pattern does not have.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::CompiledMatcher;
+    ///
+    /// let pattern = "IMG_*.jpeg";
+    /// let matcher = CompiledMatcher::from_pattern_and_flags(pattern, 0);
+    /// let matcher = matcher.unwrap();
+    ///
+    /// let replacement = matcher.replace("IMG_1234.jpeg", "photo-#1.jpg");
+    ///
+    /// assert_eq!(Ok(Some("photo-1234.jpg".into())), replacement);
+    /// ```
+    pub fn replace(
+        &self,
+        input : &str,
+        template : &str,
+    ) -> Result<Option<String>> {
+        let template = replace::Template::parse(template, self.num_captures_())?;
 
-                                num_matchers += 1;
+        Ok(self
+            .captures(input)
+            .map(|captures| template.expand(|index| captures.get(index).unwrap_or_default())))
+    }
 
-                                return Ok((minimum_required, num_matchers));
-                            },
-                            _ => {
-                                s.push(c);
-                            },
-                        };
-                    },
-                    '^' => {
-                        match state {
-                            ParseState::InRange if s.is_empty() => {
-                                state = ParseState::InNotRange;
-                            },
-                            _ => {
-                                s.push(c);
-                            },
-                        };
-                    },
-                    ']' => {
-                        match state {
-                            ParseState::InNotRange | ParseState::InRange => {
-                                debug_assert!(!s.is_empty(), "`s` expected to be not empty, but is found to be so");
+    /// Obtains the renames of those of `names` that match the instance's
+    /// compiled pattern, as by [`replace()`](Self::replace), along with
+    /// any collisions between them, such that a batch rename may be
+    /// verified before it is carried out.
+    ///
+    /// A collision arises where two or more names would be renamed to the
+    /// same name, where a name would be renamed to another of `names` that
+    /// is not itself renamed, or where names would be renamed to each
+    /// other in a cycle. The renames are ordered such that each name is
+    /// renamed before another is renamed to it.
+    ///
+    /// # Parameters:
+    /// - `names` - the names to be evaluated;
+    /// - `template` - the substitution template;
+    ///
+    /// # Returns:
+    /// The plan, or [`Error::TemplateError`] if `template` is invalid.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::CompiledMatcher;
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags("*_?.txt", 0);
+    /// let matcher = matcher.unwrap();
+    ///
+    /// let names = ["a_1.txt", "a_2.txt", "b_1.txt"];
+    ///
+    /// let plan = matcher.rename_plan(names, "#1.txt").unwrap();
+    ///
+    /// assert_eq!(3, plan.renames().len());
+    /// assert_eq!("a.txt", plan.renames()[0].to);
+    /// assert!(plan.has_collisions());
+    /// ```
+    pub fn rename_plan<I, S>(
+        &self,
+        names : I,
+        template : &str,
+    ) -> Result<RenamePlan>
+    where
+        I : IntoIterator<Item = S>,
+        S : AsRef<str>,
+    {
+        let template = replace::Template::parse(template, self.num_captures_())?;
+
+        let names : Vec<S> = names.into_iter().collect();
+        let names : Vec<&str> = names.iter().map(|name| name.as_ref()).collect();
+
+        let renames = names
+            .iter()
+            .filter_map(|&name| {
+                let captures = self.captures(name)?;
+
+                let to = template.expand(|index| captures.get(index).unwrap_or_default());
+
+                if to == name {
+                    None
+                } else {
+                    Some(Rename {
+                        from : name.into(),
+                        to,
+                    })
+                }
+            })
+            .collect();
 
-                                if let Some(_c) = continuum_prior {
-                                    // don't care about `_c` because that will already be pushed into `s`
+        Ok(RenamePlan::new(&names, renames))
+    }
 
-                                    s.push('-');
-                                }
+    /// Indicates whether a DFA is in use, i.e. whether
+    /// [`enable_dfa()`](Self::enable_dfa) has succeeded and, for
+    /// [`DfaMode::Lazy`], the state budget has not since been exceeded.
+    pub fn is_dfa_enabled(&self) -> bool {
+        self.dfa.as_ref().is_some_and(|dfa| dfa.is_usable())
+    }
 
-                                #[cfg(feature = "lookup-ranges")]
-                                let character_range = crate::utils::prepare_range_upm_from_slice(s.as_slice(), flags);
-                                #[cfg(not(feature = "lookup-ranges"))]
-                                let character_range =
-                                    crate::utils::prepare_range_string_from_slice(s.as_slice(), flags);
-
-                                num_bytes += 1;
-                                match Self::parse_(matchers, &pattern[num_bytes..], flags, line, column) {
-                                    Ok((following_mr, following_nm)) => {
-                                        minimum_required = following_mr;
-                                        num_matchers += following_nm;
-                                    },
-                                    Err(e) => {
-                                        return Err(e);
-                                    },
-                                };
+    /// Indicates whether the pattern consists only of literal text (with
+    /// any escapes resolved), such that it matches only that text, or, if
+    /// [`IGNORE_CASE`] is specified, only case-variants of it.
+    ///
+    /// The empty pattern is literal, and matches only the empty string.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::CompiledMatcher;
+    ///
+    /// let literal = CompiledMatcher::from_pattern_and_flags(
+    ///     r"report\[final\].txt",
+    ///     0,
+    /// )
+    /// .unwrap();
+    /// let wild = CompiledMatcher::from_pattern_and_flags("report*", 0)
+    ///     .unwrap();
+    ///
+    /// assert!(literal.is_literal());
+    /// assert_eq!("report[final].txt", literal.literal_prefix());
+    /// assert!(!wild.is_literal());
+    /// ```
+    pub fn is_literal(&self) -> bool {
+        self.elements
+            .iter()
+            .all(|element| std_matches!(element, PatternElement::Literal(_)))
+    }
 
-                                minimum_required = if std_matches!(state, ParseState::InRange) {
-                                    matchers.prepend_Range(character_range, flags, minimum_required)
-                                } else {
-                                    matchers.prepend_NotRange(character_range, flags, minimum_required)
-                                };
+    /// The literal text with which every matching input begins (subject to
+    /// [`IGNORE_CASE`]), which is empty if the pattern does not begin with
+    /// a literal; the whole of the text if [`is_literal()`].
+    ///
+    /// [`is_literal()`]: Self::is_literal
+    pub fn literal_prefix(&self) -> &str {
+        match self.elements.first() {
+            Some(PatternElement::Literal(literal)) => literal,
+            _ => "",
+        }
+    }
 
-                                num_matchers += 1;
+    /// The literal text with which every matching input ends (subject to
+    /// [`IGNORE_CASE`]), which is empty if the pattern does not end with a
+    /// literal; the whole of the text if [`is_literal()`].
+    ///
+    /// [`is_literal()`]: Self::is_literal
+    pub fn literal_suffix(&self) -> &str {
+        match self.elements.last() {
+            Some(PatternElement::Literal(literal)) => literal,
+            _ => "",
+        }
+    }
 
-                                return Ok((minimum_required, num_matchers));
-                            },
-                            _ => {
-                                s.push(c);
-                            },
-                        };
-                    },
-                    '\\' => {
-                        escaped = true;
-                    },
-                    '-' => {
-                        match state {
-                            ParseState::InNotRange | ParseState::InRange if !s.is_empty() => {
-                                continuum_prior = Some(*s.last().unwrap());
-                            },
-                            _ => {
-                                s.push(c);
-                            },
-                        };
-                    },
-                    '?' => {
-                        match state {
-                            ParseState::None => {
-                                num_bytes += 1;
-                                match Self::parse_(matchers, &pattern[num_bytes..], flags, line, column) {
-                                    Ok((following_mr, following_nm)) => {
-                                        minimum_required = following_mr;
-                                        num_matchers += following_nm;
-                                    },
-                                    Err(e) => {
-                                        return Err(e);
-                                    },
-                                };
+    /// The literals of the pattern, in pattern order, each of which occurs
+    /// (subject to [`IGNORE_CASE`]), without overlap and in that order, in
+    /// every matching input.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::CompiledMatcher;
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags(
+    ///     "*error*[01]?timeout*",
+    ///     0,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(vec!["error", "timeout"], matcher.required_literals());
+    /// ```
+    pub fn required_literals(&self) -> Vec<&str> {
+        self.elements
+            .iter()
+            .filter_map(|element| {
+                match element {
+                    PatternElement::Literal(literal) => Some(literal.as_str()),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
 
-                                minimum_required = matchers.prepend_Wild1(minimum_required);
+    /// The minimum length, in characters, of a matching (string) input, or
+    /// `None` if no input matches, as when the pattern contains the empty
+    /// range `[]`.
+    ///
+    /// With `IGNORE_CASE`, a literal may match input of a different length,
+    /// e.g. `"ss"` matches `"ß"`, which is reflected here and in
+    /// [`max_len()`](Self::max_len).
+    pub fn min_len(&self) -> Option<usize> {
+        self.len_bounds_().map(|(min_len, _)| min_len)
+    }
 
-                                num_matchers += 1;
+    /// The maximum length, in characters, of a matching (string) input, or
+    /// `None` if there is no maximum, as when the pattern contains a `*`
+    /// (or, with `GRAPHEME_CLUSTERS`, any wildcard element, since a grapheme
+    /// cluster may comprise any number of characters), or if no input
+    /// matches.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::{
+    ///     CompiledMatcher,
+    ///     IGNORE_CASE,
+    /// };
+    ///
+    /// let fixed = CompiledMatcher::from_pattern_and_flags("IMG_????", 0)
+    ///     .unwrap();
+    /// let open = CompiledMatcher::from_pattern_and_flags("IMG_*", 0)
+    ///     .unwrap();
+    /// let folded = CompiledMatcher::from_pattern_and_flags(
+    ///     "ß",
+    ///     IGNORE_CASE,
+    /// )
+    /// .unwrap();
+    /// let empty = CompiledMatcher::from_pattern_and_flags("a[]", 0)
+    ///     .unwrap();
+    ///
+    /// assert_eq!((Some(8), Some(8)), (fixed.min_len(), fixed.max_len()));
+    /// assert_eq!((Some(4), None), (open.min_len(), open.max_len()));
+    /// assert_eq!(Some(1), folded.min_len());
+    /// assert_eq!(Some(2), folded.max_len());
+    /// assert_eq!((None, None), (empty.min_len(), empty.max_len()));
+    /// ```
+    pub fn max_len(&self) -> Option<usize> {
+        self.len_bounds_().and_then(|(_, max_len)| max_len)
+    }
 
-                                return Ok((minimum_required, num_matchers));
-                            },
-                            ParseState::InLiteral => {
-                                debug_assert!(!s.is_empty(), "`s` expected to be not empty, but is found to be so");
+    /// Indicates whether the pattern matches the empty string, which is the
+    /// case when it is empty or consists only of `*`s.
+    pub fn matches_empty(&self) -> bool {
+        Some(0) == self.min_len()
+    }
 
-                                let literal = String::from_iter(s.iter());
+    /// Translates the pattern into an equivalent anchored regular
+    /// expression, in the syntax of the **regex** crate (which is also
+    /// that of PCRE, for the constructs used), such that a component that
+    /// accepts only regular expressions may evaluate it.
+    ///
+    /// The expression is subject to the `s` flag, such that `?` and `*`
+    /// match newlines, as they do in **shwild**. Where [`IGNORE_CASE`] is
+    /// specified, case-insensitivity is expressed by character classes,
+    /// with each literal character matching its case-variants and each
+    /// range or not-range character matching its ASCII case-variant, as in
+    /// matching, rather than by the `i` flag; and a literal that may match
+    /// a sequence of characters of a different length, e.g. `"ss"`, which
+    /// matches `"ß"`, is expressed by alternation, e.g. `"(?:[Ssſ][Ssſ]|ß)"`.
+    ///
+    /// NOTE: a regular expression cannot express extended grapheme
+    /// clusters, so with `GRAPHEME_CLUSTERS` each of `?`, ranges, and
+    /// not-ranges is translated as matching a single character.
+    ///
+    /// # Parameters:
+    /// - `flags` - flags that moderate the translation, in addition to
+    ///   those with which the matcher was created, such that, e.g., a
+    ///   case-insensitive expression may be obtained from a case-sensitive
+    ///   matcher by specifying [`IGNORE_CASE`];
+    ///
+    /// # Returns:
+    /// The expression, or [`Error::TranslationError`] if, with
+    /// [`IGNORE_CASE`], a literal has too many case-insensitive forms to be
+    /// expressed by alternation, e.g. one with a long run of `"s"`s.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::{
+    ///     CompiledMatcher,
+    ///     IGNORE_CASE,
+    /// };
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags(
+    ///     "IMG_*.[jp]?g",
+    ///     0,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     Ok(r"(?s)^IMG_.*\.[jp].g$".into()),
+    ///     matcher.to_regex_string(0)
+    /// );
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags(
+    ///     "img_[ab]",
+    ///     IGNORE_CASE,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     Ok("(?s)^[Iiı][Mm][Gg]_[ABab]$".into()),
+    ///     matcher.to_regex_string(0)
+    /// );
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags("img_[ab]", 0)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(Ok("(?s)^img_[ab]$".into()), matcher.to_regex_string(0));
+    /// assert_eq!(
+    ///     Ok("(?s)^[Iiı][Mm][Gg]_[ABab]$".into()),
+    ///     matcher.to_regex_string(IGNORE_CASE)
+    /// );
+    /// ```
+    pub fn to_regex_string(
+        &self,
+        flags : i64,
+    ) -> Result<String> {
+        translate::to_regex_string(&self.elements, self.flags | flags)
+    }
 
-                                match Self::parse_(matchers, &pattern[num_bytes..], flags, line, column) {
-                                    Ok((following_mr, following_nm)) => {
-                                        minimum_required = following_mr;
-                                        num_matchers += following_nm;
-                                    },
-                                    Err(e) => {
-                                        return Err(e);
-                                    },
-                                };
+    /// Translates the pattern into an equivalent SQL `LIKE` expression,
+    /// in which `*` and `?` are expressed as `%` and `_`, and any literal
+    /// `%`, `_`, or `escape_char` is escaped by `escape_char`, which must
+    /// therefore be given in the `ESCAPE` clause, e.g.
+    /// `name LIKE ? ESCAPE '\'`.
+    ///
+    /// Whether the comparison ignores case is determined by the database,
+    /// rather than by the expression (e.g. `LIKE` or `ILIKE` in
+    /// PostgreSQL, and `PRAGMA case_sensitive_like` in SQLite), so should
+    /// be chosen in accordance with [`IGNORE_CASE`].
+    ///
+    /// # Parameters:
+    /// - `escape_char` - the character with which to escape literal `%`,
+    ///   `_`, and `escape_char` characters;
+    ///
+    /// # Returns:
+    /// The expression, or [`Error::TranslationError`] if the pattern
+    /// contains a range or not-range (which `LIKE` cannot express) or
+    /// `escape_char` is `%` or `_`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::CompiledMatcher;
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags(
+    ///     "100%_*.?sv",
+    ///     0,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(Ok(r"100\%\_%._sv".into()), matcher.to_sql_like('\\'));
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags("*.[ct]sv", 0)
+    ///     .unwrap();
+    ///
+    /// assert!(matcher.to_sql_like('\\').is_err());
+    /// ```
+    pub fn to_sql_like(
+        &self,
+        escape_char : char,
+    ) -> Result<String> {
+        translate::to_sql_like(&self.elements, escape_char)
+    }
 
-                                minimum_required = matchers.prepend_Literal(literal, flags, minimum_required);
+    /// Translates the pattern into an equivalent SQLite `GLOB` expression,
+    /// in which literal `*`, `?`, and `[` characters are expressed as
+    /// single-character ranges. Since `GLOB` is always case-sensitive,
+    /// with [`IGNORE_CASE`] case-insensitivity is expressed by ranges, as
+    /// for [`to_regex_string()`](Self::to_regex_string).
+    ///
+    /// NOTE: with `GRAPHEME_CLUSTERS` each of `?`, ranges, and not-ranges
+    /// is translated as matching a single character.
+    ///
+    /// # Returns:
+    /// The expression, or [`Error::TranslationError`] if the pattern
+    /// contains an empty range (`"[]"`, which matches nothing), or, with
+    /// [`IGNORE_CASE`], a literal that may match a sequence of characters
+    /// of a different length (e.g. `"ss"`, which matches `"ß"`), neither
+    /// of which `GLOB` can express.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::CompiledMatcher;
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags(
+    ///     r"report\[final\]*.[^ch]",
+    ///     0,
+    /// )
+    /// .unwrap();
+    ///
+    /// let glob = matcher.to_sqlite_glob().unwrap();
+    ///
+    /// assert_eq!("report[[]final]*.[^ch]", glob);
+    /// ```
+    pub fn to_sqlite_glob(&self) -> Result<String> {
+        translate::to_sqlite_glob(&self.elements, self.flags)
+    }
 
-                                num_matchers += 1;
+    /// Indicates whether every string matched by the pattern is also
+    /// matched by that of `other`, such that, for example, a rule with the
+    /// pattern is made redundant by one with that of `other`. Each pattern
+    /// is evaluated according to its own flags.
+    ///
+    /// The decision is made by construction of the product of the
+    /// patterns' automata, rather than by sampling. Since the product may
+    /// have exponentially many states, e.g. for `*a????????`, its
+    /// construction is limited by `state_budget`.
+    ///
+    /// # Parameters:
+    /// - `other` - the matcher whose pattern is the possible superset;
+    /// - `state_budget` - the maximum number of product states;
+    ///
+    /// # Returns:
+    /// `Some(true)` or `Some(false)` as decided; or `None` if the budget is
+    /// insufficient; if either pattern is compiled with [`PURE_BYTES`] or
+    /// `GRAPHEME_CLUSTERS`, since the automata are of characters; or if
+    /// either pattern is compiled with [`IGNORE_CASE`] and has a literal
+    /// that may match a sequence of characters of a different length, e.g.
+    /// `"ß"`, which matches `"ss"`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::{
+    ///     CompiledMatcher,
+    ///     IGNORE_CASE,
+    /// };
+    ///
+    /// let all_logs = CompiledMatcher::from_pattern_and_flags("*.log", 0)
+    ///     .unwrap();
+    /// let app_logs = CompiledMatcher::from_pattern_and_flags(
+    ///     "app-*.log",
+    ///     0,
+    /// )
+    /// .unwrap();
+    /// let all_logs_ci = CompiledMatcher::from_pattern_and_flags(
+    ///     "*.LOG",
+    ///     IGNORE_CASE,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(Some(true), app_logs.is_subset_of(&all_logs, 1_000));
+    /// assert_eq!(Some(false), all_logs.is_subset_of(&app_logs, 1_000));
+    /// assert_eq!(Some(true), all_logs.is_subset_of(&all_logs_ci, 1_000));
+    /// assert_eq!(Some(false), all_logs_ci.is_subset_of(&all_logs, 1_000));
+    /// ```
+    pub fn is_subset_of(
+        &self,
+        other : &CompiledMatcher,
+        state_budget : usize,
+    ) -> Option<bool> {
+        language::find_shortest(
+            self.language_nfa_()?,
+            other.language_nfa_()?,
+            |a, b| a && !b,
+            state_budget,
+        )
+        .map(|counter_example| counter_example.is_none())
+    }
 
-                                return Ok((minimum_required, num_matchers));
-                            },
-                            _ => {
-                                s.push(c);
-                            },
-                        };
-                    },
-                    '*' => {
-                        match state {
-                            ParseState::None => {
-                                num_bytes += 1;
-                                match Self::parse_(matchers, &pattern[num_bytes..], flags, line, column) {
-                                    Ok((following_mr, following_nm)) => {
-                                        minimum_required = following_mr;
-                                        num_matchers += following_nm;
-                                    },
-                                    Err(e) => {
-                                        return Err(e);
-                                    },
-                                };
+    /// Indicates whether the pattern matches exactly the same strings as
+    /// that of `other`, as for [`is_subset_of()`](Self::is_subset_of) in
+    /// both directions.
+    ///
+    /// # Parameters:
+    /// - `other` - the matcher whose pattern is to be compared;
+    /// - `state_budget` - the maximum number of product states;
+    ///
+    /// # Returns:
+    /// `Some(true)` or `Some(false)` as decided; or `None` if it cannot be
+    /// decided, as for [`is_subset_of()`](Self::is_subset_of).
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::CompiledMatcher;
+    ///
+    /// let a = CompiledMatcher::from_pattern_and_flags("*?*[ab]", 0)
+    ///     .unwrap();
+    /// let b = CompiledMatcher::from_pattern_and_flags("?*[ba]", 0)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(Some(true), a.is_equivalent_to(&b, 1_000));
+    /// ```
+    pub fn is_equivalent_to(
+        &self,
+        other : &CompiledMatcher,
+        state_budget : usize,
+    ) -> Option<bool> {
+        language::find_shortest(
+            self.language_nfa_()?,
+            other.language_nfa_()?,
+            |a, b| a != b,
+            state_budget,
+        )
+        .map(|counter_example| counter_example.is_none())
+    }
 
-                                minimum_required = matchers.prepend_WildN(minimum_required);
+    /// Generates sample strings that are matched by the pattern, such as
+    /// for documentation or fuzzing, respecting ranges, not-ranges, and
+    /// [`IGNORE_CASE`] (by which case variants are generated).
+    ///
+    /// Each of `?`, `*`, and not-ranges generates ASCII letters and digits
+    /// wherever they are not excluded.
+    ///
+    /// # Parameters:
+    /// - `n` - the maximum number of strings;
+    /// - `rng` - the source of random numbers, such as a [`SeededRandom`],
+    ///   for reproducible generation;
+    ///
+    /// # Returns:
+    /// Up to `n` distinct strings, which is fewer where the pattern matches
+    /// fewer (or, in rare cases, more may not be found), and none where the
+    /// pattern matches nothing.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::{
+    ///     CompiledMatcher,
+    ///     SeededRandom,
+    /// };
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags(
+    ///     "IMG_*.[jp]?g",
+    ///     0,
+    /// )
+    /// .unwrap();
+    ///
+    /// let examples = matcher.examples(5, &mut SeededRandom::new(1));
+    ///
+    /// assert_eq!(5, examples.len());
+    /// assert!(examples.iter().all(|example| matcher.matches(example)));
+    /// ```
+    pub fn examples<R>(
+        &self,
+        n : usize,
+        rng : &mut R,
+    ) -> Vec<String>
+    where
+        R : RandomSource + ?Sized,
+    {
+        generate::examples(self, n, rng)
+    }
 
-                                num_matchers += 1;
+    /// Generates near-miss strings that are not matched by the pattern,
+    /// each of which is a single edit - deletion, insertion, or
+    /// substitution of a character - from one that is.
+    ///
+    /// # Parameters:
+    /// - `n` - the maximum number of strings;
+    /// - `rng` - the source of random numbers, such as a [`SeededRandom`],
+    ///   for reproducible generation;
+    ///
+    /// # Returns:
+    /// Up to `n` distinct strings, which is fewer (or none) where few (or
+    /// no) strings are not matched, as for `"*"`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::{
+    ///     CompiledMatcher,
+    ///     SeededRandom,
+    /// };
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags(
+    ///     "IMG_*.[jp]?g",
+    ///     0,
+    /// )
+    /// .unwrap();
+    ///
+    /// let mut rng = SeededRandom::new(1);
+    /// let counter_examples = matcher.counter_examples(5, &mut rng);
+    ///
+    /// assert_eq!(5, counter_examples.len());
+    /// assert!(counter_examples.iter().all(|s| !matcher.matches(s)));
+    /// ```
+    pub fn counter_examples<R>(
+        &self,
+        n : usize,
+        rng : &mut R,
+    ) -> Vec<String>
+    where
+        R : RandomSource + ?Sized,
+    {
+        generate::counter_examples(self, n, rng)
+    }
 
-                                return Ok((minimum_required, num_matchers));
-                            },
-                            ParseState::InLiteral => {
-                                debug_assert!(!s.is_empty(), "`s` expected to be not empty, but is found to be so");
+    /// Enumerates the strings matched by the pattern, where they are finite
+    /// in number, as when it comprises only literals and ranges, such that
+    /// expected files may be pre-created, or a small pattern converted into
+    /// a hash-set lookup.
+    ///
+    /// The strings are in order of the (sorted) options for each of their
+    /// successive parts (literal characters, with their case variants if
+    /// [`IGNORE_CASE`] is specified, and range characters), which, where
+    /// each option is a single character, is lexicographic order.
+    ///
+    /// # Parameters:
+    /// - `limit` - the maximum number of strings;
+    ///
+    /// # Returns:
+    /// An iterator over the strings, or `None` if they are infinite in
+    /// number (as where the pattern contains a `?`, `*`, or not-range) or
+    /// number more than `limit`, or if, with [`IGNORE_CASE`], a literal
+    /// may match a sequence of characters of a different length, e.g.
+    /// `"ß"`, which matches `"ss"`.
+    ///
+    /// # Examples:
+    ///
+    /// ```
+    /// use shwild::CompiledMatcher;
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags(
+    ///     "IMG_[12].[jp]eg",
+    ///     0,
+    /// )
+    /// .unwrap();
+    ///
+    /// let names : Vec<String> = matcher.enumerate(10).unwrap().collect();
+    ///
+    /// assert_eq!(
+    ///     vec!["IMG_1.jeg", "IMG_1.peg", "IMG_2.jeg", "IMG_2.peg"],
+    ///     names
+    /// );
+    /// assert!(matcher.enumerate(3).is_none());
+    ///
+    /// let matcher = CompiledMatcher::from_pattern_and_flags("IMG_*", 0)
+    ///     .unwrap();
+    ///
+    /// assert!(matcher.enumerate(usize::MAX).is_none());
+    /// ```
+    pub fn enumerate(
+        &self,
+        limit : usize,
+    ) -> Option<impl Iterator<Item = String> + '_> {
+        generate::enumerate(self, limit)
+    }
+}
 
-                                let literal = String::from_iter(s.iter());
 
-                                match Self::parse_(matchers, &pattern[num_bytes..], flags, line, column) {
-                                    Ok((following_mr, following_nm)) => {
-                                        minimum_required = following_mr;
-                                        num_matchers += following_nm;
-                                    },
-                                    Err(e) => {
-                                        return Err(e);
-                                    },
-                                };
+#[derive(Debug)]
+enum ParseState {
+    None,
+    InLiteral,
+    InNotRange,
+    InRange,
+}
 
-                                minimum_required = matchers.prepend_Literal(literal, flags, minimum_required);
+// Implementation
+impl CompiledMatcher {
+    /// Creates an instance from the given parsed `elements`.
+    pub(crate) fn from_elements_(
+        elements : Vec<PatternElement>,
+        flags : i64,
+    ) -> Self {
+        let matchers = Self::link_(&elements, flags);
+
+        Self {
+            matchers,
+            elements,
+            flags,
+            dfa : None,
+            nfa : std_sync::OnceLock::new(),
+            path_segments : std_sync::OnceLock::new(),
+        }
+    }
 
-                                num_matchers += 1;
+    /// The minimum and (if any) maximum lengths, in characters, of a
+    /// matching input, or `None` if no input matches.
+    fn len_bounds_(&self) -> Option<(usize, Option<usize>)> {
+        let has_clusters = self.has_clusters_();
+        let ignore_case = 0 != (self.flags & constants::IGNORE_CASE);
 
-                                return Ok((minimum_required, num_matchers));
-                            },
-                            _ => {
-                                s.push(c);
-                            },
-                        };
-                    },
-                    _ => {
-                        match state {
-                            ParseState::InNotRange | ParseState::InRange if !s.is_empty() => {
-                                match continuum_prior {
-                                    Some(prior_character) => {
-                                        match Self::push_continuum_(&mut s, prior_character, c, flags, line, column) {
-                                            Ok(_) => (),
-                                            Err(e) => {
-                                                return Err(e);
-                                            },
-                                        };
+        let mut min_len = 0;
+        let mut max_len = Some(0);
 
-                                        continuum_prior = None;
-                                    },
-                                    _ => {
-                                        s.push(c);
-                                    },
-                                };
-                            },
-                            ParseState::None => {
-                                s.push(c);
+        for element in &self.elements {
+            let (element_min, element_max) = match element {
+                PatternElement::Literal(literal) => {
+                    if ignore_case {
+                        let (min, max) = case::folded_len_bounds(literal);
 
-                                state = ParseState::InLiteral;
-                            },
-                            _ => {
-                                s.push(c);
-                            },
-                        };
-                    },
-                };
+                        (min, Some(max))
+                    } else {
+                        let len = literal.chars().count();
+
+                        (len, Some(len))
+                    }
+                },
+                PatternElement::Range {
+                    chars,
+                    negated: false,
+                } if chars.is_empty() => return None,
+                PatternElement::Wild1
+                | PatternElement::Range {
+                    ..
+                } => (1, if has_clusters { None } else { Some(1) }),
+                PatternElement::WildN => (0, None),
             };
 
-            if c == '\n' {
-                line += 1;
-                column = 0;
-            } else {
-                column += 1;
-            }
+            min_len += element_min;
+            max_len = max_len
+                .zip(element_max)
+                .map(|(max_len, element_max)| max_len + element_max);
+        }
+
+        Some((min_len, max_len))
+    }
 
-            num_bytes += c.len_utf8();
+    /// Indicates whether `?`, ranges, and not-ranges each match a grapheme
+    /// cluster, i.e. whether the flags include `GRAPHEME_CLUSTERS`.
+    fn has_clusters_(&self) -> bool {
+        #[cfg(feature = "grapheme-clusters")]
+        {
+            0 != (self.flags & constants::GRAPHEME_CLUSTERS)
+        }
+        #[cfg(not(feature = "grapheme-clusters"))]
+        {
+            false
         }
+    }
 
-        if escaped {
-            return Err(Error::ParseError {
-                line,
-                column,
-                message : "trailing slash".into(),
-            });
+    /// The number of wildcard elements, and hence of captures.
+    fn num_captures_(&self) -> usize {
+        self.elements
+            .iter()
+            .filter(|element| !std_matches!(element, PatternElement::Literal(_)))
+            .count()
+    }
+
+    /// The boundaries (of bytes, characters, or clusters, according to
+    /// the flags) between which occurrences in `input` are sought by the
+    /// interpreter, or `None` if they are sought by the position automaton,
+    /// as they are unless the flags include `PURE_BYTES` or
+    /// `GRAPHEME_CLUSTERS`, or the automaton is not exact.
+    pub(crate) fn find_boundaries_(
+        &self,
+        input : &str,
+    ) -> Option<Vec<usize>> {
+        if 0 != (self.flags & constants::PURE_BYTES) {
+            return Some((0..=input.len()).collect());
         }
 
-        match state {
-            ParseState::None => {},
-            ParseState::InLiteral => {
-                let literal = String::from_iter(s.iter());
+        #[cfg(feature = "grapheme-clusters")]
+        if 0 != (self.flags & constants::GRAPHEME_CLUSTERS) {
+            use unicode_segmentation::UnicodeSegmentation as _;
 
-                minimum_required = matchers.prepend_Literal(literal, flags, minimum_required);
-            },
-            ParseState::InNotRange | ParseState::InRange => {
-                return Err(Error::ParseError {
-                    line,
-                    column,
-                    message : "incomplete range".into(),
-                });
-            },
-        };
+            let boundaries = input
+                .grapheme_indices(true)
+                .map(|(index, _)| index)
+                .chain(std::iter::once(input.len()))
+                .collect();
 
-        Ok((minimum_required, num_matchers))
-    }
+            return Some(boundaries);
+        }
 
-    fn push_character_range_(
-        s : &mut Vec<char>,
-        c_from : char,
-        c_to : char,
-    ) {
-        if c_to < c_from {
-            Self::push_character_range_(s, c_to, c_from);
-        } else {
-            // Doing it long-hand as follows, as an observed faster
-            // alternative to
-            //
-            //   s.append((c_from..=c_to).into_iter().collect::<Vec<_>>().as_mut());
-            //
-            // although there doesn't seem to be much in it.
+        if self.nfa_().is_exact() {
+            return None;
+        }
 
-            let n = 1 + (c_to as usize - c_from as usize);
+        let boundaries = input
+            .char_indices()
+            .map(|(index, _)| index)
+            .chain(std::iter::once(input.len()))
+            .collect();
 
-            s.reserve(s.len() + n);
+        Some(boundaries)
+    }
 
-            for c in c_from..=c_to {
-                s.push(c);
-            }
+    /// Finds the occurrence selected by `mode` at or after the offset `at`,
+    /// where `boundaries` are as obtained from
+    /// [`find_boundaries_()`](Self::find_boundaries_) for `input`.
+    pub(crate) fn find_at_(
+        &self,
+        input : &str,
+        at : usize,
+        mode : FindMode,
+        boundaries : Option<&[usize]>,
+    ) -> Option<std_ops::Range<usize>> {
+        match boundaries {
+            Some(boundaries) => {
+                if 0 != (self.flags & constants::PURE_BYTES) {
+                    search::find_at_by_boundaries(boundaries, at, mode, |range| {
+                        self.matchers.matches_bytes(&input.as_bytes()[range])
+                    })
+                } else {
+                    search::find_at_by_boundaries(boundaries, at, mode, |range| self.matchers.matches(&input[range]))
+                }
+            },
+            None => search::find_at_by_nfa(self.nfa_(), input, at, mode),
         }
     }
 
-    fn push_continuum_(
-        s : &mut Vec<char>,
-        prior_character : char,
-        posterior_character : char,
+    /// The position automaton, which is created on first use.
+    pub(crate) fn nfa_(&self) -> &automaton::Nfa {
+        self.nfa
+            .get_or_init(|| automaton::Nfa::from_elements(&self.elements, self.flags))
+    }
+
+    /// The position automaton, for the operations on the language of the
+    /// pattern, or `None` if it is of a language of bytes or of clusters,
+    /// to which the automaton, of characters, does not apply.
+    pub(crate) fn language_nfa_(&self) -> Option<&automaton::Nfa> {
+        if 0 != (self.flags & constants::PURE_BYTES) {
+            return None;
+        }
+        #[cfg(feature = "grapheme-clusters")]
+        if 0 != (self.flags & constants::GRAPHEME_CLUSTERS) {
+            return None;
+        }
+
+        Some(self.nfa_())
+    }
+
+    /// The boundary (of a byte, character, or cluster, according to the
+    /// flags) that follows the boundary `at`, or `None` at the end.
+    pub(crate) fn next_boundary_(
+        &self,
+        input : &str,
+        at : usize,
+    ) -> Option<usize> {
+        if at >= input.len() {
+            return None;
+        }
+
+        if 0 != (self.flags & constants::PURE_BYTES) {
+            return Some(at + 1);
+        }
+
+        #[cfg(feature = "grapheme-clusters")]
+        if 0 != (self.flags & constants::GRAPHEME_CLUSTERS) {
+            use unicode_segmentation::UnicodeSegmentation as _;
+
+            return input[at..].graphemes(true).next().map(|cluster| at + cluster.len());
+        }
+
+        input[at..].chars().next().map(|c| at + c.len_utf8())
+    }
+
+    /// Parses `pattern` in a single forward pass into a list of (unlinked)
+    /// pattern elements, in pattern order.
+    fn parse_(
+        pattern : &str,
+        flags : i64,
+    ) -> Result<Vec<PatternElement>> {
+        let mut line = 0;
+        let mut column = 0;
+
+        let mut elements = vec![];
+        let mut state = ParseState::None;
+        let mut s = vec![];
+        let mut escaped = false;
+        let mut continuum_prior = None;
+
+        for c in pattern.chars() {
+            debug_assert!(
+                continuum_prior.is_none() || std_matches!(state, ParseState::InNotRange | ParseState::InRange)
+            );
+
+            if escaped {
+                match c {
+                    // TODO: do a lookup table
+                    'n' => {
+                        s.push('\n');
+                    },
+                    'r' => {
+                        s.push('\r');
+                    },
+                    't' => {
+                        s.push('\t');
+                    },
+                    _ => {
+                        s.push(c);
+                    },
+                };
+
+                escaped = false;
+
+                if std_matches!(state, ParseState::None) {
+                    state = ParseState::InLiteral;
+                }
+            } else {
+                match c {
+                    '[' => {
+                        match state {
+                            ParseState::None => {
+                                state = ParseState::InRange;
+                            },
+                            ParseState::InLiteral => {
+                                debug_assert!(!s.is_empty(), "`s` expected to be not empty, but is found to be so");
+
+                                elements.push(PatternElement::Literal(String::from_iter(s.drain(..))));
+
+                                state = ParseState::InRange;
+                            },
+                            _ => {
+                                s.push(c);
+                            },
+                        };
+                    },
+                    '^' => {
+                        match state {
+                            ParseState::InRange if s.is_empty() => {
+                                state = ParseState::InNotRange;
+                            },
+                            ParseState::None => {
+                                s.push(c);
+
+                                state = ParseState::InLiteral;
+                            },
+                            _ => {
+                                s.push(c);
+                            },
+                        };
+                    },
+                    ']' => {
+                        match state {
+                            ParseState::InNotRange | ParseState::InRange => {
+                                if continuum_prior.take().is_some() {
+                                    // don't care about the prior character because that will already be pushed
+                                    // into `s`
+
+                                    s.push('-');
+                                }
+
+                                let range_chars = std_mem::take(&mut s);
+
+                                elements.push(PatternElement::Range {
+                                    chars :   range_chars,
+                                    negated : std_matches!(state, ParseState::InNotRange),
+                                });
+
+                                state = ParseState::None;
+                            },
+                            ParseState::None => {
+                                s.push(c);
+
+                                state = ParseState::InLiteral;
+                            },
+                            _ => {
+                                s.push(c);
+                            },
+                        };
+                    },
+                    '\\' => {
+                        escaped = true;
+                    },
+                    '-' => {
+                        match state {
+                            ParseState::InNotRange | ParseState::InRange if !s.is_empty() => {
+                                continuum_prior = Some(*s.last().unwrap());
+                            },
+                            ParseState::None => {
+                                s.push(c);
+
+                                state = ParseState::InLiteral;
+                            },
+                            _ => {
+                                s.push(c);
+                            },
+                        };
+                    },
+                    '?' | '*' => {
+                        let element = if '?' == c {
+                            PatternElement::Wild1
+                        } else {
+                            PatternElement::WildN
+                        };
+
+                        match state {
+                            ParseState::None => {
+                                elements.push(element);
+                            },
+                            ParseState::InLiteral => {
+                                debug_assert!(!s.is_empty(), "`s` expected to be not empty, but is found to be so");
+
+                                elements.push(PatternElement::Literal(String::from_iter(s.drain(..))));
+                                elements.push(element);
+
+                                state = ParseState::None;
+                            },
+                            _ => {
+                                s.push(c);
+                            },
+                        };
+                    },
+                    _ => {
+                        match state {
+                            ParseState::InNotRange | ParseState::InRange if !s.is_empty() => {
+                                match continuum_prior {
+                                    Some(prior_character) => {
+                                        // the prior character is pushed again as part of the continuum,
+                                        // unless an escaped character has been pushed after it
+                                        if Some(&prior_character) == s.last() {
+                                            s.pop();
+                                        }
+
+                                        Self::push_continuum_(&mut s, prior_character, c, flags, line, column)?;
+
+                                        continuum_prior = None;
+                                    },
+                                    _ => {
+                                        s.push(c);
+                                    },
+                                };
+                            },
+                            ParseState::None => {
+                                s.push(c);
+
+                                state = ParseState::InLiteral;
+                            },
+                            _ => {
+                                s.push(c);
+                            },
+                        };
+                    },
+                };
+            };
+
+            if c == '\n' {
+                line += 1;
+                column = 0;
+            } else {
+                column += 1;
+            }
+        }
+
+        if escaped {
+            return Err(Error::ParseError {
+                line,
+                column,
+                message : "trailing slash".into(),
+            });
+        }
+
+        match state {
+            ParseState::None => {},
+            ParseState::InLiteral => {
+                elements.push(PatternElement::Literal(String::from_iter(s)));
+            },
+            ParseState::InNotRange | ParseState::InRange => {
+                return Err(Error::ParseError {
+                    line,
+                    column,
+                    message : "incomplete range".into(),
+                });
+            },
+        };
+
+        Ok(elements)
+    }
+
+    /// Links the given `elements` into a `MatcherSequence`, prepending from
+    /// the last element to the first.
+    fn link_(
+        elements : &[PatternElement],
+        flags : i64,
+    ) -> utils::MatcherSequence {
+        let mut matchers = utils::MatcherSequence::new();
+        let mut minimum_required = 0;
+
+        for element in elements.iter().rev() {
+            minimum_required = match element {
+                PatternElement::Literal(literal) => matchers.prepend_Literal(literal.clone(), flags, minimum_required),
+                #[cfg(feature = "grapheme-clusters")]
+                PatternElement::Range {
+                    chars: range_chars,
+                    negated,
+                } if 0 != (flags & constants::GRAPHEME_CLUSTERS) => {
+                    let (character_range, clusters) = utils::prepare_cluster_range(range_chars, flags);
+
+                    if *negated {
+                        matchers.prepend_ClusterNotRange(character_range, clusters, flags, minimum_required)
+                    } else {
+                        matchers.prepend_ClusterRange(character_range, clusters, flags, minimum_required)
+                    }
+                },
+                PatternElement::Range {
+                    chars: range_chars,
+                    negated,
+                } => {
+                    #[cfg(feature = "lookup-ranges")]
+                    let character_range = utils::prepare_range_upm_from_slice(range_chars.as_slice(), flags);
+                    #[cfg(not(feature = "lookup-ranges"))]
+                    let character_range = utils::prepare_range_string_from_slice(range_chars.as_slice(), flags);
+
+                    if *negated {
+                        matchers.prepend_NotRange(character_range, flags, minimum_required)
+                    } else {
+                        matchers.prepend_Range(character_range, flags, minimum_required)
+                    }
+                },
+                PatternElement::Wild1 => matchers.prepend_Wild1(flags, minimum_required),
+                PatternElement::WildN => matchers.prepend_WildN(flags, minimum_required),
+            };
+        }
+
+        matchers
+    }
+
+    fn push_character_range_(
+        s : &mut Vec<char>,
+        c_from : char,
+        c_to : char,
+    ) {
+        if c_to < c_from {
+            Self::push_character_range_(s, c_to, c_from);
+        } else {
+            // Doing it long-hand as follows, as an observed faster
+            // alternative to
+            //
+            //   s.append((c_from..=c_to).into_iter().collect::<Vec<_>>().as_mut());
+            //
+            // although there doesn't seem to be much in it.
+
+            let n = 1 + (c_to as usize - c_from as usize);
+
+            s.reserve(s.len() + n);
+
+            for c in c_from..=c_to {
+                s.push(c);
+            }
+        }
+    }
+
+    fn push_continuum_(
+        s : &mut Vec<char>,
+        prior_character : char,
+        posterior_character : char,
         flags : i64,
         line : usize,
         column : usize,
@@ -1900,13 +4050,296 @@ impl CompiledMatcher {
 
 // Trait implementations
 
-// NONE DEFINED AT THIS TIME
+/// Writes the normalised form of the pattern, from which a matcher of
+/// identical behaviour may be compiled (with the same flags, which are
+/// not written), such that equivalent patterns may be de-duplicated:
+///
+/// - adjacent literals are merged;
+/// - each run of `?`s and `*`s is written as its `?`s followed by a
+///   single `*`, if it has any, e.g. `"*?*"` as `"?*"`;
+/// - the characters of each range and not-range are sorted and
+///   de-duplicated (except with `GRAPHEME_CLUSTERS`, in which their order
+///   determines the clusters that they form), and each run of three or
+///   more consecutive same-case ASCII letters is written as a continuum,
+///   e.g. `"[cbaZa]"` as `"[Za-c]"`;
+/// - each special character is escaped;
+///
+/// # Examples:
+///
+/// ```
+/// use shwild::CompiledMatcher;
+///
+/// let matcher = CompiledMatcher::from_pattern_and_flags(
+///     r"*\*?*[dcba]",
+///     0,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(r"*\*?*[a-d]", matcher.to_string());
+/// ```
+impl std_fmt::Display for CompiledMatcher {
+    fn fmt(
+        &self,
+        f : &mut std_fmt::Formatter<'_>,
+    ) -> std_fmt::Result {
+        // with `GRAPHEME_CLUSTERS`, the characters of a range form clusters
+        // according to their order, which must therefore be preserved
+        pattern::write_normalised_elements(f, &self.elements, !self.has_clusters_())
+    }
+}
 
 
 // /////////////////////////////////////////////////////////
 // API functions
 
-/// Determines whether the given `input` matches the given `pattern`,
+/// Parses the given `pattern`, according to the given `flags`, into its
+/// elements, using the same rules as are used by
+/// [`CompiledMatcher::from_pattern_and_flags()`].
+///
+/// # Parameters:
+/// - `pattern` - the pattern to be parsed;
+/// - `flags` - flags that moderate the parsing;
+///
+/// # Returns:
+/// - `Ok(Pattern)` - `pattern` represents a valid wildcard specification;
+/// - `Err(Error)` - `pattern` does not represent a valid wildcard
+///   specification;
+///
+/// # Examples:
+///
+/// ```
+/// use shwild::PatternElement;
+///
+/// let pattern = shwild::parse("*.[ch]", 0).unwrap();
+///
+/// assert_eq!(3, pattern.elements().len());
+/// assert_eq!(PatternElement::WildN, pattern.elements()[0]);
+/// ```
+pub fn parse(
+    pattern : &str,
+    flags : i64,
+) -> Result<Pattern> {
+    CompiledMatcher::parse_(pattern, flags).map(|elements| Pattern::new(elements, flags))
+}
+
+/// Escapes the given `text` such that it forms a pattern that matches
+/// only `text` itself, under any flags.
+///
+/// Each `*`, `?`, `[`, `]`, and `\` is escaped; no other character
+/// (including `^` and `-`, which are special only within a range)
+/// requires escaping, and no flag affects the escaping.
+///
+/// # Parameters:
+/// - `text` - the text to be escaped;
+///
+/// # Returns:
+/// `text` itself, borrowed, if it contains no special characters;
+/// otherwise, an escaped copy.
+///
+/// # Examples:
+///
+/// ```
+/// let pattern = shwild::escape("report[final]*.txt");
+///
+/// assert_eq!(r"report\[final\]\*.txt", pattern);
+///
+/// let matcher = shwild::CompiledMatcher::from_pattern_and_flags(
+///     &pattern,
+///     0,
+/// )
+/// .unwrap();
+///
+/// assert!(matcher.matches("report[final]*.txt"));
+/// assert!(!matcher.matches("report-v2.txt"));
+/// ```
+pub fn escape(text : &str) -> Cow<'_, str> {
+    pattern::escape_literal(text)
+}
+
+/// Obtains the text matched by the given entirely-literal `pattern`,
+/// resolving escapes exactly as does [`parse()`]; the counterpart of
+/// [`escape()`].
+///
+/// # Parameters:
+/// - `pattern` - the pattern to be unescaped;
+///
+/// # Returns:
+/// - `Some(text)` - `pattern` is a valid pattern consisting only of
+///   literal text, which is borrowed if `pattern` contains no escapes;
+/// - `None` - `pattern` contains an (unescaped) `?`, `*`, or range, or
+///   has a trailing slash;
+///
+/// # Examples:
+///
+/// ```
+/// assert_eq!(
+///     Some("report[final]*.txt".into()),
+///     shwild::unescape(r"report\[final\]\*.txt")
+/// );
+/// assert_eq!(None, shwild::unescape("report*.txt"));
+/// ```
+pub fn unescape(pattern : &str) -> Option<Cow<'_, str>> {
+    pattern::unescape_literal(pattern)
+}
+
+/// Converts the given regular expression, which must be within the subset
+/// that is expressible as a pattern, into an equivalent (normalised)
+/// pattern, such as when migrating legacy configuration.
+///
+/// The subset comprises:
+/// - literal characters, and escapes of punctuation characters, of
+///   `\n`, `\r`, and `\t`, and of hexadecimal (`\xHH` and `\x{H...}`)
+///   characters;
+/// - `.` (as `?`), `.*` (as `*`), and `.+` (as `?*`), each of the latter
+///   two of which may be lazy (as `.*?` and `.+?`), provided that the
+///   expression begins with `(?s)`, since otherwise `.` does not match a
+///   newline, whereas `?` and `*` do;
+/// - classes, e.g. `[abc]`, `[^a-z]`;
+/// - the anchors `^` (or `\A`) at the start and `$` (or `\z`) at the end,
+///   the absence of either of which is expressed by a leading or trailing
+///   `*`;
+/// - a leading `(?s)`;
+///
+/// # Parameters:
+/// - `regex` - the regular expression to be converted;
+///
+/// # Returns:
+/// The pattern, or [`Error::TranslationError`], identifying the column of
+/// the construct, if `regex` is outside the subset, such as by
+/// alternation, groups, quantifiers (other than as above), lookarounds,
+/// backreferences, Perl classes (e.g. `\d`), flags (other than a leading
+/// `(?s)`), or `.` without a leading `(?s)`.
+///
+/// # Examples:
+///
+/// ```
+/// assert_eq!(
+///     Ok(r"foo*.log".into()),
+///     shwild::from_regex_subset(r"(?s)^foo.*\.log$")
+/// );
+/// assert_eq!(Ok("a?b".into()), shwild::from_regex_subset("(?s)^a.b$"));
+/// assert_eq!(Ok("*[abx]*".into()), shwild::from_regex_subset("[xab]"));
+///
+/// assert!(shwild::from_regex_subset("^(foo|bar)$").is_err());
+/// // without `(?s)`, `.` does not match a newline, as `?` does
+/// assert!(shwild::from_regex_subset("^a.b$").is_err());
+/// ```
+pub fn from_regex_subset(regex : &str) -> Result<String> {
+    translate::from_regex_subset(regex)
+}
+
+/// Determines whether the patterns of the given matchers overlap, i.e.
+/// whether there is any string that is matched by both, each according to
+/// its own flags, and, if so, obtains a shortest such string.
+///
+/// The decision is made by construction of the product of the patterns'
+/// automata, limited by `state_budget`, as for
+/// [`CompiledMatcher::is_subset_of()`]. The result is therefore nested,
+/// rather than a plain `Option<String>`, so that a pair of patterns
+/// whose overlap cannot be decided is not mistaken for a pair that do not
+/// overlap.
+///
+/// # Parameters:
+/// - `a` - the first matcher;
+/// - `b` - the second matcher;
+/// - `state_budget` - the maximum number of product states;
+///
+/// # Returns:
+/// `Some(Some(witness))` for a shortest string that is matched by both;
+/// `Some(None)` if there is none; or `None` if this cannot be decided, as
+/// for [`CompiledMatcher::is_subset_of()`].
+///
+/// # Examples:
+///
+/// ```
+/// use shwild::CompiledMatcher;
+///
+/// let a = CompiledMatcher::from_pattern_and_flags("a*", 0).unwrap();
+/// let b = CompiledMatcher::from_pattern_and_flags("*z", 0).unwrap();
+/// let c = CompiledMatcher::from_pattern_and_flags("b*", 0).unwrap();
+///
+/// assert_eq!(Some(Some("az".into())), shwild::intersects(&a, &b, 1_000));
+/// assert_eq!(Some(None), shwild::intersects(&a, &c, 1_000));
+/// ```
+pub fn intersects(
+    a : &CompiledMatcher,
+    b : &CompiledMatcher,
+    state_budget : usize,
+) -> Option<Option<String>> {
+    language::find_shortest(a.language_nfa_()?, b.language_nfa_()?, |a, b| a && b, state_budget)
+}
+
+/// Walks the filesystem for the paths that match the given `pattern`, as
+/// by [`glob_with()`] with the default options (i.e. case-sensitive, not
+/// following symbolic links, excluding hidden names, and unsorted).
+///
+/// # Parameters:
+/// - `pattern` - the pattern, whose segments are matched against path
+///   components as by [`CompiledMatcher::matches_path_components()`];
+///
+/// # Returns:
+/// An iterator over the matching paths, or an error if `pattern` is not a
+/// valid wildcard specification.
+///
+/// # Examples:
+///
+/// ```
+/// for path in shwild::glob("src/**/*.rs").unwrap() {
+///     let path = path.unwrap();
+///
+///     assert_eq!(Some("rs".as_ref()), path.extension());
+/// }
+/// ```
+#[inline]
+pub fn glob(pattern : &str) -> Result<Glob> {
+    glob_with(pattern, &GlobOptions::default())
+}
+
+/// Walks the filesystem for the paths that match the given `pattern`,
+/// according to the given `options`.
+///
+/// The pattern is split into its literal directory prefix - e.g. `src`
+/// for `"src/**/*.rs"` - from which the walk begins, and the segments
+/// that are matched against the components below it, such that only the
+/// directories that may contain matches are descended into. The paths
+/// are obtained lazily, each directory being read only when it is
+/// reached, and each only once, however many ways it may match.
+///
+/// # Parameters:
+/// - `pattern` - the pattern, whose segments are matched against path
+///   components as by [`CompiledMatcher::matches_path_components()`];
+/// - `options` - options that moderate the walk;
+///
+/// # Returns:
+/// An iterator over the matching paths, or an error if `pattern` is not a
+/// valid wildcard specification.
+///
+/// # Examples:
+///
+/// ```
+/// use shwild::GlobOptions;
+///
+/// let options = GlobOptions {
+///     sort : true,
+///     ..Default::default()
+/// };
+///
+/// let paths : Vec<_> = shwild::glob_with("src/*.rs", &options)
+///     .unwrap()
+///     .collect::<std::io::Result<_>>()
+///     .unwrap();
+///
+/// assert!(paths.windows(2).all(|w| w[0] < w[1]));
+/// assert!(paths.contains(&"src/lib.rs".into()));
+/// ```
+pub fn glob_with(
+    pattern : &str,
+    options : &GlobOptions,
+) -> Result<Glob> {
+    Glob::new_(pattern, options)
+}
+
+/// Determines whether the given `input` matches the given `pattern`,
 /// according to the given `flags`.
 ///
 /// # Parameters:
@@ -2032,206 +4465,785 @@ macro_rules! assert_shwild_not_matches {
         let flags = {
             let flags : &dyn base_traits::AsI64 = &$flags;
 
-            flags.as_i64()
-        };
-        #[cfg(not(feature = "flexible-flags-type"))]
-        let flags : i64 = $flags;
+            flags.as_i64()
+        };
+        #[cfg(not(feature = "flexible-flags-type"))]
+        let flags : i64 = $flags;
+
+        match $crate::matches(expected_pattern, actual, flags) {
+            Err(e) => {
+                panic!(
+                    "could not evaluate actual value due to a failure in the parsing of expected pattern '{}': {}",
+                    expected_pattern, e
+                );
+            },
+            Ok(b) => {
+                assert!(
+                    !b,
+                    "assertion failed: actual value '{}' matches unexpectedly with the pattern '{}'",
+                    actual, expected_pattern
+                );
+            },
+        }
+    };
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use crate::{
+        self as shwild,
+        constants::*,
+    };
+
+
+    mod TEST_CompiledMatcher_PARSING {
+        #![allow(non_snake_case)]
+
+        use super::*;
+
+
+        #[test]
+        fn TEST_CompiledMatcher_parse_EMPTY() {
+            let pattern = "";
+
+            {
+                let flags = 0;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                assert_eq!(0, matcher.len());
+
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(!matcher.matches("abcd"));
+                assert!(!matcher.matches("ABCD"));
+                assert!(!matcher.matches("abcde"));
+            }
+
+            {
+                let flags = IGNORE_CASE;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                assert_eq!(0, matcher.len());
+
+                assert!(matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(!matcher.matches("abcd"));
+                assert!(!matcher.matches("ABCD"));
+                assert!(!matcher.matches("abcde"));
+            }
+        }
+
+        #[test]
+        fn TEST_CompiledMatcher_parse_LITERAL_1() {
+            let pattern = "abcd";
+
+            {
+                let flags = 0;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+
+                assert_eq!(1, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(matcher.matches("abcd"));
+                assert!(!matcher.matches("ABCD"));
+                assert!(!matcher.matches("abcde"));
+            }
+
+            {
+                let flags = IGNORE_CASE;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                assert_eq!(1, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(matcher.matches("abcd"));
+                assert!(matcher.matches("ABCD"));
+                assert!(!matcher.matches("abcde"));
+            }
+        }
+
+        #[test]
+        fn TEST_CompiledMatcher_parse_LITERAL_2() {
+            let pattern = r"ab-d";
+
+            {
+                let flags = 0;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+
+                assert_eq!(1, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(matcher.matches("ab-d"));
+                assert!(!matcher.matches("AB-D"));
+                assert!(!matcher.matches("ab-de"));
+            }
+
+            {
+                let flags = IGNORE_CASE;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                assert_eq!(1, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(matcher.matches("ab-d"));
+                assert!(matcher.matches("AB-D"));
+                assert!(!matcher.matches("ab-de"));
+            }
+        }
+
+        #[test]
+        fn TEST_CompiledMatcher_parse_LITERAL_3() {
+            let pattern = r"ab\-d";
+
+            {
+                let flags = 0;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+
+                assert_eq!(1, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(matcher.matches("ab-d"));
+                assert!(!matcher.matches("AB-D"));
+                assert!(!matcher.matches("ab-de"));
+            }
+
+            {
+                let flags = IGNORE_CASE;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                assert_eq!(1, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(matcher.matches("ab-d"));
+                assert!(matcher.matches("AB-D"));
+                assert!(!matcher.matches("ab-de"));
+            }
+        }
+
+        #[test]
+        fn TEST_CompiledMatcher_parse_LITERAL_4() {
+            let pattern = r"ab\\d";
+
+            {
+                let flags = 0;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+
+                assert_eq!(1, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(matcher.matches(r"ab\d"));
+                assert!(!matcher.matches(r"a🐻\d"));
+                assert!(!matcher.matches("AB-D"));
+                assert!(!matcher.matches("ab-de"));
+            }
+
+            {
+                let flags = IGNORE_CASE;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                assert_eq!(1, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(matcher.matches(r"ab\d"));
+                assert!(!matcher.matches(r"a🐻\d"));
+                assert!(matcher.matches(r"AB\D"));
+                assert!(!matcher.matches(r"A🐻\D"));
+                assert!(!matcher.matches(r"ab\de"));
+                assert!(!matcher.matches(r"a🐻\de"));
+            }
+        }
+
+        #[test]
+        fn TEST_CompiledMatcher_parse_LITERAL_5() {
+            let pattern = r"a🐻\\d";
+
+            {
+                let flags = 0;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+
+                assert_eq!(1, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(!matcher.matches(r"ab\d"));
+                assert!(matcher.matches(r"a🐻\d"));
+                assert!(!matcher.matches("AB-D"));
+                assert!(!matcher.matches("ab-de"));
+            }
+
+            {
+                let flags = IGNORE_CASE;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                assert_eq!(1, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(!matcher.matches(r"ab\d"));
+                assert!(matcher.matches(r"a🐻\d"));
+                assert!(!matcher.matches(r"AB\D"));
+                assert!(matcher.matches(r"A🐻\D"));
+                assert!(!matcher.matches(r"ab\de"));
+                assert!(!matcher.matches(r"a🐻\de"));
+            }
+        }
+
+        #[test]
+        fn TEST_CompiledMatcher_parse_RANGE_1() {
+            let pattern = "[abcd]";
+
+            {
+                let flags = 0;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+
+                assert_eq!(1, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("["));
+                assert!(!matcher.matches("]"));
+                assert!(!matcher.matches("^"));
+                assert!(matcher.matches("a"));
+                assert!(matcher.matches("b"));
+                assert!(matcher.matches("c"));
+                assert!(matcher.matches("d"));
+                assert!(!matcher.matches("e"));
+                assert!(!matcher.matches("A"));
+                assert!(!matcher.matches("B"));
+                assert!(!matcher.matches("C"));
+                assert!(!matcher.matches("D"));
+                assert!(!matcher.matches("E"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(!matcher.matches("abcd"));
+                assert!(!matcher.matches("ABCD"));
+                assert!(!matcher.matches("abcde"));
+            }
+
+            {
+                let flags = IGNORE_CASE;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                assert_eq!(1, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("["));
+                assert!(!matcher.matches("]"));
+                assert!(!matcher.matches("^"));
+                assert!(matcher.matches("a"));
+                assert!(matcher.matches("b"));
+                assert!(matcher.matches("c"));
+                assert!(matcher.matches("d"));
+                assert!(!matcher.matches("e"));
+                assert!(matcher.matches("A"));
+                assert!(matcher.matches("B"));
+                assert!(matcher.matches("C"));
+                assert!(matcher.matches("D"));
+                assert!(!matcher.matches("E"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(!matcher.matches("abcd"));
+                assert!(!matcher.matches("ABCD"));
+                assert!(!matcher.matches("abcde"));
+            }
+        }
+
+        #[test]
+        fn TEST_CompiledMatcher_parse_NOTRANGE_1() {
+            let pattern = "[^abcd]";
 
-        match $crate::matches(expected_pattern, actual, flags) {
-            Err(e) => {
-                panic!(
-                    "could not evaluate actual value due to a failure in the parsing of expected pattern '{}': {}",
-                    expected_pattern, e
-                );
-            },
-            Ok(b) => {
-                assert!(
-                    !b,
-                    "assertion failed: actual value '{}' matches unexpectedly with the pattern '{}'",
-                    actual, expected_pattern
-                );
-            },
-        }
-    };
-}
+            {
+                let flags = 0;
 
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-#[cfg(test)]
-mod tests {
-    #![allow(non_snake_case)]
+                assert_eq!(1, matcher.len());
 
-    use crate::{
-        self as shwild,
-        constants::*,
-    };
+                assert!(!matcher.matches(""));
+                assert!(matcher.matches(" "));
+                assert!(matcher.matches("["));
+                assert!(matcher.matches("]"));
+                assert!(matcher.matches("^"));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("b"));
+                assert!(!matcher.matches("c"));
+                assert!(!matcher.matches("d"));
+                assert!(matcher.matches("e"));
+                assert!(matcher.matches("A"));
+                assert!(matcher.matches("B"));
+                assert!(matcher.matches("C"));
+                assert!(matcher.matches("D"));
+                assert!(matcher.matches("E"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(!matcher.matches("abcd"));
+                assert!(!matcher.matches("ABCD"));
+                assert!(!matcher.matches("abcde"));
+            }
 
+            {
+                let flags = IGNORE_CASE;
 
-    mod TEST_CompiledMatcher_PARSING {
-        #![allow(non_snake_case)]
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-        use super::*;
+                assert_eq!(1, matcher.len());
 
+                assert!(!matcher.matches(""));
+                assert!(matcher.matches(" "));
+                assert!(matcher.matches("["));
+                assert!(matcher.matches("]"));
+                assert!(matcher.matches("^"));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("b"));
+                assert!(!matcher.matches("c"));
+                assert!(!matcher.matches("d"));
+                assert!(matcher.matches("e"));
+                assert!(!matcher.matches("A"));
+                assert!(!matcher.matches("B"));
+                assert!(!matcher.matches("C"));
+                assert!(!matcher.matches("D"));
+                assert!(matcher.matches("E"));
+                assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("abc"));
+                assert!(!matcher.matches("abcd"));
+                assert!(!matcher.matches("ABCD"));
+                assert!(!matcher.matches("abcde"));
+            }
+        }
 
         #[test]
-        fn TEST_CompiledMatcher_parse_EMPTY() {
-            let pattern = "";
+        fn TEST_CompiledMatcher_parse_WILD1_1() {
+            let pattern = "?";
 
             {
                 let flags = 0;
 
                 let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert_eq!(0, matcher.len());
+                assert_eq!(1, matcher.len());
 
-                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches(""));
+                assert!(matcher.matches(" "));
+                assert!(matcher.matches("["));
+                assert!(matcher.matches("]"));
+                assert!(matcher.matches("^"));
+                assert!(matcher.matches("a"));
+                assert!(matcher.matches("b"));
+                assert!(matcher.matches("c"));
+                assert!(matcher.matches("d"));
+                assert!(matcher.matches("e"));
+                assert!(matcher.matches("A"));
+                assert!(matcher.matches("B"));
+                assert!(matcher.matches("C"));
+                assert!(matcher.matches("D"));
+                assert!(matcher.matches("E"));
                 assert!(!matcher.matches("ab"));
                 assert!(!matcher.matches("abc"));
                 assert!(!matcher.matches("abcd"));
                 assert!(!matcher.matches("ABCD"));
                 assert!(!matcher.matches("abcde"));
             }
+        }
+
+        #[test]
+        fn TEST_CompiledMatcher_parse_WILDN_1() {
+            let pattern = "*";
 
             {
-                let flags = IGNORE_CASE;
+                let flags = 0;
 
                 let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert_eq!(0, matcher.len());
+                assert_eq!(1, matcher.len());
 
                 assert!(matcher.matches(""));
+                assert!(matcher.matches(" "));
+                assert!(matcher.matches("["));
+                assert!(matcher.matches("]"));
+                assert!(matcher.matches("^"));
+                assert!(matcher.matches("a"));
+                assert!(matcher.matches("b"));
+                assert!(matcher.matches("c"));
+                assert!(matcher.matches("d"));
+                assert!(matcher.matches("e"));
+                assert!(matcher.matches("A"));
+                assert!(matcher.matches("B"));
+                assert!(matcher.matches("C"));
+                assert!(matcher.matches("D"));
+                assert!(matcher.matches("E"));
+                assert!(matcher.matches("ab"));
+                assert!(matcher.matches("abc"));
+                assert!(matcher.matches("abcd"));
+                assert!(matcher.matches("ABCD"));
+                assert!(matcher.matches("abcde"));
+            }
+        }
+
+        #[test]
+        fn TEST_CompiledMatcher_parse_LITERAL_THEN_WILDN_THEN_RANGE_1() {
+            let pattern = "ma*[der]";
+
+            {
+                let flags = 0;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                assert_eq!(3, matcher.len());
+
+                assert!(!matcher.matches(""));
                 assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("["));
+                assert!(!matcher.matches("]"));
+                assert!(!matcher.matches("^"));
                 assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("b"));
+                assert!(!matcher.matches("c"));
+                assert!(!matcher.matches("d"));
+                assert!(!matcher.matches("e"));
+                assert!(!matcher.matches("A"));
+                assert!(!matcher.matches("B"));
+                assert!(!matcher.matches("C"));
+                assert!(!matcher.matches("D"));
+                assert!(!matcher.matches("E"));
                 assert!(!matcher.matches("ab"));
                 assert!(!matcher.matches("abc"));
                 assert!(!matcher.matches("abcd"));
                 assert!(!matcher.matches("ABCD"));
                 assert!(!matcher.matches("abcde"));
+
+
+                assert!(!matcher.matches("ma"));
+                assert!(matcher.matches("mad"));
+                assert!(matcher.matches("made"));
+                assert!(matcher.matches("madder"));
+            }
+        }
+
+        #[test]
+        fn TEST_CompiledMatcher_parse_RANGE_THEN_WILD1_1() {
+            let pattern = "[abc]?";
+
+            {
+                let flags = 0;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                assert_eq!(2, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("["));
+                assert!(!matcher.matches("]"));
+                assert!(!matcher.matches("^"));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("b"));
+                assert!(!matcher.matches("c"));
+                assert!(!matcher.matches("d"));
+                assert!(!matcher.matches("e"));
+
+                assert!(matcher.matches("aa"));
+                assert!(matcher.matches("ax"));
+                assert!(matcher.matches("bb"));
+                assert!(matcher.matches("by"));
+                assert!(matcher.matches("cc"));
+                assert!(matcher.matches("cz"));
+                assert!(!matcher.matches("da"));
+                assert!(!matcher.matches("ee"));
+
+                assert!(!matcher.matches("aa "));
+                assert!(!matcher.matches("ax "));
+                assert!(!matcher.matches("bb "));
+                assert!(!matcher.matches("by "));
+                assert!(!matcher.matches("cc "));
+                assert!(!matcher.matches("cz "));
+                assert!(!matcher.matches("da "));
+                assert!(!matcher.matches("ee "));
+            }
+        }
+
+        #[test]
+        fn TEST_CompiledMatcher_parse_RANGE_THEN_WILD1_2() {
+            let pattern = "[a🐻c]?";
+
+            {
+                let flags = 0;
+
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                assert_eq!(2, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("["));
+                assert!(!matcher.matches("]"));
+                assert!(!matcher.matches("^"));
+                assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("🐻"));
+                assert!(!matcher.matches("c"));
+                assert!(!matcher.matches("d"));
+                assert!(!matcher.matches("e"));
+
+                assert!(matcher.matches("aa"));
+                assert!(matcher.matches("ax"));
+                assert!(matcher.matches("🐻🐻"));
+                assert!(matcher.matches("🐻y"));
+                assert!(matcher.matches("cc"));
+                assert!(matcher.matches("cz"));
+                assert!(!matcher.matches("da"));
+                assert!(!matcher.matches("ee"));
+
+                assert!(!matcher.matches("aa "));
+                assert!(!matcher.matches("ax "));
+                assert!(!matcher.matches("🐻b "));
+                assert!(!matcher.matches("🐻y "));
+                assert!(!matcher.matches("cc "));
+                assert!(!matcher.matches("cz "));
+                assert!(!matcher.matches("da "));
+                assert!(!matcher.matches("ee "));
             }
         }
 
         #[test]
-        fn TEST_CompiledMatcher_parse_LITERAL_1() {
-            let pattern = "abcd";
+        fn TEST_CompiledMatcher_parse_RANGE_THEN_LITERAL_THEN_WILDN_THEN_RANGE_1() {
+            let pattern = "[mb]a*[der]";
 
             {
                 let flags = 0;
 
                 let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-
-                assert_eq!(1, matcher.len());
+                assert_eq!(4, matcher.len());
 
                 assert!(!matcher.matches(""));
                 assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("["));
+                assert!(!matcher.matches("]"));
+                assert!(!matcher.matches("^"));
                 assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("b"));
+                assert!(!matcher.matches("🐻"));
+                assert!(!matcher.matches("c"));
+                assert!(!matcher.matches("d"));
+                assert!(!matcher.matches("e"));
+                assert!(!matcher.matches("A"));
+                assert!(!matcher.matches("B"));
+                assert!(!matcher.matches("🐻"));
+                assert!(!matcher.matches("C"));
+                assert!(!matcher.matches("D"));
+                assert!(!matcher.matches("E"));
                 assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("ae"));
                 assert!(!matcher.matches("abc"));
-                assert!(matcher.matches("abcd"));
+                assert!(!matcher.matches("abcd"));
                 assert!(!matcher.matches("ABCD"));
                 assert!(!matcher.matches("abcde"));
-            }
-
-            {
-                let flags = IGNORE_CASE;
 
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
-
-                assert_eq!(1, matcher.len());
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("abc"));
-                assert!(matcher.matches("abcd"));
-                assert!(matcher.matches("ABCD"));
-                assert!(!matcher.matches("abcde"));
+                assert!(!matcher.matches("ma"));
+                assert!(matcher.matches("bad"));
+                assert!(!matcher.matches("🐻ad"));
+                assert!(matcher.matches("bar"));
+                assert!(!matcher.matches("🐻ar"));
+                assert!(matcher.matches("bald"));
+                assert!(!matcher.matches("🐻ald"));
+                assert!(matcher.matches("bard"));
+                assert!(!matcher.matches("🐻ard"));
+                assert!(!matcher.matches("cad"));
+                assert!(!matcher.matches("car"));
+                assert!(matcher.matches("mad"));
+                assert!(matcher.matches("mar"));
+                assert!(matcher.matches("bade"));
+                assert!(!matcher.matches("🐻ade"));
+                assert!(!matcher.matches("lade"));
+                assert!(matcher.matches("made"));
+                assert!(matcher.matches("badder"));
+                assert!(!matcher.matches("🐻adder"));
+                assert!(!matcher.matches("ladder"));
+                assert!(matcher.matches("madder"));
             }
         }
 
         #[test]
-        fn TEST_CompiledMatcher_parse_LITERAL_2() {
-            let pattern = r"ab-d";
+        fn TEST_CompiledMatcher_parse_RANGE_THEN_LITERAL_THEN_WILDN_THEN_RANGE_2() {
+            let pattern = "[m🐻]a*[der]";
 
             {
                 let flags = 0;
 
                 let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-
-                assert_eq!(1, matcher.len());
+                assert_eq!(4, matcher.len());
 
                 assert!(!matcher.matches(""));
                 assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("["));
+                assert!(!matcher.matches("]"));
+                assert!(!matcher.matches("^"));
                 assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("b"));
+                assert!(!matcher.matches("🐻"));
+                assert!(!matcher.matches("c"));
+                assert!(!matcher.matches("d"));
+                assert!(!matcher.matches("e"));
+                assert!(!matcher.matches("A"));
+                assert!(!matcher.matches("B"));
+                assert!(!matcher.matches("🐻"));
+                assert!(!matcher.matches("C"));
+                assert!(!matcher.matches("D"));
+                assert!(!matcher.matches("E"));
                 assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("ae"));
                 assert!(!matcher.matches("abc"));
-                assert!(matcher.matches("ab-d"));
-                assert!(!matcher.matches("AB-D"));
-                assert!(!matcher.matches("ab-de"));
-            }
-
-            {
-                let flags = IGNORE_CASE;
-
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                assert!(!matcher.matches("abcd"));
+                assert!(!matcher.matches("ABCD"));
+                assert!(!matcher.matches("abcde"));
 
-                assert_eq!(1, matcher.len());
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("abc"));
-                assert!(matcher.matches("ab-d"));
-                assert!(matcher.matches("AB-D"));
-                assert!(!matcher.matches("ab-de"));
+                assert!(!matcher.matches("ma"));
+                assert!(!matcher.matches("bad"));
+                assert!(matcher.matches("🐻ad"));
+                assert!(!matcher.matches("bar"));
+                assert!(matcher.matches("🐻ar"));
+                assert!(!matcher.matches("bald"));
+                assert!(matcher.matches("🐻ald"));
+                assert!(!matcher.matches("bard"));
+                assert!(matcher.matches("🐻ard"));
+                assert!(!matcher.matches("cad"));
+                assert!(!matcher.matches("car"));
+                assert!(matcher.matches("mad"));
+                assert!(matcher.matches("mar"));
+                assert!(!matcher.matches("bade"));
+                assert!(matcher.matches("🐻ade"));
+                assert!(!matcher.matches("lade"));
+                assert!(matcher.matches("made"));
+                assert!(!matcher.matches("badder"));
+                assert!(matcher.matches("🐻adder"));
+                assert!(!matcher.matches("ladder"));
+                assert!(matcher.matches("madder"));
             }
         }
 
         #[test]
-        fn TEST_CompiledMatcher_parse_LITERAL_3() {
-            let pattern = r"ab\-d";
+        fn TEST_CompiledMatcher_parse_RANGE_THEN_LITERAL_THEN_WILD1_THEN_RANGE_1() {
+            let pattern = "[mb]a?[der]";
 
             {
                 let flags = 0;
 
                 let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-
-                assert_eq!(1, matcher.len());
+                assert_eq!(4, matcher.len());
 
                 assert!(!matcher.matches(""));
                 assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("["));
+                assert!(!matcher.matches("]"));
+                assert!(!matcher.matches("^"));
                 assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("b"));
+                assert!(!matcher.matches("c"));
+                assert!(!matcher.matches("d"));
+                assert!(!matcher.matches("e"));
+                assert!(!matcher.matches("A"));
+                assert!(!matcher.matches("B"));
+                assert!(!matcher.matches("C"));
+                assert!(!matcher.matches("D"));
+                assert!(!matcher.matches("E"));
                 assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("ae"));
                 assert!(!matcher.matches("abc"));
-                assert!(matcher.matches("ab-d"));
-                assert!(!matcher.matches("AB-D"));
-                assert!(!matcher.matches("ab-de"));
-            }
-
-            {
-                let flags = IGNORE_CASE;
-
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                assert!(!matcher.matches("abcd"));
+                assert!(!matcher.matches("ABCD"));
+                assert!(!matcher.matches("abcde"));
 
-                assert_eq!(1, matcher.len());
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("abc"));
-                assert!(matcher.matches("ab-d"));
-                assert!(matcher.matches("AB-D"));
-                assert!(!matcher.matches("ab-de"));
+                assert!(!matcher.matches("ma"));
+                assert!(!matcher.matches("bad"));
+                assert!(!matcher.matches("bar"));
+                assert!(matcher.matches("bald"));
+                assert!(matcher.matches("bard"));
+                assert!(!matcher.matches("cad"));
+                assert!(!matcher.matches("car"));
+                assert!(!matcher.matches("mad"));
+                assert!(!matcher.matches("mar"));
+                assert!(matcher.matches("bade"));
+                assert!(!matcher.matches("lade"));
+                assert!(matcher.matches("made"));
+                assert!(!matcher.matches("badder"));
+                assert!(!matcher.matches("ladder"));
+                assert!(!matcher.matches("madder"));
             }
         }
 
         #[test]
-        fn TEST_CompiledMatcher_parse_LITERAL_4() {
-            let pattern = r"ab\\d";
+        fn TEST_CompiledMatcher_parse_RANGE_THEN_LITERAL_THEN_WILD1_THEN_RANGE_2() {
+            let pattern = "[mb]a?[x👀🛑👁]";
 
             {
                 let flags = 0;
@@ -2239,1050 +5251,1234 @@ mod tests {
                 let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
 
-                assert_eq!(1, matcher.len());
+                assert_eq!(4, matcher.len());
 
                 assert!(!matcher.matches(""));
                 assert!(!matcher.matches(" "));
+                assert!(!matcher.matches("["));
+                assert!(!matcher.matches("]"));
+                assert!(!matcher.matches("^"));
                 assert!(!matcher.matches("a"));
+                assert!(!matcher.matches("b"));
+                assert!(!matcher.matches("c"));
+                assert!(!matcher.matches("👁"));
+                assert!(!matcher.matches("e"));
+                assert!(!matcher.matches("A"));
+                assert!(!matcher.matches("B"));
+                assert!(!matcher.matches("C"));
+                assert!(!matcher.matches("D"));
+                assert!(!matcher.matches("E"));
                 assert!(!matcher.matches("ab"));
+                assert!(!matcher.matches("ae"));
                 assert!(!matcher.matches("abc"));
-                assert!(matcher.matches(r"ab\d"));
-                assert!(!matcher.matches(r"a🐻\d"));
-                assert!(!matcher.matches("AB-D"));
-                assert!(!matcher.matches("ab-de"));
-            }
-
-            {
-                let flags = IGNORE_CASE;
-
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                assert!(!matcher.matches("abc👁"));
+                assert!(!matcher.matches("ABC👁"));
+                assert!(!matcher.matches("abc👁🛑"));
 
-                assert_eq!(1, matcher.len());
+                assert!(matcher.matches("ba x"));
+                assert!(matcher.matches("ba 👀"));
+                assert!(matcher.matches("ba 🛑"));
+                assert!(matcher.matches("ba👁x"));
+                assert!(matcher.matches("ba 👁"));
+                assert!(matcher.matches("ba 🛑"));
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("abc"));
-                assert!(matcher.matches(r"ab\d"));
-                assert!(!matcher.matches(r"a🐻\d"));
-                assert!(matcher.matches(r"AB\D"));
-                assert!(!matcher.matches(r"A🐻\D"));
-                assert!(!matcher.matches(r"ab\de"));
-                assert!(!matcher.matches(r"a🐻\de"));
+                assert!(!matcher.matches("ma"));
+                assert!(!matcher.matches("bad"));
+                assert!(!matcher.matches("ba👀"));
+                assert!(matcher.matches("bal👀"));
+                assert!(matcher.matches("bar👁"));
+                assert!(!matcher.matches("ca👁"));
+                assert!(!matcher.matches("ca👀"));
+                assert!(!matcher.matches("ma👁"));
+                assert!(!matcher.matches("ma👀"));
+                assert!(matcher.matches("ba👁🛑"));
+                assert!(!matcher.matches("lad🛑"));
+                assert!(matcher.matches("ma👁🛑"));
+                assert!(!matcher.matches("bad👁🛑r"));
+                assert!(!matcher.matches("lad👁🛑r"));
+                assert!(!matcher.matches("mad👁🛑r"));
             }
         }
 
+        // These all taken from https://github.com/synesissoftware/shwild/blob/master/test/unit/test.unit.shwild.pattern/test.unit.shwild.pattern.cpp
         #[test]
-        fn TEST_CompiledMatcher_parse_LITERAL_5() {
-            let pattern = r"a🐻\\d";
+        fn TEST_CompiledMatcher_parse_CXX_TEST_1() {
+            /* Matching literal strings. */
+            {
+                let pattern = "abcd";
+
+                {
+                    let flags = 0;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-            {
-                let flags = 0;
+                    assert_eq!(1, matcher.len());
 
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                    assert!(matcher.matches("abcd"));
+                    assert!(!matcher.matches("ABCD"));
+                }
 
+                {
+                    let flags = IGNORE_CASE;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert_eq!(1, matcher.len());
+                    assert_eq!(1, matcher.len());
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("abc"));
-                assert!(!matcher.matches(r"ab\d"));
-                assert!(matcher.matches(r"a🐻\d"));
-                assert!(!matcher.matches("AB-D"));
-                assert!(!matcher.matches("ab-de"));
+                    assert!(matcher.matches("abcd"));
+                    assert!(matcher.matches("ABCD"));
+                }
             }
 
+            /* Using wildcards. */
             {
-                let flags = IGNORE_CASE;
-
+                let pattern = "a*c?";
+                let flags = 0;
                 let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert_eq!(1, matcher.len());
+                assert_eq!(4, matcher.len());
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("abc"));
-                assert!(!matcher.matches(r"ab\d"));
-                assert!(matcher.matches(r"a🐻\d"));
-                assert!(!matcher.matches(r"AB\D"));
-                assert!(matcher.matches(r"A🐻\D"));
-                assert!(!matcher.matches(r"ab\de"));
-                assert!(!matcher.matches(r"a🐻\de"));
+                assert!(matcher.matches("abcd"));
+                assert!(matcher.matches("a*c?"));
+                assert!(matcher.matches("abbbbbbbbcd"));
+                assert!(matcher.matches("acd"));
+                assert!(!matcher.matches("abdc"));
+                assert!(matcher.matches("abc?"));
             }
-        }
-
-        #[test]
-        fn TEST_CompiledMatcher_parse_RANGE_1() {
-            let pattern = "[abcd]";
 
+            /* Using escaped characters. */
             {
-                let flags = 0;
+                let pattern = r"a\*c\?";
 
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                {
+                    let flags = 0;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
+                    assert_eq!(1, matcher.len());
 
-                assert_eq!(1, matcher.len());
+                    assert!(!matcher.matches("abcd"));
+                    assert!(matcher.matches("a*c?"));
+                    assert!(!matcher.matches("abbbbbbbbcd"));
+                    assert!(!matcher.matches("acd"));
+                    assert!(!matcher.matches("abdc"));
+                    assert!(!matcher.matches("abc?"));
+                }
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("["));
-                assert!(!matcher.matches("]"));
-                assert!(!matcher.matches("^"));
-                assert!(matcher.matches("a"));
-                assert!(matcher.matches("b"));
-                assert!(matcher.matches("c"));
-                assert!(matcher.matches("d"));
-                assert!(!matcher.matches("e"));
-                assert!(!matcher.matches("A"));
-                assert!(!matcher.matches("B"));
-                assert!(!matcher.matches("C"));
-                assert!(!matcher.matches("D"));
-                assert!(!matcher.matches("E"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("abc"));
-                assert!(!matcher.matches("abcd"));
-                assert!(!matcher.matches("ABCD"));
-                assert!(!matcher.matches("abcde"));
+                /*
+                {
+                    let flags = SUPPRESS_BACKSLASH_ESCAPE;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                    assert_eq!(1, matcher.len());
+
+                    assert!(!matcher.matches("abcd"));
+                    assert!(matcher.matches(r"a\*c\?"));
+                }
+                 */
             }
 
+            /* Matching ranges. */
             {
-                let flags = IGNORE_CASE;
+                let pattern = "a[bc]c[defghijklm]";
 
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                {
+                    let flags = 0;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert_eq!(1, matcher.len());
+                    assert_eq!(4, matcher.len());
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("["));
-                assert!(!matcher.matches("]"));
-                assert!(!matcher.matches("^"));
-                assert!(matcher.matches("a"));
-                assert!(matcher.matches("b"));
-                assert!(matcher.matches("c"));
-                assert!(matcher.matches("d"));
-                assert!(!matcher.matches("e"));
-                assert!(matcher.matches("A"));
-                assert!(matcher.matches("B"));
-                assert!(matcher.matches("C"));
-                assert!(matcher.matches("D"));
-                assert!(!matcher.matches("E"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("abc"));
-                assert!(!matcher.matches("abcd"));
-                assert!(!matcher.matches("ABCD"));
-                assert!(!matcher.matches("abcde"));
-            }
-        }
+                    assert!(matcher.matches("abcd"));
+                    assert!(!matcher.matches("aacd"));
+                    assert!(matcher.matches("accm"));
+                    assert!(!matcher.matches("abcn"));
+                    assert!(!matcher.matches("a[bc]c[defghijklm]"));
+                }
 
-        #[test]
-        fn TEST_CompiledMatcher_parse_NOTRANGE_1() {
-            let pattern = "[^abcd]";
+                /*
+                {
+                    let flags = SUPPRESS_RANGE_SUPPORT;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
+                    assert_eq!(1, matcher.len());
+
+                    assert!(!matcher.matches("abcd"));
+                    assert!(!matcher.matches("aacd"));
+                    assert!(!matcher.matches("accm"));
+                    assert!(!matcher.matches("abcn"));
+                    assert!(matcher.matches("a[bc]c[defghijklm]"));
+                }
+                 */
+            }
+
+            /* Matching ranges with continuum. */
             {
-                let flags = 0;
+                let pattern = "a[b-c]c[d-m]";
 
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                {
+                    let flags = 0;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert_eq!(1, matcher.len());
+                    assert_eq!(4, matcher.len());
 
-                assert!(!matcher.matches(""));
-                assert!(matcher.matches(" "));
-                assert!(matcher.matches("["));
-                assert!(matcher.matches("]"));
-                assert!(matcher.matches("^"));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("b"));
-                assert!(!matcher.matches("c"));
-                assert!(!matcher.matches("d"));
-                assert!(matcher.matches("e"));
-                assert!(matcher.matches("A"));
-                assert!(matcher.matches("B"));
-                assert!(matcher.matches("C"));
-                assert!(matcher.matches("D"));
-                assert!(matcher.matches("E"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("abc"));
-                assert!(!matcher.matches("abcd"));
-                assert!(!matcher.matches("ABCD"));
-                assert!(!matcher.matches("abcde"));
+                    assert!(matcher.matches("abcd"));
+                    assert!(matcher.matches("abce"));
+                    assert!(matcher.matches("abcf"));
+                    assert!(matcher.matches("abcg"));
+                    assert!(!matcher.matches("aacd"));
+                    assert!(matcher.matches("accm"));
+                    assert!(!matcher.matches("abcn"));
+
+                    assert!(!matcher.matches("a-cm"));
+                    assert!(!matcher.matches("acc-"));
+                }
+
+                /*
+                {
+                    const shwild::Pattern   pattern2("a[b-c]c[d-m]", SHWILD_F_SUPPRESS_RANGE_CONTINUUM_SUPPORT);
+
+                    BDUT_ASSERT_TRUE(matcher.matches("abcd"));
+                    BDUT_ASSERT_TRUE(matcher.matches("a-cd"));
+                    BDUT_ASSERT_TRUE(matcher.matches("accd"));
+                    BDUT_ASSERT_FALSE(matcher.matches("aacd"));
+                    BDUT_ASSERT_TRUE(matcher.matches("accm"));
+                    BDUT_ASSERT_FALSE(matcher.matches("accl"));
+                    BDUT_ASSERT_FALSE(matcher.matches("abcn"));
+                }
+                 */
             }
 
+            /* Matching ranges with high-low continuum. */
             {
-                let flags = IGNORE_CASE;
+                let pattern = "a[c-b]c[m-d]";
 
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                {
+                    let flags = 0;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert_eq!(1, matcher.len());
+                    assert_eq!(4, matcher.len());
 
-                assert!(!matcher.matches(""));
-                assert!(matcher.matches(" "));
-                assert!(matcher.matches("["));
-                assert!(matcher.matches("]"));
-                assert!(matcher.matches("^"));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("b"));
-                assert!(!matcher.matches("c"));
-                assert!(!matcher.matches("d"));
-                assert!(matcher.matches("e"));
-                assert!(!matcher.matches("A"));
-                assert!(!matcher.matches("B"));
-                assert!(!matcher.matches("C"));
-                assert!(!matcher.matches("D"));
-                assert!(matcher.matches("E"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("abc"));
-                assert!(!matcher.matches("abcd"));
-                assert!(!matcher.matches("ABCD"));
-                assert!(!matcher.matches("abcde"));
-            }
-        }
+                    assert!(matcher.matches("abcd"));
+                    assert!(matcher.matches("abce"));
+                    assert!(matcher.matches("abcf"));
+                    assert!(matcher.matches("abcg"));
+                    assert!(!matcher.matches("aacd"));
+                    assert!(matcher.matches("accm"));
+                    assert!(!matcher.matches("abcn"));
+
+                    assert!(!matcher.matches("a-cm"));
+                    assert!(!matcher.matches("acc-"));
+                }
+
+                /*
+                {
+
+                    const shwild::Pattern   pattern2("a[b-c]c[d-m]", SHWILD_F_SUPPRESS_RANGE_CONTINUUM_SUPPORT);
 
-        #[test]
-        fn TEST_CompiledMatcher_parse_WILD1_1() {
-            let pattern = "?";
+                    BDUT_ASSERT_TRUE(matcher.matches("abcd"));
+                    BDUT_ASSERT_TRUE(matcher.matches("a-cd"));
+                    BDUT_ASSERT_TRUE(matcher.matches("accd"));
+                    BDUT_ASSERT_FALSE(matcher.matches("aacd"));
+                    BDUT_ASSERT_TRUE(matcher.matches("accm"));
+                    BDUT_ASSERT_FALSE(matcher.matches("accl"));
+                    BDUT_ASSERT_FALSE(matcher.matches("abcn"));
+                }
+                 */
+            }
 
+            /* Matching ranges with cross-case continuum. */
             {
-                let flags = 0;
+                let pattern = "a[b-C]c[m-D]";
 
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                {
+                    let flags = 0;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert_eq!(1, matcher.len());
+                    assert_eq!(4, matcher.len());
 
-                assert!(!matcher.matches(""));
-                assert!(matcher.matches(" "));
-                assert!(matcher.matches("["));
-                assert!(matcher.matches("]"));
-                assert!(matcher.matches("^"));
-                assert!(matcher.matches("a"));
-                assert!(matcher.matches("b"));
-                assert!(matcher.matches("c"));
-                assert!(matcher.matches("d"));
-                assert!(matcher.matches("e"));
-                assert!(matcher.matches("A"));
-                assert!(matcher.matches("B"));
-                assert!(matcher.matches("C"));
-                assert!(matcher.matches("D"));
-                assert!(matcher.matches("E"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("abc"));
-                assert!(!matcher.matches("abcd"));
-                assert!(!matcher.matches("ABCD"));
-                assert!(!matcher.matches("abcde"));
-            }
-        }
+                    assert!(matcher.matches("abcd"));
+                    assert!(!matcher.matches("aacd"));
+                    assert!(matcher.matches("aCcJ"));
+                    assert!(!matcher.matches("abcn"));
 
-        #[test]
-        fn TEST_CompiledMatcher_parse_WILDN_1() {
-            let pattern = "*";
+                    assert!(matcher.matches("abcd"));
+                    assert!(matcher.matches("abce"));
+                    assert!(matcher.matches("abcf"));
+                    assert!(matcher.matches("abcg"));
+                    assert!(!matcher.matches("aacd"));
+                    assert!(matcher.matches("accm"));
+                    assert!(!matcher.matches("abcn"));
+
+                    assert!(!matcher.matches("a-cm"));
+                    assert!(!matcher.matches("acc-"));
+                }
+            }
 
+            /* Matching ranges with wildcards as literals. */
             {
-                let flags = 0;
+                let pattern = "a[*]c[?]";
 
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                {
+                    let flags = 0;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert_eq!(1, matcher.len());
+                    assert_eq!(4, matcher.len());
 
-                assert!(matcher.matches(""));
-                assert!(matcher.matches(" "));
-                assert!(matcher.matches("["));
-                assert!(matcher.matches("]"));
-                assert!(matcher.matches("^"));
-                assert!(matcher.matches("a"));
-                assert!(matcher.matches("b"));
-                assert!(matcher.matches("c"));
-                assert!(matcher.matches("d"));
-                assert!(matcher.matches("e"));
-                assert!(matcher.matches("A"));
-                assert!(matcher.matches("B"));
-                assert!(matcher.matches("C"));
-                assert!(matcher.matches("D"));
-                assert!(matcher.matches("E"));
-                assert!(matcher.matches("ab"));
-                assert!(matcher.matches("abc"));
-                assert!(matcher.matches("abcd"));
-                assert!(matcher.matches("ABCD"));
-                assert!(matcher.matches("abcde"));
+                    assert!(!matcher.matches("abcd"));
+                    assert!(matcher.matches("a*c?"));
+                    assert!(!matcher.matches("abbbbbbbbcd"));
+                    assert!(!matcher.matches("acd"));
+                    assert!(!matcher.matches("abdc"));
+                    assert!(!matcher.matches("abc?"));
+                }
             }
-        }
-
-        #[test]
-        fn TEST_CompiledMatcher_parse_LITERAL_THEN_WILDN_THEN_RANGE_1() {
-            let pattern = "ma*[der]";
 
+            /* Matching ranges with continuum and leading/trailing hyphens. */
             {
-                let flags = 0;
-
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
-
-                assert_eq!(3, matcher.len());
+                let pattern = "a[-a-c]c[d-]";
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("["));
-                assert!(!matcher.matches("]"));
-                assert!(!matcher.matches("^"));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("b"));
-                assert!(!matcher.matches("c"));
-                assert!(!matcher.matches("d"));
-                assert!(!matcher.matches("e"));
-                assert!(!matcher.matches("A"));
-                assert!(!matcher.matches("B"));
-                assert!(!matcher.matches("C"));
-                assert!(!matcher.matches("D"));
-                assert!(!matcher.matches("E"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("abc"));
-                assert!(!matcher.matches("abcd"));
-                assert!(!matcher.matches("ABCD"));
-                assert!(!matcher.matches("abcde"));
+                {
+                    let flags = 0;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
+                    assert_eq!(4, matcher.len());
 
-                assert!(!matcher.matches("ma"));
-                assert!(matcher.matches("mad"));
-                assert!(matcher.matches("made"));
-                assert!(matcher.matches("madder"));
+                    assert!(matcher.matches("abcd"));
+                    assert!(matcher.matches("aacd"));
+                    assert!(matcher.matches("a-cd"));
+                    assert!(matcher.matches("acc-"));
+                    assert!(matcher.matches("a-c-"));
+                    assert!(!matcher.matches("abce"));
+                }
             }
-        }
-
-        #[test]
-        fn TEST_CompiledMatcher_parse_RANGE_THEN_WILD1_1() {
-            let pattern = "[abc]?";
 
+            /* Matching ranges with inverse continuum. */
             {
-                let flags = 0;
-
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
-
-                assert_eq!(2, matcher.len());
+                let pattern = "a[b-c]c[^d-m]";
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("["));
-                assert!(!matcher.matches("]"));
-                assert!(!matcher.matches("^"));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("b"));
-                assert!(!matcher.matches("c"));
-                assert!(!matcher.matches("d"));
-                assert!(!matcher.matches("e"));
+                {
+                    let flags = 0;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert!(matcher.matches("aa"));
-                assert!(matcher.matches("ax"));
-                assert!(matcher.matches("bb"));
-                assert!(matcher.matches("by"));
-                assert!(matcher.matches("cc"));
-                assert!(matcher.matches("cz"));
-                assert!(!matcher.matches("da"));
-                assert!(!matcher.matches("ee"));
+                    assert_eq!(4, matcher.len());
 
-                assert!(!matcher.matches("aa "));
-                assert!(!matcher.matches("ax "));
-                assert!(!matcher.matches("bb "));
-                assert!(!matcher.matches("by "));
-                assert!(!matcher.matches("cc "));
-                assert!(!matcher.matches("cz "));
-                assert!(!matcher.matches("da "));
-                assert!(!matcher.matches("ee "));
+                    assert!(!matcher.matches("abcd"));
+                    assert!(!matcher.matches("aacd"));
+                    assert!(matcher.matches("abcc"));
+                    assert!(!matcher.matches("accm"));
+                    assert!(matcher.matches("abcn"));
+                }
             }
         }
 
         #[test]
-        fn TEST_CompiledMatcher_parse_RANGE_THEN_WILD1_2() {
-            let pattern = "[a🐻c]?";
-
+        fn TEST_CompiledMatcher_parse_RANGES_WITH_ESCAPED_CHARACTERS_1() {
+            // Range
             {
-                let flags = 0;
+                let pattern = r"[\[\^\]\\\-\*\?]";
 
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                {
+                    let flags = 0;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert_eq!(2, matcher.len());
+                    assert_eq!(1, matcher.len());
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("["));
-                assert!(!matcher.matches("]"));
-                assert!(!matcher.matches("^"));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("🐻"));
-                assert!(!matcher.matches("c"));
-                assert!(!matcher.matches("d"));
-                assert!(!matcher.matches("e"));
+                    assert!(!matcher.matches(r""));
+                    assert!(!matcher.matches(r"a"));
+                    assert!(!matcher.matches(r"&"));
+                    assert!(matcher.matches(r"\"));
+                    assert!(!matcher.matches(r"/"));
+                    assert!(matcher.matches(r"["));
+                    assert!(matcher.matches(r"]"));
+                    assert!(!matcher.matches(r"{"));
+                    assert!(!matcher.matches(r"}"));
+                    assert!(matcher.matches(r"*"));
+                    assert!(!matcher.matches(r"!"));
+                    assert!(!matcher.matches(r"|"));
+                    assert!(matcher.matches(r"-"));
+                    assert!(!matcher.matches(r"+"));
+                }
+            }
 
-                assert!(matcher.matches("aa"));
-                assert!(matcher.matches("ax"));
-                assert!(matcher.matches("🐻🐻"));
-                assert!(matcher.matches("🐻y"));
-                assert!(matcher.matches("cc"));
-                assert!(matcher.matches("cz"));
-                assert!(!matcher.matches("da"));
-                assert!(!matcher.matches("ee"));
+            // NotRange
+            {
+                let pattern = r"[^\[\^\]\\\-\*\?]";
 
-                assert!(!matcher.matches("aa "));
-                assert!(!matcher.matches("ax "));
-                assert!(!matcher.matches("🐻b "));
-                assert!(!matcher.matches("🐻y "));
-                assert!(!matcher.matches("cc "));
-                assert!(!matcher.matches("cz "));
-                assert!(!matcher.matches("da "));
-                assert!(!matcher.matches("ee "));
+                {
+                    let flags = 0;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                    assert_eq!(1, matcher.len());
+
+                    assert!(!matcher.matches(r""));
+                    assert!(matcher.matches(r"a"));
+                    assert!(matcher.matches(r"&"));
+                    assert!(!matcher.matches(r"\"));
+                    assert!(matcher.matches(r"/"));
+                    assert!(!matcher.matches(r"["));
+                    assert!(!matcher.matches(r"]"));
+                    assert!(matcher.matches(r"{"));
+                    assert!(matcher.matches(r"}"));
+                    assert!(!matcher.matches(r"*"));
+                    assert!(matcher.matches(r"!"));
+                    assert!(matcher.matches(r"|"));
+                    assert!(!matcher.matches(r"-"));
+                    assert!(matcher.matches(r"+"));
+                }
             }
         }
 
         #[test]
-        fn TEST_CompiledMatcher_parse_RANGE_THEN_LITERAL_THEN_WILDN_THEN_RANGE_1() {
-            let pattern = "[mb]a*[der]";
+        fn TEST_CompiledMatcher_parse_RANGES_WITH_ESCAPED_CHARACTERS_2() {
+            // an escaped character between a continuum's `-` and its end
+            let pattern = r"[a-\zc]";
 
             {
                 let flags = 0;
-
                 let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert_eq!(4, matcher.len());
-
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("["));
-                assert!(!matcher.matches("]"));
-                assert!(!matcher.matches("^"));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("b"));
-                assert!(!matcher.matches("🐻"));
-                assert!(!matcher.matches("c"));
-                assert!(!matcher.matches("d"));
-                assert!(!matcher.matches("e"));
-                assert!(!matcher.matches("A"));
-                assert!(!matcher.matches("B"));
-                assert!(!matcher.matches("🐻"));
-                assert!(!matcher.matches("C"));
-                assert!(!matcher.matches("D"));
-                assert!(!matcher.matches("E"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("ae"));
-                assert!(!matcher.matches("abc"));
-                assert!(!matcher.matches("abcd"));
-                assert!(!matcher.matches("ABCD"));
-                assert!(!matcher.matches("abcde"));
-
+                assert_eq!(1, matcher.len());
 
-                assert!(!matcher.matches("ma"));
-                assert!(matcher.matches("bad"));
-                assert!(!matcher.matches("🐻ad"));
-                assert!(matcher.matches("bar"));
-                assert!(!matcher.matches("🐻ar"));
-                assert!(matcher.matches("bald"));
-                assert!(!matcher.matches("🐻ald"));
-                assert!(matcher.matches("bard"));
-                assert!(!matcher.matches("🐻ard"));
-                assert!(!matcher.matches("cad"));
-                assert!(!matcher.matches("car"));
-                assert!(matcher.matches("mad"));
-                assert!(matcher.matches("mar"));
-                assert!(matcher.matches("bade"));
-                assert!(!matcher.matches("🐻ade"));
-                assert!(!matcher.matches("lade"));
-                assert!(matcher.matches("made"));
-                assert!(matcher.matches("badder"));
-                assert!(!matcher.matches("🐻adder"));
-                assert!(!matcher.matches("ladder"));
-                assert!(matcher.matches("madder"));
+                assert!(matcher.matches(r"a"));
+                assert!(matcher.matches(r"b"));
+                assert!(matcher.matches(r"c"));
+                assert!(matcher.matches(r"z"));
+                assert!(!matcher.matches(r"d"));
+                assert!(!matcher.matches(r"-"));
+                assert!(!matcher.matches(r"\"));
             }
         }
 
         #[test]
-        fn TEST_CompiledMatcher_parse_RANGE_THEN_LITERAL_THEN_WILDN_THEN_RANGE_2() {
-            let pattern = "[m🐻]a*[der]";
-
+        fn TEST_CompiledMatcher_parse_RANGES_WITH_UNESCAPED_SPECIAL_CHARACTERS_1() {
+            // Range
             {
-                let flags = 0;
-
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
-
-                assert_eq!(4, matcher.len());
+                let pattern = r"[[^\]\-*?]";
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("["));
-                assert!(!matcher.matches("]"));
-                assert!(!matcher.matches("^"));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("b"));
-                assert!(!matcher.matches("🐻"));
-                assert!(!matcher.matches("c"));
-                assert!(!matcher.matches("d"));
-                assert!(!matcher.matches("e"));
-                assert!(!matcher.matches("A"));
-                assert!(!matcher.matches("B"));
-                assert!(!matcher.matches("🐻"));
-                assert!(!matcher.matches("C"));
-                assert!(!matcher.matches("D"));
-                assert!(!matcher.matches("E"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("ae"));
-                assert!(!matcher.matches("abc"));
-                assert!(!matcher.matches("abcd"));
-                assert!(!matcher.matches("ABCD"));
-                assert!(!matcher.matches("abcde"));
+                {
+                    let flags = 0;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
+                    assert_eq!(1, matcher.len());
 
-                assert!(!matcher.matches("ma"));
-                assert!(!matcher.matches("bad"));
-                assert!(matcher.matches("🐻ad"));
-                assert!(!matcher.matches("bar"));
-                assert!(matcher.matches("🐻ar"));
-                assert!(!matcher.matches("bald"));
-                assert!(matcher.matches("🐻ald"));
-                assert!(!matcher.matches("bard"));
-                assert!(matcher.matches("🐻ard"));
-                assert!(!matcher.matches("cad"));
-                assert!(!matcher.matches("car"));
-                assert!(matcher.matches("mad"));
-                assert!(matcher.matches("mar"));
-                assert!(!matcher.matches("bade"));
-                assert!(matcher.matches("🐻ade"));
-                assert!(!matcher.matches("lade"));
-                assert!(matcher.matches("made"));
-                assert!(!matcher.matches("badder"));
-                assert!(matcher.matches("🐻adder"));
-                assert!(!matcher.matches("ladder"));
-                assert!(matcher.matches("madder"));
+                    assert!(!matcher.matches(r""));
+                    assert!(!matcher.matches(r"a"));
+                    assert!(!matcher.matches(r"&"));
+                    assert!(!matcher.matches(r"\"));
+                    assert!(!matcher.matches(r"/"));
+                    assert!(matcher.matches(r"["));
+                    assert!(matcher.matches(r"]"));
+                    assert!(!matcher.matches(r"{"));
+                    assert!(!matcher.matches(r"}"));
+                    assert!(matcher.matches(r"*"));
+                    assert!(!matcher.matches(r"!"));
+                    assert!(!matcher.matches(r"|"));
+                    assert!(matcher.matches(r"-"));
+                    assert!(!matcher.matches(r"+"));
+                }
             }
-        }
-
-        #[test]
-        fn TEST_CompiledMatcher_parse_RANGE_THEN_LITERAL_THEN_WILD1_THEN_RANGE_1() {
-            let pattern = "[mb]a?[der]";
 
+            // NotRange
             {
-                let flags = 0;
+                let pattern = r"[^[^\]\-*?]";
 
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                {
+                    let flags = 0;
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert_eq!(4, matcher.len());
+                    assert_eq!(1, matcher.len());
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("["));
-                assert!(!matcher.matches("]"));
-                assert!(!matcher.matches("^"));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("b"));
-                assert!(!matcher.matches("c"));
-                assert!(!matcher.matches("d"));
-                assert!(!matcher.matches("e"));
-                assert!(!matcher.matches("A"));
-                assert!(!matcher.matches("B"));
-                assert!(!matcher.matches("C"));
-                assert!(!matcher.matches("D"));
-                assert!(!matcher.matches("E"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("ae"));
-                assert!(!matcher.matches("abc"));
-                assert!(!matcher.matches("abcd"));
-                assert!(!matcher.matches("ABCD"));
-                assert!(!matcher.matches("abcde"));
+                    assert!(!matcher.matches(r""));
+                    assert!(matcher.matches(r"a"));
+                    assert!(matcher.matches(r"&"));
+                    assert!(matcher.matches(r"\"));
+                    assert!(matcher.matches(r"/"));
+                    assert!(!matcher.matches(r"["));
+                    assert!(!matcher.matches(r"]"));
+                    assert!(matcher.matches(r"{"));
+                    assert!(matcher.matches(r"}"));
+                    assert!(!matcher.matches(r"*"));
+                    assert!(matcher.matches(r"!"));
+                    assert!(matcher.matches(r"|"));
+                    assert!(!matcher.matches(r"-"));
+                    assert!(matcher.matches(r"+"));
+                }
+            }
+        }
 
+        #[test]
+        fn TEST_CompiledMatcher_parse_TRAILING_SLASH_1() {
+            let pattern = r"abcd\";
 
-                assert!(!matcher.matches("ma"));
-                assert!(!matcher.matches("bad"));
-                assert!(!matcher.matches("bar"));
-                assert!(matcher.matches("bald"));
-                assert!(matcher.matches("bard"));
-                assert!(!matcher.matches("cad"));
-                assert!(!matcher.matches("car"));
-                assert!(!matcher.matches("mad"));
-                assert!(!matcher.matches("mar"));
-                assert!(matcher.matches("bade"));
-                assert!(!matcher.matches("lade"));
-                assert!(matcher.matches("made"));
-                assert!(!matcher.matches("badder"));
-                assert!(!matcher.matches("ladder"));
-                assert!(!matcher.matches("madder"));
-            }
+            match shwild::CompiledMatcher::from_pattern_and_flags(pattern, 0) {
+                Ok(_) => {
+                    panic!("unexpected success");
+                },
+                Err(e) => {
+                    let expected = r#"pattern syntax error (at 0:5): trailing slash"#;
+                    let actual = format!("{e}");
+
+                    assert_eq!(expected, actual);
+                },
+            };
         }
 
         #[test]
-        fn TEST_CompiledMatcher_parse_RANGE_THEN_LITERAL_THEN_WILD1_THEN_RANGE_2() {
-            let pattern = "[mb]a?[x👀🛑👁]";
+        fn TEST_CompiledMatcher_parse_SPECIAL_CHARACTERS_OUTSIDE_RANGES_1() {
+            // each of `^`, `-`, and `]` outside a range is a literal
+            // character, whereas previously, unless followed by a literal
+            // character, each was discarded or taken into a following
+            // range, such that `"^"` matched `""`, `"-*"` matched `"abc"`,
+            // and `"][ab]"` matched `"a"`
 
             {
-                let flags = 0;
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags("^", 0).unwrap();
 
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                assert_eq!(1, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(matcher.matches("^"));
+            }
 
+            {
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags("-*", 0).unwrap();
 
-                assert_eq!(4, matcher.len());
+                assert_eq!(2, matcher.len());
 
                 assert!(!matcher.matches(""));
-                assert!(!matcher.matches(" "));
-                assert!(!matcher.matches("["));
-                assert!(!matcher.matches("]"));
-                assert!(!matcher.matches("^"));
-                assert!(!matcher.matches("a"));
-                assert!(!matcher.matches("b"));
-                assert!(!matcher.matches("c"));
-                assert!(!matcher.matches("👁"));
-                assert!(!matcher.matches("e"));
-                assert!(!matcher.matches("A"));
-                assert!(!matcher.matches("B"));
-                assert!(!matcher.matches("C"));
-                assert!(!matcher.matches("D"));
-                assert!(!matcher.matches("E"));
-                assert!(!matcher.matches("ab"));
-                assert!(!matcher.matches("ae"));
+                assert!(matcher.matches("-"));
+                assert!(matcher.matches("-abc"));
                 assert!(!matcher.matches("abc"));
-                assert!(!matcher.matches("abc👁"));
-                assert!(!matcher.matches("ABC👁"));
-                assert!(!matcher.matches("abc👁🛑"));
+            }
 
-                assert!(matcher.matches("ba x"));
-                assert!(matcher.matches("ba 👀"));
-                assert!(matcher.matches("ba 🛑"));
-                assert!(matcher.matches("ba👁x"));
-                assert!(matcher.matches("ba 👁"));
-                assert!(matcher.matches("ba 🛑"));
+            {
+                let matcher = shwild::CompiledMatcher::from_pattern_and_flags("][ab]", 0).unwrap();
 
-                assert!(!matcher.matches("ma"));
-                assert!(!matcher.matches("bad"));
-                assert!(!matcher.matches("ba👀"));
-                assert!(matcher.matches("bal👀"));
-                assert!(matcher.matches("bar👁"));
-                assert!(!matcher.matches("ca👁"));
-                assert!(!matcher.matches("ca👀"));
-                assert!(!matcher.matches("ma👁"));
-                assert!(!matcher.matches("ma👀"));
-                assert!(matcher.matches("ba👁🛑"));
-                assert!(!matcher.matches("lad🛑"));
-                assert!(matcher.matches("ma👁🛑"));
-                assert!(!matcher.matches("bad👁🛑r"));
-                assert!(!matcher.matches("lad👁🛑r"));
-                assert!(!matcher.matches("mad👁🛑r"));
+                assert_eq!(2, matcher.len());
+
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches("]"));
+                assert!(matcher.matches("]a"));
+                assert!(matcher.matches("]b"));
+                assert!(!matcher.matches("]]"));
             }
         }
 
-        // These all taken from https://github.com/synesissoftware/shwild/blob/master/test/unit/test.unit.shwild.pattern/test.unit.shwild.pattern.cpp
         #[test]
-        fn TEST_CompiledMatcher_parse_CXX_TEST_1() {
-            /* Matching literal strings. */
-            {
-                let pattern = "abcd";
+        fn TEST_CompiledMatcher_parse_LARGE_PATTERN_1() {
+            // 100,000 elements - alternating literal and wild-1 - which, were
+            // parsing (or dropping) to recurse per element, would exhaust the
+            // small stack of the thread
 
-                {
-                    let flags = 0;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+            let pattern = "a?".repeat(50_000);
 
-                    assert_eq!(1, matcher.len());
+            let handle = std::thread::Builder::new()
+                .stack_size(128 * 1024)
+                .spawn(move || {
+                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(&pattern, 0).unwrap();
 
-                    assert!(matcher.matches("abcd"));
-                    assert!(!matcher.matches("ABCD"));
-                }
+                    assert_eq!(100_000, matcher.len());
 
-                {
-                    let flags = IGNORE_CASE;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                    assert!(!matcher.matches(""));
+                    assert!(!matcher.matches("b"));
+                })
+                .unwrap();
 
-                    assert_eq!(1, matcher.len());
+            handle.join().unwrap();
+        }
 
-                    assert!(matcher.matches("abcd"));
-                    assert!(matcher.matches("ABCD"));
-                }
-            }
+        #[test]
+        fn TEST_matches_WILDN_BEAR_WILD1_1() {
+            let pattern = r"Where are the* [🐼🐻]s\?";
 
-            /* Using wildcards. */
             {
-                let pattern = "a*c?";
                 let flags = 0;
                 let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                assert_eq!(4, matcher.len());
+                assert_eq!(5, matcher.len());
 
-                assert!(matcher.matches("abcd"));
-                assert!(matcher.matches("a*c?"));
-                assert!(matcher.matches("abbbbbbbbcd"));
-                assert!(matcher.matches("acd"));
-                assert!(!matcher.matches("abdc"));
-                assert!(matcher.matches("abc?"));
+                assert!(!matcher.matches(""));
+                assert!(!matcher.matches("Where are the bears?"));
+                assert!(matcher.matches("Where are the 🐻s?"));
+                assert!(matcher.matches("Where are the 🐼s?"));
+                assert!(matcher.matches("Where are their 🐻s?"));
+                assert!(matcher.matches("Where are the big brown 🐻s?"));
+                assert!(!matcher.matches("Where are the teddy-🐻s?"));
             }
+        }
+    }
 
-            /* Using escaped characters. */
-            {
-                let pattern = r"a\*c\?";
 
-                {
-                    let flags = 0;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+    mod TEST_CompiledMatcher_MATCHES_BYTES {
+        #![allow(non_snake_case)]
 
-                    assert_eq!(1, matcher.len());
+        use super::*;
 
-                    assert!(!matcher.matches("abcd"));
-                    assert!(matcher.matches("a*c?"));
-                    assert!(!matcher.matches("abbbbbbbbcd"));
-                    assert!(!matcher.matches("acd"));
-                    assert!(!matcher.matches("abdc"));
-                    assert!(!matcher.matches("abc?"));
-                }
 
-                /*
-                {
-                    let flags = SUPPRESS_BACKSLASH_ESCAPE;
+        #[test]
+        fn TEST_CompiledMatcher_matches_bytes_AGREES_WITH_matches_1() {
+            let patterns = [
+                "",
+                "abc",
+                "a*c",
+                "[A-Z]:\\\\?*\\\\?*.[ce][ox][em]",
+                "*🐻?",
+                "*🐻[!?]",
+                "Where are the* [🐼🐻]s\\?",
+                "s",
+                "ß*",
+                "*sse",
+                "\u{212A}",
+            ];
+            let inputs = [
+                "",
+                "abc",
+                "ABC",
+                "abbbbc",
+                r"C:\directory\file.exe",
+                "🐻s",
+                "teddy-🐻!",
+                "teddy-🐼s",
+                "Where are the 🐼s?",
+                "ſ",
+                "ss",
+                "SS",
+                "Straße",
+                "STRASSE",
+                "k",
+            ];
+
+            for pattern in patterns {
+                for flags in [0, IGNORE_CASE] {
                     let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                    assert_eq!(1, matcher.len());
-
-                    assert!(!matcher.matches("abcd"));
-                    assert!(matcher.matches(r"a\*c\?"));
+                    for input in inputs {
+                        assert_eq!(
+                            matcher.matches(input),
+                            matcher.matches_bytes(input.as_bytes()),
+                            "pattern '{pattern}', flags {flags}, input '{input}'"
+                        );
+                    }
                 }
-                 */
             }
+        }
 
-            /* Matching ranges. */
-            {
-                let pattern = "a[bc]c[defghijklm]";
+        #[test]
+        fn TEST_CompiledMatcher_matches_bytes_INVALID_UTF8_1() {
+            let matcher = shwild::CompiledMatcher::from_pattern_and_flags("log-??-[678].t[^y]t", 0).unwrap();
+
+            assert!(matcher.matches_bytes(b"log-ab-7.txt"));
+            assert!(matcher.matches_bytes(b"log-\xff\xfe-7.txt"));
+            assert!(matcher.matches_bytes(b"log-\xff\xfe-7.t\x80t"));
+            assert!(matcher.matches_bytes(b"log-\xc3\xa9\xff-7.txt"));
+            assert!(!matcher.matches_bytes(b"log-\xff-7.txt"));
+            assert!(!matcher.matches_bytes(b"log-ab-\xff.txt"));
+            assert!(!matcher.matches_bytes(b"log-ab-7.txt\xff"));
+        }
 
-                {
-                    let flags = 0;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+        #[test]
+        fn TEST_CompiledMatcher_matches_bytes_HAVING__IGNORE_CASE__1() {
+            let matcher = shwild::CompiledMatcher::from_pattern_and_flags("*.log", IGNORE_CASE).unwrap();
 
-                    assert_eq!(4, matcher.len());
+            assert!(matcher.matches_bytes(b"\xff.LOG"));
+            assert!(matcher.matches_bytes(b"\xff.Log"));
+            assert!(!matcher.matches_bytes(b"\xff.LOGS"));
 
-                    assert!(matcher.matches("abcd"));
-                    assert!(!matcher.matches("aacd"));
-                    assert!(matcher.matches("accm"));
-                    assert!(!matcher.matches("abcn"));
-                    assert!(!matcher.matches("a[bc]c[defghijklm]"));
-                }
+            // case variants of different encoded lengths, and of different
+            // numbers of characters, are folded as by `matches()`
 
-                /*
-                {
-                    let flags = SUPPRESS_RANGE_SUPPORT;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+            let matcher = shwild::CompiledMatcher::from_pattern_and_flags("straße.*", IGNORE_CASE).unwrap();
 
-                    assert_eq!(1, matcher.len());
+            assert!(matcher.matches("STRASSE.txt"));
+            assert!(matcher.matches_bytes(b"STRASSE.\xff"));
+            assert!(matcher.matches_bytes("ſtraße.txt".as_bytes()));
+            assert!(!matcher.matches_bytes(b"STRASE.txt"));
+            assert!(!matcher.matches_bytes(b"STRA\xffSSE.txt"));
+        }
 
-                    assert!(!matcher.matches("abcd"));
-                    assert!(!matcher.matches("aacd"));
-                    assert!(!matcher.matches("accm"));
-                    assert!(!matcher.matches("abcn"));
-                    assert!(matcher.matches("a[bc]c[defghijklm]"));
-                }
-                 */
-            }
+        #[test]
+        fn TEST_CompiledMatcher_matches_bytes_HAVING__PURE_BYTES__1() {
+            let matcher = shwild::CompiledMatcher::from_pattern_and_flags("f??.log", PURE_BYTES).unwrap();
 
-            /* Matching ranges with continuum. */
-            {
-                let pattern = "a[b-c]c[d-m]";
+            assert!(matcher.matches_bytes(b"fab.log"));
+            assert!(matcher.matches_bytes(b"f\xff\xfe.log"));
+            assert!(matcher.matches_bytes("fé.log".as_bytes()));
+            assert!(!matcher.matches_bytes("f🐻.log".as_bytes()));
 
-                {
-                    let flags = 0;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+            // `&str` input is evaluated in the same way
+            assert!(matcher.matches("fé.log"));
+            assert!(!matcher.matches("f🐻.log"));
+        }
 
-                    assert_eq!(4, matcher.len());
+        #[test]
+        fn TEST_CompiledMatcher_enable_dfa_HAVING__PURE_BYTES__1() {
+            let mut matcher = shwild::CompiledMatcher::from_pattern_and_flags("f??.log", PURE_BYTES).unwrap();
 
-                    assert!(matcher.matches("abcd"));
-                    assert!(matcher.matches("abce"));
-                    assert!(matcher.matches("abcf"));
-                    assert!(matcher.matches("abcg"));
-                    assert!(!matcher.matches("aacd"));
-                    assert!(matcher.matches("accm"));
-                    assert!(!matcher.matches("abcn"));
+            assert!(!matcher.enable_dfa(shwild::DfaMode::Eager, 1_000));
+            assert!(matcher.matches("fé.log"));
+        }
+    }
 
-                    assert!(!matcher.matches("a-cm"));
-                    assert!(!matcher.matches("acc-"));
-                }
 
-                /*
-                {
-                    const shwild::Pattern   pattern2("a[b-c]c[d-m]", SHWILD_F_SUPPRESS_RANGE_CONTINUUM_SUPPORT);
+    #[cfg(feature = "grapheme-clusters")]
+    mod TEST_CompiledMatcher_GRAPHEME_CLUSTERS {
+        #![allow(non_snake_case)]
 
-                    BDUT_ASSERT_TRUE(matcher.matches("abcd"));
-                    BDUT_ASSERT_TRUE(matcher.matches("a-cd"));
-                    BDUT_ASSERT_TRUE(matcher.matches("accd"));
-                    BDUT_ASSERT_FALSE(matcher.matches("aacd"));
-                    BDUT_ASSERT_TRUE(matcher.matches("accm"));
-                    BDUT_ASSERT_FALSE(matcher.matches("accl"));
-                    BDUT_ASSERT_FALSE(matcher.matches("abcn"));
-                }
-                 */
-            }
+        use super::*;
 
-            /* Matching ranges with high-low continuum. */
-            {
-                let pattern = "a[c-b]c[m-d]";
+        use shwild::GRAPHEME_CLUSTERS;
 
-                {
-                    let flags = 0;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                    assert_eq!(4, matcher.len());
+        /// "é", as 'e' followed by U+0301 COMBINING ACUTE ACCENT.
+        const __DECOMPOSED_E_ACUTE__ : &str = "e\u{301}";
 
-                    assert!(matcher.matches("abcd"));
-                    assert!(matcher.matches("abce"));
-                    assert!(matcher.matches("abcf"));
-                    assert!(matcher.matches("abcg"));
-                    assert!(!matcher.matches("aacd"));
-                    assert!(matcher.matches("accm"));
-                    assert!(!matcher.matches("abcn"));
+        /// "👁️", as U+1F441 EYE followed by U+FE0F VARIATION SELECTOR-16.
+        const __EYE__ : &str = "\u{1f441}\u{fe0f}";
 
-                    assert!(!matcher.matches("a-cm"));
-                    assert!(!matcher.matches("acc-"));
-                }
 
-                /*
-                {
+        #[test]
+        fn TEST_GRAPHEME_CLUSTERS_Wild1_1() {
+            let input = format!("caf{__DECOMPOSED_E_ACUTE__}");
 
-                    const shwild::Pattern   pattern2("a[b-c]c[d-m]", SHWILD_F_SUPPRESS_RANGE_CONTINUUM_SUPPORT);
+            assert!(!shwild::matches("caf?", &input, 0).unwrap());
+            assert!(shwild::matches("caf??", &input, 0).unwrap());
 
-                    BDUT_ASSERT_TRUE(matcher.matches("abcd"));
-                    BDUT_ASSERT_TRUE(matcher.matches("a-cd"));
-                    BDUT_ASSERT_TRUE(matcher.matches("accd"));
-                    BDUT_ASSERT_FALSE(matcher.matches("aacd"));
-                    BDUT_ASSERT_TRUE(matcher.matches("accm"));
-                    BDUT_ASSERT_FALSE(matcher.matches("accl"));
-                    BDUT_ASSERT_FALSE(matcher.matches("abcn"));
-                }
-                 */
-            }
+            assert!(shwild::matches("caf?", &input, GRAPHEME_CLUSTERS).unwrap());
+            assert!(!shwild::matches("caf??", &input, GRAPHEME_CLUSTERS).unwrap());
 
-            /* Matching ranges with cross-case continuum. */
-            {
-                let pattern = "a[b-C]c[m-D]";
+            assert!(shwild::matches("?", __EYE__, GRAPHEME_CLUSTERS).unwrap());
+            assert!(!shwild::matches("?", __EYE__, 0).unwrap());
 
-                {
-                    let flags = 0;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+            let matcher = shwild::CompiledMatcher::from_pattern_and_flags("caf?", GRAPHEME_CLUSTERS).unwrap();
 
-                    assert_eq!(4, matcher.len());
+            assert_eq!(Some(__DECOMPOSED_E_ACUTE__), matcher.captures(&input).unwrap().get(0));
+        }
 
-                    assert!(matcher.matches("abcd"));
-                    assert!(!matcher.matches("aacd"));
-                    assert!(matcher.matches("aCcJ"));
-                    assert!(!matcher.matches("abcn"));
+        #[test]
+        fn TEST_GRAPHEME_CLUSTERS_WildN_1() {
+            let input = format!("caf{__DECOMPOSED_E_ACUTE__}");
+
+            // `*` and `?` do not split a cluster, so there is only one
+            // (rather than two) following the 'f'
+            assert!(shwild::matches("*f??", &input, 0).unwrap());
+            assert!(!shwild::matches("*f??", &input, GRAPHEME_CLUSTERS).unwrap());
+            assert!(shwild::matches("*f?", &input, GRAPHEME_CLUSTERS).unwrap());
+            assert!(!shwild::matches("*??", __DECOMPOSED_E_ACUTE__, GRAPHEME_CLUSTERS).unwrap());
+            assert!(shwild::matches("*", &input, GRAPHEME_CLUSTERS).unwrap());
+            assert!(shwild::matches("c*", &input, GRAPHEME_CLUSTERS).unwrap());
+            assert!(shwild::matches("*?", &input, GRAPHEME_CLUSTERS).unwrap());
+        }
 
-                    assert!(matcher.matches("abcd"));
-                    assert!(matcher.matches("abce"));
-                    assert!(matcher.matches("abcf"));
-                    assert!(matcher.matches("abcg"));
-                    assert!(!matcher.matches("aacd"));
-                    assert!(matcher.matches("accm"));
-                    assert!(!matcher.matches("abcn"));
+        #[test]
+        fn TEST_GRAPHEME_CLUSTERS_Range_1() {
+            let pattern = format!("[{__EYE__}x]-?");
 
-                    assert!(!matcher.matches("a-cm"));
-                    assert!(!matcher.matches("acc-"));
-                }
-            }
+            assert!(shwild::matches(&pattern, &format!("{__EYE__}-a"), GRAPHEME_CLUSTERS).unwrap());
+            assert!(shwild::matches(&pattern, "x-a", GRAPHEME_CLUSTERS).unwrap());
+            assert!(!shwild::matches(&pattern, "y-a", GRAPHEME_CLUSTERS).unwrap());
+            assert!(!shwild::matches(&pattern, "\u{1f441}-a", GRAPHEME_CLUSTERS).unwrap());
 
-            /* Matching ranges with wildcards as literals. */
-            {
-                let pattern = "a[*]c[?]";
+            let pattern = format!("[ab{__DECOMPOSED_E_ACUTE__}]");
+
+            assert!(shwild::matches(&pattern, __DECOMPOSED_E_ACUTE__, GRAPHEME_CLUSTERS).unwrap());
+            assert!(shwild::matches(&pattern, "A", GRAPHEME_CLUSTERS | IGNORE_CASE).unwrap());
+            assert!(!shwild::matches(&pattern, "e", GRAPHEME_CLUSTERS).unwrap());
+        }
+
+        #[test]
+        fn TEST_GRAPHEME_CLUSTERS_NotRange_1() {
+            let pattern = format!("[^{__EYE__}x]");
+
+            assert!(!shwild::matches(&pattern, __EYE__, GRAPHEME_CLUSTERS).unwrap());
+            assert!(!shwild::matches(&pattern, "x", GRAPHEME_CLUSTERS).unwrap());
+            assert!(shwild::matches(&pattern, "y", GRAPHEME_CLUSTERS).unwrap());
+            assert!(shwild::matches(&pattern, __DECOMPOSED_E_ACUTE__, GRAPHEME_CLUSTERS).unwrap());
+        }
+
+        #[test]
+        fn TEST_GRAPHEME_CLUSTERS_DISABLES_DFA_1() {
+            let mut matcher = shwild::CompiledMatcher::from_pattern_and_flags("caf?", GRAPHEME_CLUSTERS).unwrap();
+
+            assert!(!matcher.enable_dfa(shwild::DfaMode::Eager, 1_000));
+            assert!(matcher.matches(&format!("caf{__DECOMPOSED_E_ACUTE__}")));
+        }
+    }
+
+
+    mod TEST_CompiledMatcher_INTROSPECTION {
+        #![allow(non_snake_case)]
+
+        use super::*;
+
+
+        fn matcher(
+            pattern : &str,
+            flags : i64,
+        ) -> shwild::CompiledMatcher {
+            shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap()
+        }
+
+
+        #[test]
+        fn TEST_is_literal_1() {
+            assert!(matcher("", 0).is_literal());
+            assert!(matcher("abc", 0).is_literal());
+            assert!(matcher(r"a\*b\?c\[d]", 0).is_literal());
+            assert!(matcher("ABC", IGNORE_CASE).is_literal());
+
+            assert!(!matcher("a?", 0).is_literal());
+            assert!(!matcher("*", 0).is_literal());
+            assert!(!matcher("[a]", 0).is_literal());
+            assert!(!matcher("[^a]", 0).is_literal());
+        }
+
+        #[test]
+        fn TEST_literal_prefix_AND_literal_suffix_1() {
+            assert_eq!(
+                ("", ""),
+                (matcher("", 0).literal_prefix(), matcher("", 0).literal_suffix())
+            );
+            assert_eq!(
+                ("abc", "abc"),
+                (matcher("abc", 0).literal_prefix(), matcher("abc", 0).literal_suffix())
+            );
+            assert_eq!(
+                ("IMG_", ".jpeg"),
+                (
+                    matcher("IMG_*.jpeg", 0).literal_prefix(),
+                    matcher("IMG_*.jpeg", 0).literal_suffix()
+                )
+            );
+            assert_eq!(
+                ("", ""),
+                (matcher("*a*", 0).literal_prefix(), matcher("*a*", 0).literal_suffix())
+            );
+            assert_eq!(
+                ("a*", "]"),
+                (
+                    matcher(r"a\*[bc]]", 0).literal_prefix(),
+                    matcher(r"a\*[bc]]", 0).literal_suffix()
+                )
+            );
+        }
+
+        #[test]
+        fn TEST_required_literals_1() {
+            assert!(matcher("", 0).required_literals().is_empty());
+            assert!(matcher("*?[ab]", 0).required_literals().is_empty());
+            assert_eq!(vec!["abc"], matcher("abc", 0).required_literals());
+            assert_eq!(vec!["a", "b", "c"], matcher("a*b?c", 0).required_literals());
+            assert_eq!(vec!["IMG_", ".", "pe"], matcher("IMG_*.[jJ]pe*", 0).required_literals());
+        }
 
-                {
-                    let flags = 0;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+        #[test]
+        fn TEST_min_len_AND_max_len_1() {
+            let lens = |pattern : &str, flags : i64| {
+                let matcher = matcher(pattern, flags);
 
-                    assert_eq!(4, matcher.len());
+                (matcher.min_len(), matcher.max_len())
+            };
 
-                    assert!(!matcher.matches("abcd"));
-                    assert!(matcher.matches("a*c?"));
-                    assert!(!matcher.matches("abbbbbbbbcd"));
-                    assert!(!matcher.matches("acd"));
-                    assert!(!matcher.matches("abdc"));
-                    assert!(!matcher.matches("abc?"));
+            assert_eq!((Some(0), Some(0)), lens("", 0));
+            assert_eq!((Some(3), Some(3)), lens("abc", 0));
+            assert_eq!((Some(3), Some(3)), lens("é🐻?", 0));
+            assert_eq!((Some(4), Some(4)), lens("a?[bc][^de]", 0));
+            assert_eq!((Some(0), None), lens("*", 0));
+            assert_eq!((Some(2), None), lens("a**b", 0));
+            assert_eq!((Some(2), Some(2)), lens("a?", PURE_BYTES));
+            assert_eq!((None, None), lens("[]", 0));
+            assert_eq!((None, None), lens("a*[]", 0));
+            assert_eq!((Some(1), Some(1)), lens("[^]", 0));
+            assert_eq!((Some(2), Some(2)), lens("ss", 0));
+            assert_eq!((Some(1), Some(2)), lens("ss", IGNORE_CASE));
+            assert_eq!((Some(1), Some(2)), lens("ß", IGNORE_CASE));
+            assert_eq!((Some(6), Some(8)), lens("Straße?", IGNORE_CASE));
+
+            #[cfg(feature = "grapheme-clusters")]
+            {
+                assert_eq!((Some(2), Some(2)), lens("ab", GRAPHEME_CLUSTERS));
+                assert_eq!((Some(2), None), lens("a?", GRAPHEME_CLUSTERS));
+                assert_eq!((Some(2), None), lens("a[bc]", GRAPHEME_CLUSTERS));
+            }
+        }
+
+        #[test]
+        fn TEST_min_len_AND_max_len_AGREE_WITH_matches_1() {
+            let patterns = ["", "a", "a?", "*", "a*b", "?[ab][^ab]", "é*?", "[]", "ss", "ß?", "ﬃ*"];
+            let inputs = [
+                "",
+                "a",
+                "ab",
+                "aab",
+                "abab",
+                "é🐻",
+                "aé🐻b",
+                "ss",
+                "ß",
+                "SSa",
+                "ßa",
+                "ffi",
+                "ﬃ",
+            ];
+
+            for flags in [0, IGNORE_CASE] {
+                for pattern in patterns {
+                    let matcher = matcher(pattern, flags);
+
+                    for input in inputs {
+                        if matcher.matches(input) {
+                            let len = input.chars().count();
+
+                            assert!(
+                                matcher.min_len().is_some_and(|min_len| min_len <= len),
+                                "pattern '{pattern}', input '{input}'"
+                            );
+                            assert!(
+                                matcher.max_len().map_or(true, |max_len| len <= max_len),
+                                "pattern '{pattern}', input '{input}'"
+                            );
+                        }
+                    }
                 }
             }
+        }
 
-            /* Matching ranges with continuum and leading/trailing hyphens. */
-            {
-                let pattern = "a[-a-c]c[d-]";
+        #[test]
+        fn TEST_matches_empty_1() {
+            for pattern in ["", "*", "***"] {
+                assert!(matcher(pattern, 0).matches_empty());
+                assert!(matcher(pattern, 0).matches(""));
+            }
 
-                {
-                    let flags = 0;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+            for pattern in ["a", "?", "*?*", "[ab]", "[^ab]", "[]"] {
+                assert!(!matcher(pattern, 0).matches_empty());
+                assert!(!matcher(pattern, 0).matches(""));
+            }
+        }
+    }
 
-                    assert_eq!(4, matcher.len());
 
-                    assert!(matcher.matches("abcd"));
-                    assert!(matcher.matches("aacd"));
-                    assert!(matcher.matches("a-cd"));
-                    assert!(matcher.matches("acc-"));
-                    assert!(matcher.matches("a-c-"));
-                    assert!(!matcher.matches("abce"));
-                }
-            }
+    mod TEST_CompiledMatcher_LANGUAGES {
+        #![allow(non_snake_case)]
 
-            /* Matching ranges with inverse continuum. */
-            {
-                let pattern = "a[b-c]c[^d-m]";
+        use super::*;
 
-                {
-                    let flags = 0;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                    assert_eq!(4, matcher.len());
+        const BUDGET : usize = 10_000;
 
-                    assert!(!matcher.matches("abcd"));
-                    assert!(!matcher.matches("aacd"));
-                    assert!(matcher.matches("abcc"));
-                    assert!(!matcher.matches("accm"));
-                    assert!(matcher.matches("abcn"));
-                }
-            }
+        fn matcher(
+            pattern : &str,
+            flags : i64,
+        ) -> shwild::CompiledMatcher {
+            shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap()
         }
 
-        #[test]
-        fn TEST_CompiledMatcher_parse_RANGES_WITH_ESCAPED_CHARACTERS_1() {
-            // Range
-            {
-                let pattern = r"[\[\^\]\\\-\*\?]";
+        /// All strings of up to `max_len` characters of `alphabet`.
+        fn all_strings(
+            alphabet : &[char],
+            max_len : usize,
+        ) -> Vec<String> {
+            let mut strings = vec![String::new()];
+            let mut from = 0;
 
-                {
-                    let flags = 0;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+            for _ in 0..max_len {
+                let to = strings.len();
 
-                    assert_eq!(1, matcher.len());
+                for i in from..to {
+                    for &c in alphabet {
+                        let s = format!("{}{c}", strings[i]);
 
-                    assert!(!matcher.matches(r""));
-                    assert!(!matcher.matches(r"a"));
-                    assert!(!matcher.matches(r"&"));
-                    assert!(matcher.matches(r"\"));
-                    assert!(!matcher.matches(r"/"));
-                    assert!(matcher.matches(r"["));
-                    assert!(matcher.matches(r"]"));
-                    assert!(!matcher.matches(r"{"));
-                    assert!(!matcher.matches(r"}"));
-                    assert!(matcher.matches(r"*"));
-                    assert!(!matcher.matches(r"!"));
-                    assert!(!matcher.matches(r"|"));
-                    assert!(matcher.matches(r"-"));
-                    assert!(!matcher.matches(r"+"));
+                        strings.push(s);
+                    }
                 }
+
+                from = to;
             }
 
-            // NotRange
-            {
-                let pattern = r"[^\[\^\]\\\-\*\?]";
+            strings
+        }
 
-                {
-                    let flags = 0;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
 
-                    assert_eq!(1, matcher.len());
+        #[test]
+        fn TEST_is_subset_of_1() {
+            assert_eq!(
+                Some(true),
+                matcher("app-*.log", 0).is_subset_of(&matcher("*.log", 0), BUDGET)
+            );
+            assert_eq!(
+                Some(false),
+                matcher("*.log", 0).is_subset_of(&matcher("app-*.log", 0), BUDGET)
+            );
+            assert_eq!(Some(true), matcher("", 0).is_subset_of(&matcher("*", 0), BUDGET));
+            assert_eq!(Some(false), matcher("*", 0).is_subset_of(&matcher("", 0), BUDGET));
+            assert_eq!(Some(true), matcher("[ab]", 0).is_subset_of(&matcher("?", 0), BUDGET));
+            assert_eq!(
+                Some(true),
+                matcher("[ab]", 0).is_subset_of(&matcher("[^cd]", 0), BUDGET)
+            );
+            assert_eq!(
+                Some(false),
+                matcher("[^cd]", 0).is_subset_of(&matcher("[ab]", 0), BUDGET)
+            );
+            assert_eq!(Some(true), matcher("[]", 0).is_subset_of(&matcher("", 0), BUDGET));
+            assert_eq!(Some(true), matcher("a?c", 0).is_subset_of(&matcher("a*", 0), BUDGET));
+            assert_eq!(Some(true), matcher("a*b*c", 0).is_subset_of(&matcher("a*c", 0), BUDGET));
+            assert_eq!(
+                Some(false),
+                matcher("a*c", 0).is_subset_of(&matcher("a*b*c", 0), BUDGET)
+            );
+        }
 
-                    assert!(!matcher.matches(r""));
-                    assert!(matcher.matches(r"a"));
-                    assert!(matcher.matches(r"&"));
-                    assert!(!matcher.matches(r"\"));
-                    assert!(matcher.matches(r"/"));
-                    assert!(!matcher.matches(r"["));
-                    assert!(!matcher.matches(r"]"));
-                    assert!(matcher.matches(r"{"));
-                    assert!(matcher.matches(r"}"));
-                    assert!(!matcher.matches(r"*"));
-                    assert!(matcher.matches(r"!"));
-                    assert!(matcher.matches(r"|"));
-                    assert!(!matcher.matches(r"-"));
-                    assert!(matcher.matches(r"+"));
-                }
-            }
+        #[test]
+        fn TEST_is_subset_of_IGNORE_CASE_1() {
+            assert_eq!(
+                Some(true),
+                matcher("abc", 0).is_subset_of(&matcher("ABC", IGNORE_CASE), BUDGET)
+            );
+            assert_eq!(
+                Some(true),
+                matcher("ABC", IGNORE_CASE).is_subset_of(&matcher("[aA][bB][cC]", 0), BUDGET)
+            );
+            assert_eq!(
+                Some(false),
+                matcher("ABC", IGNORE_CASE).is_subset_of(&matcher("abc", 0), BUDGET)
+            );
+            assert_eq!(
+                Some(true),
+                matcher("[a-c]", IGNORE_CASE).is_subset_of(&matcher("[A-Ca-c]", 0), BUDGET)
+            );
+            assert_eq!(
+                Some(true),
+                matcher("É", 0).is_subset_of(&matcher("é", IGNORE_CASE), BUDGET)
+            );
+            assert_eq!(
+                Some(true),
+                matcher("*.log", 0).is_subset_of(&matcher("*.LOG", IGNORE_CASE), BUDGET)
+            );
         }
 
         #[test]
-        fn TEST_CompiledMatcher_parse_RANGES_WITH_UNESCAPED_SPECIAL_CHARACTERS_1() {
-            // Range
-            {
-                let pattern = r"[[^\]\-*?]";
+        fn TEST_is_equivalent_to_1() {
+            assert_eq!(
+                Some(true),
+                matcher("*?*", 0).is_equivalent_to(&matcher("?*", 0), BUDGET)
+            );
+            assert_eq!(Some(true), matcher("**", 0).is_equivalent_to(&matcher("*", 0), BUDGET));
+            assert_eq!(
+                Some(true),
+                matcher("[a-c]", 0).is_equivalent_to(&matcher("[cba]", 0), BUDGET)
+            );
+            assert_eq!(
+                Some(true),
+                matcher("[abc]", IGNORE_CASE).is_equivalent_to(&matcher("[a-cA-C]", 0), BUDGET)
+            );
+            assert_eq!(Some(true), matcher("[^]", 0).is_equivalent_to(&matcher("?", 0), BUDGET));
+            assert_eq!(
+                Some(true),
+                matcher("a[]", 0).is_equivalent_to(&matcher("[]b", 0), BUDGET)
+            );
 
-                {
-                    let flags = 0;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+            assert_eq!(Some(false), matcher("*", 0).is_equivalent_to(&matcher("?*", 0), BUDGET));
+            assert_eq!(
+                Some(false),
+                matcher("a*", 0).is_equivalent_to(&matcher("*a", 0), BUDGET)
+            );
+            assert_eq!(
+                Some(false),
+                matcher("abc", 0).is_equivalent_to(&matcher("abc", IGNORE_CASE), BUDGET)
+            );
+        }
 
-                    assert_eq!(1, matcher.len());
+        #[test]
+        fn TEST_intersects_1() {
+            let intersects =
+                |pa : &str, pb : &str| shwild::intersects(&matcher(pa, 0), &matcher(pb, 0), BUDGET).unwrap();
+
+            assert_eq!(Some("az".into()), intersects("a*", "*z"));
+            assert_eq!(Some("".into()), intersects("*", ""));
+            assert_eq!(Some("abc".into()), intersects("abc", "a?c"));
+            assert_eq!(Some("a.c".into()), intersects("*.[ch]", "?.c*"));
+            assert_eq!(Some("aXb".into()), intersects("a?b", "*X*"));
+            assert_eq!(None, intersects("a*", "b*"));
+            assert_eq!(None, intersects("*.c", "*.h"));
+            assert_eq!(None, intersects("??", "???*"));
+            assert_eq!(None, intersects("[ab]", "[^ab]"));
+            assert_eq!(None, intersects("[]", "*"));
+        }
 
-                    assert!(!matcher.matches(r""));
-                    assert!(!matcher.matches(r"a"));
-                    assert!(!matcher.matches(r"&"));
-                    assert!(!matcher.matches(r"\"));
-                    assert!(!matcher.matches(r"/"));
-                    assert!(matcher.matches(r"["));
-                    assert!(matcher.matches(r"]"));
-                    assert!(!matcher.matches(r"{"));
-                    assert!(!matcher.matches(r"}"));
-                    assert!(matcher.matches(r"*"));
-                    assert!(!matcher.matches(r"!"));
-                    assert!(!matcher.matches(r"|"));
-                    assert!(matcher.matches(r"-"));
-                    assert!(!matcher.matches(r"+"));
-                }
-            }
+        #[test]
+        fn TEST_intersects_IGNORE_CASE_1() {
+            let a = matcher("*.LOG", IGNORE_CASE);
+            let b = matcher("app.log", 0);
+            let c = matcher("APP.log", 0);
+
+            assert_eq!(Some(Some("app.log".into())), shwild::intersects(&a, &b, BUDGET));
+            assert_eq!(Some(Some("APP.log".into())), shwild::intersects(&a, &c, BUDGET));
+            assert_eq!(Some(None), shwild::intersects(&b, &c, BUDGET));
+        }
 
-            // NotRange
-            {
-                let pattern = r"[^[^\]\-*?]";
+        #[test]
+        fn TEST_LANGUAGES_WITH_STATE_BUDGET_1() {
+            // the product of these has a state for each subset of the last
+            // 17 positions of the first
+            let a = matcher(&format!("*a{}", "?".repeat(16)), 0);
+            let b = matcher("*b", 0);
+
+            assert_eq!(None, a.is_subset_of(&b, BUDGET));
+            assert_eq!(None, a.is_equivalent_to(&a, BUDGET));
+            assert_eq!(None, shwild::intersects(&a, &b, BUDGET));
+
+            // whereas that of a shorter pattern is within the budget
+            let a = matcher("*a????", 0);
+
+            assert_eq!(Some(false), a.is_subset_of(&b, BUDGET));
+            assert_eq!(Some(true), a.is_equivalent_to(&a, BUDGET));
+            assert_eq!(
+                Some(Some(5)),
+                shwild::intersects(&a, &b, BUDGET).map(|witness| witness.map(|witness| witness.chars().count()))
+            );
+        }
 
-                {
-                    let flags = 0;
-                    let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+        #[test]
+        fn TEST_LANGUAGES_NOT_EXACT_1() {
+            // with `IGNORE_CASE`, "ß" matches "ss", which the automata cannot
+            // express
+            let sharp_s = matcher("ß", IGNORE_CASE);
+            let double_s = matcher("ss", IGNORE_CASE);
 
-                    assert_eq!(1, matcher.len());
+            assert!(sharp_s.matches("ss"));
+            assert!(double_s.matches("ß"));
 
-                    assert!(!matcher.matches(r""));
-                    assert!(matcher.matches(r"a"));
-                    assert!(matcher.matches(r"&"));
-                    assert!(matcher.matches(r"\"));
-                    assert!(matcher.matches(r"/"));
-                    assert!(!matcher.matches(r"["));
-                    assert!(!matcher.matches(r"]"));
-                    assert!(matcher.matches(r"{"));
-                    assert!(matcher.matches(r"}"));
-                    assert!(!matcher.matches(r"*"));
-                    assert!(matcher.matches(r"!"));
-                    assert!(matcher.matches(r"|"));
-                    assert!(!matcher.matches(r"-"));
-                    assert!(matcher.matches(r"+"));
-                }
-            }
+            assert_eq!(None, sharp_s.is_subset_of(&matcher("*", 0), BUDGET));
+            assert_eq!(None, matcher("ss", 0).is_subset_of(&double_s, BUDGET));
+            assert_eq!(None, sharp_s.is_equivalent_to(&double_s, BUDGET));
+            assert_eq!(None, shwild::intersects(&sharp_s, &matcher("ss", 0), BUDGET));
+
+            assert_eq!(Some(true), matcher("ß", 0).is_subset_of(&matcher("ß*", 0), BUDGET));
         }
 
         #[test]
-        fn TEST_CompiledMatcher_parse_TRAILING_SLASH_1() {
-            let pattern = r"abcd\";
-
-            match shwild::CompiledMatcher::from_pattern_and_flags(pattern, 0) {
-                Ok(_) => {
-                    panic!("unexpected success");
-                },
-                Err(e) => {
-                    let expected = r#"pattern syntax error (at 0:5): trailing slash"#;
-                    let actual = format!("{e}");
+        fn TEST_LANGUAGES_PURE_BYTES_1() {
+            // the automata are of characters, whereas `?` with `PURE_BYTES`
+            // matches a byte
+            let byte = matcher("??", PURE_BYTES);
+            let character = matcher("?", 0);
+
+            assert!(byte.matches("é"));
+            assert!(!character.matches("ab"));
+
+            assert_eq!(None, byte.is_subset_of(&character, BUDGET));
+            assert_eq!(None, character.is_subset_of(&byte, BUDGET));
+            assert_eq!(None, byte.is_equivalent_to(&byte, BUDGET));
+        }
 
-                    assert_eq!(expected, actual);
-                },
-            };
+        #[cfg(feature = "grapheme-clusters")]
+        #[test]
+        fn TEST_LANGUAGES_GRAPHEME_CLUSTERS_1() {
+            // the automata are of characters, whereas `?` with
+            // `GRAPHEME_CLUSTERS` matches a cluster
+            let cluster = matcher("?", GRAPHEME_CLUSTERS);
+            let character = matcher("?", 0);
+
+            assert!(cluster.matches("e\u{301}"));
+            assert!(!character.matches("e\u{301}"));
+
+            assert_eq!(None, cluster.is_subset_of(&character, BUDGET));
+            assert_eq!(None, character.is_subset_of(&cluster, BUDGET));
+            assert_eq!(None, cluster.is_equivalent_to(&character, BUDGET));
         }
 
         #[test]
-        fn TEST_matches_WILDN_BEAR_WILD1_1() {
-            let pattern = r"Where are the* [🐼🐻]s\?";
+        fn TEST_intersects_PURE_BYTES_1() {
+            assert_eq!(
+                None,
+                shwild::intersects(&matcher("??", PURE_BYTES), &matcher("?", 0), BUDGET)
+            );
+        }
 
-            {
-                let flags = 0;
-                let matcher = shwild::CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+        #[cfg(feature = "grapheme-clusters")]
+        #[test]
+        fn TEST_intersects_GRAPHEME_CLUSTERS_1() {
+            assert_eq!(
+                None,
+                shwild::intersects(&matcher("?", GRAPHEME_CLUSTERS), &matcher("?", 0), BUDGET)
+            );
+        }
 
-                assert_eq!(5, matcher.len());
+        #[test]
+        fn TEST_intersects_AGREES_WITH_ENUMERATION_1() {
+            let patterns = [
+                "",
+                "*",
+                "a",
+                "a*",
+                "*a",
+                "a*b",
+                "*a*",
+                "?",
+                "??*",
+                "[ab]*",
+                "*[^a]",
+                "?a*",
+                "*ab*",
+                "b?",
+            ];
+            let strings = all_strings(&['a', 'b', 'c'], 5);
+
+            for pa in patterns {
+                let a = matcher(pa, 0);
+
+                for pb in patterns {
+                    let b = matcher(pb, 0);
+
+                    let shortest = strings
+                        .iter()
+                        .filter(|s| a.matches(s) && b.matches(s))
+                        .map(|s| s.chars().count())
+                        .min();
+
+                    let witness = shwild::intersects(&a, &b, BUDGET).unwrap();
+
+                    assert_eq!(shortest, witness.as_ref().map(|w| w.chars().count()), "'{pa}' ∩ '{pb}'");
+
+                    if let Some(witness) = witness {
+                        assert!(a.matches(&witness) && b.matches(&witness), "'{pa}' ∩ '{pb}'");
+                    }
+                }
+            }
+        }
 
-                assert!(!matcher.matches(""));
-                assert!(!matcher.matches("Where are the bears?"));
-                assert!(matcher.matches("Where are the 🐻s?"));
-                assert!(matcher.matches("Where are the 🐼s?"));
-                assert!(matcher.matches("Where are their 🐻s?"));
-                assert!(matcher.matches("Where are the big brown 🐻s?"));
-                assert!(!matcher.matches("Where are the teddy-🐻s?"));
+        #[test]
+        fn TEST_is_subset_of_AGREES_WITH_ENUMERATION_1() {
+            let patterns = [
+                "",
+                "*",
+                "a",
+                "a*",
+                "*a",
+                "a*b",
+                "*a*",
+                "?",
+                "??*",
+                "[ab]*",
+                "*[^a]",
+                "?a*",
+                "*ab*",
+                "a**",
+            ];
+            let strings = all_strings(&['a', 'b', 'c'], 6);
+
+            for pa in patterns {
+                let a = matcher(pa, 0);
+
+                for pb in patterns {
+                    let b = matcher(pb, 0);
+
+                    let has_counter_example = strings.iter().any(|s| a.matches(s) && !b.matches(s));
+
+                    assert_eq!(
+                        !has_counter_example,
+                        a.is_subset_of(&b, BUDGET).unwrap(),
+                        "'{pa}' ⊆ '{pb}'"
+                    );
+                }
             }
         }
     }
@@ -3342,6 +6538,21 @@ mod tests {
             };
         }
 
+        #[test]
+        fn TEST_matches_INVALID_PATTERN_2_AS_Display() {
+            match shwild::matches("*?[a-9]", "", 0) {
+                Ok(_) => {
+                    panic!("unexpected success");
+                },
+                Err(e) => {
+                    let expected = r#"pattern syntax error (at 0:5): the character range a-9 does not define a supported (ASCII) range continuum"#;
+                    let actual = format!("{e}");
+
+                    assert_eq!(expected, actual);
+                },
+            };
+        }
+
         #[test]
         fn TEST_matches_INVALID_PATTERN_INCOMPLETE_RANGE_1_AS_Display() {
             match shwild::matches("[a-z", "", 0) {
@@ -3748,6 +6959,185 @@ mod regex_comparision_tests {
         assert!(!re.is_match(r"C:\directory\file.bat"));
         assert!(!cm.matches(r"C:\directory\file.bat"));
     }
+
+    #[test]
+    fn TEST_from_regex_subset_AGREES_WITH_Regex() {
+        let regexes = [
+            r"(?s)^foo.*\.log$",
+            r"(?s)^a.b$",
+            "[abc]",
+            "^[^a-c]x",
+            "y[0-9]$",
+            r"(?s)^.+?\$\x41\x{1F43B}$",
+            r"^[\]\-a]\.$",
+            r"(?s)^a.b.*$",
+            r"(?s)^a.*b$",
+        ];
+        let inputs = [
+            "",
+            "foo.log",
+            "foobar.log",
+            "foo.lag",
+            "axb",
+            "ab",
+            "xxcxx",
+            "dx",
+            "ax",
+            "y7",
+            "y77",
+            "zy",
+            "-$A🐻",
+            "$A🐻",
+            "].",
+            "-.",
+            "a.",
+            "ab",
+            "a-x-b",
+            "a\nb",
+            "a\nb\n",
+            "foo\n.log",
+        ];
+
+        for regex in regexes {
+            let re = Regex::new(regex).unwrap();
+            let pattern = super::from_regex_subset(regex).unwrap();
+            let cm = CompiledMatcher::from_pattern_and_flags(&pattern, 0).unwrap();
+
+            for input in inputs {
+                assert_eq!(
+                    re.is_match(input),
+                    cm.matches(input),
+                    "regex '{regex}', pattern '{pattern}', input '{input}'"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn TEST_from_regex_subset_ROUND_TRIP() {
+        for pattern in ["", "*", "a?b", "IMG_*.[jp]?[^eg]", r"\*\?\[\]\\^$", "🐻*[🐼🐻]"] {
+            let cm = CompiledMatcher::from_pattern_and_flags(pattern, 0).unwrap();
+
+            assert_eq!(
+                Ok(cm.to_string()),
+                super::from_regex_subset(&cm.to_regex_string(0).unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_to_regex_string_EXAMPLES() {
+        let patterns = [
+            "",
+            "*",
+            "*brown*",
+            r"[A-Z]:\\?*\\?*.[ce][ox][em]",
+            r"\*\?\[\]\\^$.+(){}|#&~",
+            "[a-c][^d-f]",
+            "[][^]",
+            "Where are the [🐼🐻]s?",
+            "Straße",
+            "*ss*",
+            "ﬁle?",
+        ];
+        let inputs = [
+            "",
+            "brown",
+            "A:\\b\\c.com",
+            r"*?[]\^$.+(){}|#&~",
+            "bx",
+            "be",
+            "\n",
+            "Where are the 🐻s!",
+            "Where are the 🐨s!",
+            "STRASSE",
+            "strasse",
+            "Strasße",
+            "ß",
+            "ſs",
+            "file1",
+            "ﬁle1",
+            "FILE1",
+        ];
+
+        for flags in [0, crate::IGNORE_CASE] {
+            for pattern in patterns {
+                let cm = CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+                let re = Regex::new(&cm.to_regex_string(0).unwrap()).unwrap();
+
+                // the flags may instead be given to the translation
+                let re_of_flags = Regex::new(
+                    &CompiledMatcher::from_pattern_and_flags(pattern, 0)
+                        .unwrap()
+                        .to_regex_string(flags)
+                        .unwrap(),
+                )
+                .unwrap();
+
+                for input in inputs {
+                    assert_eq!(
+                        cm.matches(input),
+                        re.is_match(input),
+                        "pattern '{pattern}', regex '{re}', flags {flags:#x}, input '{input}'"
+                    );
+                    assert_eq!(
+                        cm.matches(input),
+                        re_of_flags.is_match(input),
+                        "pattern '{pattern}', regex '{re_of_flags}', flags {flags:#x}, input '{input}'"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn TEST_to_regex_string_RANDOM() {
+        let mut rng = crate::test_utils::TestRandom::new(4);
+
+        let alphabet = [
+            'a',
+            'A',
+            'b',
+            'B',
+            'é',
+            'É',
+            '-',
+            '^',
+            ']',
+            '\\',
+            '.',
+            '$',
+            '\n',
+            '🐻',
+            's',
+            'S',
+            'ß',
+            'f',
+            'i',
+            'ﬁ',
+        ];
+        let pattern_alphabet = [&alphabet[..], &['*', '?', '[']].concat();
+
+        for _ in 0..1_000 {
+            let builder = rng.pattern_builder(&pattern_alphabet, 6, 4);
+
+            for flags in [0, crate::IGNORE_CASE] {
+                let cm = builder.clone().with_flags(flags).compile();
+                let re = Regex::new(&cm.to_regex_string(0).unwrap()).unwrap();
+
+                for _ in 0..20 {
+                    let input = rng.string(&alphabet, 7);
+
+                    assert_eq!(
+                        cm.matches(&input),
+                        re.is_match(&input),
+                        "pattern '{}', regex '{re}', flags {flags:#x}, input '{input}'",
+                        builder.to_pattern_string()
+                    );
+                }
+            }
+        }
+    }
 }
 
 
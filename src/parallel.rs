@@ -0,0 +1,831 @@
+// src/parallel.rs : parallel walking of the filesystem for the paths
+//                   matching a pattern
+
+// ///////////////////////////////////////////////
+// imports
+
+use crate::{
+    gitignore,
+    walk::{
+        self,
+        DescentPath,
+        GlobPlan,
+        Positions,
+    },
+    Gitignore,
+    GlobOptions,
+    Result,
+};
+
+use std::{
+    collections::VecDeque,
+    fs,
+    io,
+    num::NonZeroUsize,
+    ops::ControlFlow,
+    panic,
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::{
+        atomic::{
+            AtomicBool,
+            AtomicUsize,
+            Ordering,
+        },
+        mpsc,
+        Arc,
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+
+// ///////////////////////////////////////////////
+// constants
+
+/// The capacity of the channel of [`ParallelGlob::into_receiver()`],
+/// beyond which the walk waits for the receiver.
+const CHANNEL_CAPACITY : usize = 1024;
+
+/// The number of times that an idle worker yields before it begins to
+/// sleep between attempts to find work.
+const IDLE_YIELDS : u32 = 64;
+
+/// The time for which an idle worker sleeps between attempts to find
+/// work, once it has yielded [`IDLE_YIELDS`] times.
+const IDLE_SLEEP : Duration = Duration::from_micros(100);
+
+
+// ///////////////////////////////////////////////
+// CancelToken
+
+/// A handle by which a walk by [`ParallelGlob`] may be cancelled, from
+/// any thread. Clones share the same state.
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub struct CancelToken {
+    is_cancelled : Arc<AtomicBool>,
+}
+
+// API functions
+impl CancelToken {
+    /// Creates an instance that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// Non-mutating methods
+impl CancelToken {
+    /// Cancels the walk, which stops as soon as each of its threads has
+    /// finished with the entry that it is visiting.
+    #[inline]
+    pub fn cancel(&self) {
+        self.is_cancelled.store(true, Ordering::Release);
+    }
+
+    /// Indicates whether the walk has been cancelled.
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.is_cancelled.load(Ordering::Acquire)
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// ParallelGlob
+
+/// A directory that is to be walked.
+struct Job {
+    dir :          PathBuf,
+    positions :    Positions,
+    /// The path of the directory relative to the root of the ignore rules,
+    /// if it is within it - in which case, since ignored directories are
+    /// not walked, none of its ancestors within the root is ignored, and
+    /// so each of its entries may be evaluated against the rules alone.
+    relative :     Option<PathBuf>,
+    /// The descent path of the directory, if symbolic links are followed.
+    descent_path : Option<Arc<DescentPath>>,
+}
+
+/// The state shared by the threads of a single walk.
+struct Walk<'a, F> {
+    /// The queue of each thread, from the back of which it takes its own
+    /// jobs, and from the front of which the others steal.
+    queues :  Vec<Mutex<VecDeque<Job>>>,
+    /// The number of jobs that are queued or being processed.
+    pending : AtomicUsize,
+    /// Whether the callback has requested that the walk stop.
+    stopped : AtomicBool,
+    f :       &'a F,
+}
+
+/// A job that is being processed, which, when dropped, is marked as
+/// complete - and, if it is dropped during a panic (of the callback),
+/// stops the walk, so that the other threads do not wait for it forever.
+struct JobGuard<'w, 'a, F> {
+    walk : &'w Walk<'a, F>,
+}
+
+// Trait implementations
+impl<F> Drop for JobGuard<'_, '_, F> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            self.walk.stopped.store(true, Ordering::Release);
+        }
+
+        self.walk.pending.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// A walker of the filesystem for the paths that match a pattern, as by
+/// [`glob_with()`](crate::glob_with), that reads directories on a number
+/// of threads, which share the work by stealing directories from each
+/// other's queues. The matching paths are passed to a callback, by
+/// [`for_each()`](Self::for_each), or sent over a channel, by
+/// [`into_receiver()`](Self::into_receiver), in no particular order.
+///
+/// The walk may skip the paths ignored by a [`Gitignore`] (along with
+/// everything within ignored directories), and may be cancelled, by the
+/// callback or by a [`CancelToken`].
+///
+/// # Examples:
+///
+/// ```
+/// use shwild::{
+///     GlobOptions,
+///     ParallelGlob,
+/// };
+/// use std::{
+///     ops::ControlFlow,
+///     sync::Mutex,
+/// };
+///
+/// let options = GlobOptions::default();
+/// let walker = ParallelGlob::new("src/**/*.rs", &options).unwrap();
+/// let walker = walker.with_threads(4);
+///
+/// let paths = Mutex::new(vec![]);
+///
+/// walker.for_each(|path| {
+///     paths.lock().unwrap().push(path.unwrap());
+///
+///     ControlFlow::Continue(())
+/// });
+///
+/// assert!(paths.lock().unwrap().contains(&"src/lib.rs".into()));
+/// ```
+#[derive(Debug)]
+pub struct ParallelGlob {
+    plan :         GlobPlan,
+    threads :      usize,
+    /// The ignore rules, and the directory to which they are relative.
+    gitignore :    Option<(Gitignore, PathBuf)>,
+    cancel_token : CancelToken,
+}
+
+// API functions
+impl ParallelGlob {
+    /// Creates an instance for the given `pattern`, according to the
+    /// given `options` (of which `sort` affects only the order in which
+    /// the entries of each directory are visited), that walks with as
+    /// many threads as the available parallelism.
+    ///
+    /// # Returns:
+    /// The walker, or an error if `pattern` is not a valid wildcard
+    /// specification.
+    pub fn new(
+        pattern : &str,
+        options : &GlobOptions,
+    ) -> Result<Self> {
+        Ok(Self {
+            plan :         GlobPlan::new(pattern, options)?,
+            threads :      thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            gitignore :    None,
+            cancel_token : CancelToken::new(),
+        })
+    }
+}
+
+// Mutating methods
+impl ParallelGlob {
+    /// Specifies the number of threads (at least 1) with which to walk.
+    pub fn with_threads(
+        mut self,
+        threads : usize,
+    ) -> Self {
+        self.threads = threads.max(1);
+
+        self
+    }
+
+    /// Specifies ignore rules, relative to the directory `root`, by which
+    /// paths (and the contents of directories) are skipped.
+    pub fn with_gitignore(
+        mut self,
+        gitignore : Gitignore,
+        root : &Path,
+    ) -> Self {
+        self.gitignore = Some((gitignore, root.to_path_buf()));
+
+        self
+    }
+
+    /// Specifies the token by which the walk may be cancelled.
+    pub fn with_cancel_token(
+        mut self,
+        cancel_token : CancelToken,
+    ) -> Self {
+        self.cancel_token = cancel_token;
+
+        self
+    }
+}
+
+// Non-mutating methods
+impl ParallelGlob {
+    /// The token by which the walk may be cancelled.
+    #[inline]
+    pub fn cancel_token(&self) -> &CancelToken {
+        &self.cancel_token
+    }
+
+    /// Walks the filesystem, passing each matching path (or error) to `f`,
+    /// from any of the threads, until the walk is complete, `f` returns
+    /// [`ControlFlow::Break`], or the walk is cancelled.
+    ///
+    /// # Panics:
+    /// If `f` panics, the walk is stopped and the panic is resumed on the
+    /// calling thread.
+    pub fn for_each<F>(
+        &self,
+        f : F,
+    ) where
+        F : Fn(io::Result<PathBuf>) -> ControlFlow<()> + Sync,
+    {
+        if self.cancel_token.is_cancelled() {
+            return;
+        }
+
+        let Some(job) = self.start_(&f) else {
+            return;
+        };
+
+        let walk = Walk {
+            queues :  (0..self.threads).map(|_| Mutex::new(VecDeque::new())).collect(),
+            pending : AtomicUsize::new(1),
+            stopped : AtomicBool::new(false),
+            f :       &f,
+        };
+
+        walk.queues[0].lock().unwrap().push_back(job);
+
+        thread::scope(|scope| {
+            let handles : Vec<_> = (0..self.threads)
+                .map(|index| {
+                    let walk = &walk;
+
+                    scope.spawn(move || self.work_(walk, index))
+                })
+                .collect();
+
+            for handle in handles {
+                if let Err(payload) = handle.join() {
+                    panic::resume_unwind(payload);
+                }
+            }
+        });
+    }
+
+    /// Walks the filesystem on background threads, sending each matching
+    /// path (or error) over the returned channel, until the walk is
+    /// complete, the receiver is dropped, or the walk is cancelled.
+    pub fn into_receiver(self) -> mpsc::Receiver<io::Result<PathBuf>> {
+        let (sender, receiver) = mpsc::sync_channel(CHANNEL_CAPACITY);
+
+        thread::spawn(move || {
+            self.for_each(|result| {
+                match sender.send(result) {
+                    Ok(()) => ControlFlow::Continue(()),
+                    Err(_) => ControlFlow::Break(()),
+                }
+            });
+        });
+
+        receiver
+    }
+}
+
+// Implementation
+impl ParallelGlob {
+    /// Visits the base of the walk.
+    ///
+    /// # Returns:
+    /// The job of walking the base, if it is a directory that may contain
+    /// matches (and the walk is not stopped).
+    fn start_<F>(
+        &self,
+        f : &F,
+    ) -> Option<Job>
+    where
+        F : Fn(io::Result<PathBuf>) -> ControlFlow<()>,
+    {
+        let base = self.plan.base();
+        let positions = self.plan.start_positions();
+
+        let relative = self.relative_(base);
+
+        if !base.as_os_str().is_empty() {
+            let metadata = match fs::metadata(base) {
+                Ok(metadata) => metadata,
+                Err(error) if io::ErrorKind::NotFound == error.kind() => return None,
+                Err(error) => {
+                    let _ = f(Err(walk::with_path_(error, base)));
+
+                    return None;
+                },
+            };
+
+            // the base is the only path whose ancestors are evaluated
+            if let (Some((gitignore, _)), Some(relative)) = (&self.gitignore, &relative) {
+                if gitignore.is_ignored(relative, metadata.is_dir()) {
+                    return None;
+                }
+            }
+
+            if self.plan.is_final(&positions) && f(Ok(base.to_path_buf())).is_break() {
+                return None;
+            }
+
+            if !metadata.is_dir() {
+                return None;
+            }
+        }
+
+        if !self.plan.can_descend(&positions) {
+            return None;
+        }
+
+        match self.plan.base_descent_path() {
+            Ok(descent_path) => {
+                Some(Job {
+                    dir : base.to_path_buf(),
+                    positions,
+                    relative,
+                    descent_path,
+                })
+            },
+            Err(error) => {
+                let _ = f(Err(error));
+
+                None
+            },
+        }
+    }
+
+    /// Processes jobs - its own, or, failing that, others' - until the
+    /// walk is complete or stopped.
+    fn work_<F>(
+        &self,
+        walk : &Walk<'_, F>,
+        index : usize,
+    ) where
+        F : Fn(io::Result<PathBuf>) -> ControlFlow<()>,
+    {
+        let mut idle_count = 0;
+
+        loop {
+            if walk.stopped.load(Ordering::Acquire) || self.cancel_token.is_cancelled() {
+                return;
+            }
+
+            match Self::take_job_(walk, index) {
+                Some(job) => {
+                    idle_count = 0;
+
+                    let _guard = JobGuard {
+                        walk,
+                    };
+
+                    if self.walk_directory_(walk, index, &job).is_break() {
+                        walk.stopped.store(true, Ordering::Release);
+                    }
+                },
+                None => {
+                    if 0 == walk.pending.load(Ordering::Acquire) {
+                        return;
+                    }
+
+                    idle_count += 1;
+
+                    if idle_count < IDLE_YIELDS {
+                        thread::yield_now();
+                    } else {
+                        thread::sleep(IDLE_SLEEP);
+                    }
+                },
+            };
+        }
+    }
+
+    /// Takes the most recent of the thread's own jobs, or, failing that,
+    /// steals the oldest job of another thread.
+    fn take_job_<F>(
+        walk : &Walk<'_, F>,
+        index : usize,
+    ) -> Option<Job> {
+        if let Some(job) = walk.queues[index].lock().unwrap().pop_back() {
+            return Some(job);
+        }
+
+        let n = walk.queues.len();
+
+        (1..n).find_map(|offset| walk.queues[(index + offset) % n].lock().unwrap().pop_front())
+    }
+
+    /// Visits the entries of the directory of `job`, queueing a job for
+    /// each subdirectory that may contain matches.
+    fn walk_directory_<F>(
+        &self,
+        walk : &Walk<'_, F>,
+        index : usize,
+        job : &Job,
+    ) -> ControlFlow<()>
+    where
+        F : Fn(io::Result<PathBuf>) -> ControlFlow<()>,
+    {
+        let entries = match self.plan.read_entries(&job.dir, &job.positions) {
+            Ok(entries) => entries,
+            Err(error) => return (walk.f)(Err(error)),
+        };
+
+        for (name, file_type) in entries {
+            if walk.stopped.load(Ordering::Acquire) || self.cancel_token.is_cancelled() {
+                return ControlFlow::Break(());
+            }
+
+            let Some(visit) = self
+                .plan
+                .visit(&job.dir, &name, file_type, &job.positions, job.descent_path.as_ref())
+            else {
+                continue;
+            };
+
+            let relative = match &job.relative {
+                Some(relative) => Some(relative.join(&name)),
+                None => self.relative_(&visit.path),
+            };
+
+            if self.is_ignored_(relative.as_deref(), file_type.is_dir() || visit.descend.is_some()) {
+                continue;
+            }
+
+            if visit.is_match {
+                (walk.f)(Ok(visit.path.clone()))?;
+            }
+
+            match visit.descend {
+                Some(Ok(positions)) => {
+                    walk.pending.fetch_add(1, Ordering::AcqRel);
+                    walk.queues[index].lock().unwrap().push_back(Job {
+                        dir : visit.path,
+                        positions,
+                        relative,
+                        descent_path : visit.descent_path,
+                    });
+                },
+                Some(Err(error)) => (walk.f)(Err(error))?,
+                None => {},
+            };
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    /// Obtains `path` relative to the root of the ignore rules, if there
+    /// are any and it is within it.
+    fn relative_(
+        &self,
+        path : &Path,
+    ) -> Option<PathBuf> {
+        let (_, root) = self.gitignore.as_ref()?;
+
+        path.strip_prefix(root).ok().map(gitignore::normalise_)
+    }
+
+    /// Determines whether the entry at `relative` - the path relative to
+    /// the root of the ignore rules, if it is within it - is ignored by
+    /// the rules alone, since its parent directory is not ignored.
+    fn is_ignored_(
+        &self,
+        relative : Option<&Path>,
+        is_dir : bool,
+    ) -> bool {
+        match (&self.gitignore, relative) {
+            (Some((gitignore, _)), Some(relative)) => {
+                gitignore
+                    .check_single_(relative, is_dir)
+                    .is_some_and(|m| m.is_ignored())
+            },
+            _ => false,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use crate::{
+        glob_with,
+        test_utils::TempTree,
+    };
+
+
+    fn collect(walker : &ParallelGlob) -> Vec<PathBuf> {
+        let paths = Mutex::new(vec![]);
+
+        walker.for_each(|path| {
+            paths.lock().unwrap().push(path.unwrap());
+
+            ControlFlow::Continue(())
+        });
+
+        let mut paths = paths.into_inner().unwrap();
+
+        paths.sort();
+
+        paths
+    }
+
+
+    #[test]
+    fn TEST_ParallelGlob_AGREES_WITH_Glob_1() {
+        let tree = TempTree::with_levels("parallel-agrees", 3, 3);
+
+        for pattern in ["**/*.rs", "*/*/b.txt", "**/d1/**/*", "d0/**", "*", "nowhere/*"] {
+            let pattern = tree.pattern(pattern);
+
+            let mut expected : Vec<PathBuf> = glob_with(&pattern, &GlobOptions::default())
+                .unwrap()
+                .map(|path| path.unwrap())
+                .collect();
+
+            expected.sort();
+
+            for threads in [1, 2, 8] {
+                let walker = ParallelGlob::new(&pattern, &GlobOptions::default())
+                    .unwrap()
+                    .with_threads(threads);
+
+                assert_eq!(expected, collect(&walker), "pattern '{pattern}', threads {threads}");
+            }
+        }
+    }
+
+    #[test]
+    fn TEST_ParallelGlob_into_receiver_1() {
+        let tree = TempTree::with_levels("parallel-receiver", 2, 3);
+
+        let walker = ParallelGlob::new(&tree.pattern("**/*.rs"), &GlobOptions::default()).unwrap();
+
+        let mut paths : Vec<PathBuf> = walker.into_receiver().into_iter().map(|path| path.unwrap()).collect();
+
+        paths.sort();
+
+        assert_eq!(1 + 2 + 4 + 8, paths.len());
+        assert_eq!(tree.root.join("a.rs"), paths[0]);
+    }
+
+    #[test]
+    fn TEST_ParallelGlob_GITIGNORE_1() {
+        let tree = TempTree::with_levels("parallel-gitignore", 2, 2);
+
+        let mut gitignore = Gitignore::new();
+
+        gitignore.add_rules(Path::new(""), "d1/\n*.txt\n!/b.txt\n");
+
+        let walker = ParallelGlob::new(&tree.pattern("**"), &GlobOptions::default())
+            .unwrap()
+            .with_gitignore(gitignore, &tree.root);
+
+        let paths : Vec<PathBuf> = collect(&walker)
+            .into_iter()
+            .map(|path| path.strip_prefix(&tree.root).unwrap().to_path_buf())
+            .collect();
+
+        // `d1/` is not anchored, and so ignores `d0/d1` too
+        let expected : Vec<PathBuf> = ["", "a.rs", "b.txt", "d0", "d0/a.rs", "d0/d0", "d0/d0/a.rs"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+
+        assert_eq!(expected, paths);
+    }
+
+    #[test]
+    fn TEST_ParallelGlob_GITIGNORE_NESTED_1() {
+        let tree = TempTree::with_levels("parallel-gitignore-nested", 2, 2);
+
+        let walk = |pattern : &str| {
+            let mut gitignore = Gitignore::new();
+
+            gitignore.add_rules(Path::new(""), "*.txt\n");
+            gitignore.add_rules(Path::new("d0"), "!b.txt\nd1/\n");
+
+            let walker = ParallelGlob::new(&tree.pattern(pattern), &GlobOptions::default())
+                .unwrap()
+                .with_gitignore(gitignore, &tree.root);
+
+            collect(&walker)
+                .into_iter()
+                .map(|path| path.strip_prefix(&tree.root).unwrap().to_path_buf())
+                .collect::<Vec<_>>()
+        };
+
+        // the rules of `d0` apply within it, beneath those of the root
+        let expected : Vec<PathBuf> = ["d0", "d0/a.rs", "d0/b.txt", "d0/d0", "d0/d0/a.rs", "d0/d0/b.txt"]
+            .iter()
+            .map(PathBuf::from)
+            .collect();
+
+        assert_eq!(expected, walk("d0/**"));
+
+        // the base is evaluated with its parent directories
+        assert!(walk("d0/d1/**").is_empty());
+        assert!(walk("d0/d1/d0/**").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn TEST_ParallelGlob_SYMLINKS_CROSS_LINKED_1() {
+        let tree = TempTree::with_levels("parallel-cross-linked", 2, 1);
+
+        std::os::unix::fs::symlink(tree.root.join("d1"), tree.root.join("d0/to-d1")).unwrap();
+        std::os::unix::fs::symlink(tree.root.join("d0"), tree.root.join("d1/to-d0")).unwrap();
+
+        let options = GlobOptions {
+            follow_symlinks : true,
+            ..Default::default()
+        };
+
+        let walker = ParallelGlob::new(&tree.pattern("**/*.rs"), &options)
+            .unwrap()
+            .with_threads(4);
+
+        let results = Mutex::new(vec![]);
+
+        walker.for_each(|result| {
+            results.lock().unwrap().push(result);
+
+            ControlFlow::Continue(())
+        });
+
+        let results = results.into_inner().unwrap();
+
+        // `d0/to-d1/to-d0` and `d1/to-d0/to-d1` are loops
+        assert_eq!(2, results.iter().filter(|result| result.is_err()).count());
+
+        let mut paths : Vec<PathBuf> = results.into_iter().filter_map(|result| result.ok()).collect();
+
+        paths.sort();
+
+        let expected : Vec<PathBuf> = ["a.rs", "d0/a.rs", "d0/to-d1/a.rs", "d1/a.rs", "d1/to-d0/a.rs"]
+            .iter()
+            .map(|path| tree.root.join(path))
+            .collect();
+
+        assert_eq!(expected, paths);
+    }
+
+    #[test]
+    fn TEST_ParallelGlob_BASE_ERROR_1() {
+        let tree = TempTree::with_levels("parallel-base-error", 1, 1);
+
+        let results = |pattern : &str| {
+            let results = Mutex::new(vec![]);
+
+            ParallelGlob::new(&tree.pattern(pattern), &GlobOptions::default())
+                .unwrap()
+                .for_each(|result| {
+                    results.lock().unwrap().push(result);
+
+                    ControlFlow::Continue(())
+                });
+
+            results.into_inner().unwrap()
+        };
+
+        // a missing base is not an error, but one that cannot be examined
+        // (here, because a file is treated as a directory) is
+        assert!(results("nowhere/**/*").is_empty());
+
+        let results = results("a.rs/d0/*");
+
+        assert_eq!(1, results.len());
+        assert!(results[0].is_err());
+    }
+
+    #[test]
+    fn TEST_ParallelGlob_BREAK_1() {
+        let tree = TempTree::with_levels("parallel-break", 3, 3);
+
+        let walker = ParallelGlob::new(&tree.pattern("**/*"), &GlobOptions::default())
+            .unwrap()
+            .with_threads(4);
+
+        let count = AtomicUsize::new(0);
+
+        walker.for_each(|_| {
+            if count.fetch_add(1, Ordering::AcqRel) < 4 {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        });
+
+        // each thread may deliver at most one path after the break
+        assert!(
+            count.load(Ordering::Acquire) <= 5 + 4,
+            "{}",
+            count.load(Ordering::Acquire)
+        );
+    }
+
+    #[test]
+    fn TEST_ParallelGlob_CANCEL_1() {
+        let tree = TempTree::with_levels("parallel-cancel", 3, 3);
+
+        let walker = ParallelGlob::new(&tree.pattern("**/*"), &GlobOptions::default())
+            .unwrap()
+            .with_threads(4);
+
+        let count = AtomicUsize::new(0);
+
+        walker.cancel_token().cancel();
+        walker.for_each(|_| {
+            count.fetch_add(1, Ordering::AcqRel);
+
+            ControlFlow::Continue(())
+        });
+
+        assert_eq!(0, count.load(Ordering::Acquire));
+
+        let cancel_token = CancelToken::new();
+        let walker = ParallelGlob::new(&tree.pattern("**/*"), &GlobOptions::default())
+            .unwrap()
+            .with_threads(4)
+            .with_cancel_token(cancel_token.clone());
+
+        walker.for_each(|_| {
+            if count.fetch_add(1, Ordering::AcqRel) == 2 {
+                cancel_token.cancel();
+            }
+
+            ControlFlow::Continue(())
+        });
+
+        assert!(cancel_token.is_cancelled());
+        assert!(
+            count.load(Ordering::Acquire) <= 3 + 4,
+            "{}",
+            count.load(Ordering::Acquire)
+        );
+    }
+    #[test]
+    fn TEST_ParallelGlob_PANIC_1() {
+        let tree = TempTree::with_levels("parallel-panic", 3, 3);
+
+        let walker = ParallelGlob::new(&tree.pattern("**/*"), &GlobOptions::default())
+            .unwrap()
+            .with_threads(4);
+
+        let count = AtomicUsize::new(0);
+
+        // the walk stops, rather than waiting forever for the job of the
+        // panicking thread, and the panic is resumed
+        let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            walker.for_each(|_| {
+                if count.fetch_add(1, Ordering::AcqRel) == 2 {
+                    panic!("callback panicked");
+                }
+
+                ControlFlow::Continue(())
+            });
+        }));
+
+        let payload = result.unwrap_err();
+
+        assert_eq!(Some(&"callback panicked"), payload.downcast_ref::<&str>());
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
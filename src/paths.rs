@@ -0,0 +1,305 @@
+// src/paths.rs : component-wise matching of paths
+
+// ///////////////////////////////////////////////
+// imports
+
+use crate::{
+    utils::MatcherSequence,
+    CompiledMatcher,
+    PatternElement,
+};
+
+use std::{
+    ffi::OsStr,
+    path::{
+        Component,
+        Path,
+    },
+};
+
+
+// ///////////////////////////////////////////////
+// segment splitting
+
+/// Splits the given parsed pattern `elements` at their (literal) `'/'`
+/// separators into the elements of each (non-empty) segment.
+///
+/// # Returns:
+/// A tuple of whether the pattern begins with a separator, and the
+/// elements of each segment.
+pub(crate) fn split_segments(elements : &[PatternElement]) -> (bool, Vec<Vec<PatternElement>>) {
+    let absolute = std::matches!(elements.first(), Some(PatternElement::Literal(literal)) if literal.starts_with('/'));
+
+    let mut segment_elements = vec![vec![]];
+
+    for element in elements {
+        match element {
+            PatternElement::Literal(literal) => {
+                for (index, part) in literal.split('/').enumerate() {
+                    if 0 != index {
+                        segment_elements.push(vec![]);
+                    }
+
+                    if !part.is_empty() {
+                        segment_elements
+                            .last_mut()
+                            .unwrap()
+                            .push(PatternElement::Literal(part.into()));
+                    }
+                }
+            },
+            _ => {
+                segment_elements.last_mut().unwrap().push(element.clone());
+            },
+        };
+    }
+
+    // empty segments arise from leading, trailing, and repeated separators
+    segment_elements.retain(|elements| !elements.is_empty());
+
+    (absolute, segment_elements)
+}
+
+/// Indicates whether the segment `elements` are exactly `**`, which
+/// matches any number (0 or more) of components.
+pub(crate) fn is_any_components(elements : &[PatternElement]) -> bool {
+    std::matches!(elements, [PatternElement::WildN, PatternElement::WildN])
+}
+
+
+// ///////////////////////////////////////////////
+// PathSegments
+
+/// A pattern segment, being the part of a pattern between separators.
+#[derive(Debug)]
+enum Segment {
+    /// `**`, which matches any number (0 or more) of components.
+    AnyComponents,
+    /// Matches exactly one component.
+    Component(MatcherSequence),
+}
+
+/// A pattern split at its (literal) `'/'` separators into segments, each
+/// of which is matched against a single path component.
+#[derive(Debug)]
+pub(crate) struct PathSegments {
+    /// Whether the pattern begins with a separator.
+    absolute : bool,
+    segments : Vec<Segment>,
+}
+
+// API functions
+impl PathSegments {
+    /// Splits the given parsed pattern `elements` into segments.
+    pub(crate) fn from_elements(
+        elements : &[PatternElement],
+        flags : i64,
+    ) -> Self {
+        let (absolute, segment_elements) = split_segments(elements);
+
+        let segments = segment_elements
+            .into_iter()
+            .map(|elements| {
+                if is_any_components(&elements) {
+                    Segment::AnyComponents
+                } else {
+                    Segment::Component(CompiledMatcher::link_(&elements, flags))
+                }
+            })
+            .collect();
+
+        Self {
+            absolute,
+            segments,
+        }
+    }
+}
+
+// Non-mutating methods
+impl PathSegments {
+    /// Determines whether the components of `path` match the segments.
+    pub(crate) fn matches(
+        &self,
+        path : &Path,
+    ) -> bool {
+        let mut has_prefix = false;
+        let mut has_root = false;
+        let mut components : Vec<&OsStr> = vec![];
+
+        for component in path.components() {
+            match component {
+                Component::Prefix(prefix) => {
+                    has_prefix = true;
+
+                    components.push(prefix.as_os_str());
+                },
+                Component::RootDir => {
+                    has_root = true;
+                },
+                Component::CurDir => {},
+                Component::ParentDir | Component::Normal(_) => {
+                    components.push(component.as_os_str());
+                },
+            };
+        }
+
+        // where there is a (Windows) prefix the root is part of it, and so
+        // is not required to agree with the pattern
+        if !has_prefix && has_root != self.absolute {
+            return false;
+        }
+
+        self.matches_components_(&components)
+    }
+}
+
+// Implementation
+impl PathSegments {
+    /// Matches `components` against the segments, backtracking only to
+    /// the most recent `**`.
+    fn matches_components_(
+        &self,
+        components : &[&OsStr],
+    ) -> bool {
+        let segments = &self.segments;
+
+        let mut s = 0;
+        let mut c = 0;
+        let mut last_any : Option<(usize, usize)> = None;
+
+        loop {
+            if let Some(Segment::AnyComponents) = segments.get(s) {
+                last_any = Some((s, c));
+
+                s += 1;
+
+                continue;
+            }
+
+            if c == components.len() {
+                return segments[s..]
+                    .iter()
+                    .all(|segment| std::matches!(segment, Segment::AnyComponents));
+            }
+
+            if let Some(Segment::Component(matchers)) = segments.get(s) {
+                if matchers.matches_bytes(components[c].as_encoded_bytes()) {
+                    s += 1;
+                    c += 1;
+
+                    continue;
+                }
+            }
+
+            match last_any {
+                Some((any_s, any_c)) => {
+                    // the `**` consumes one more component
+                    last_any = Some((any_s, any_c + 1));
+
+                    s = any_s + 1;
+                    c = any_c + 1;
+                },
+                None => return false,
+            };
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use crate::{
+        CompiledMatcher,
+        IGNORE_CASE,
+    };
+
+    use std::path::Path;
+
+
+    fn matches_path_components(
+        pattern : &str,
+        flags : i64,
+        path : &str,
+    ) -> bool {
+        CompiledMatcher::from_pattern_and_flags(pattern, flags)
+            .unwrap()
+            .matches_path_components(Path::new(path))
+    }
+
+
+    #[test]
+    fn TEST_matches_path_components_SIMPLE_1() {
+        assert!(matches_path_components("src/*.rs", 0, "src/lib.rs"));
+        assert!(matches_path_components("src/*.rs", 0, "./src/lib.rs"));
+        assert!(matches_path_components("src/*.rs", 0, "src//lib.rs"));
+        assert!(matches_path_components("src/*.rs", 0, "src/lib.rs/"));
+        assert!(matches_path_components("src//*.rs/", 0, "src/lib.rs"));
+        assert!(!matches_path_components("src/*.rs", 0, "src/x/lib.rs"));
+        assert!(!matches_path_components("src/*.rs", 0, "src"));
+        assert!(!matches_path_components("src/*.rs", 0, "SRC/lib.rs"));
+        assert!(matches_path_components("src/*.rs", IGNORE_CASE, "SRC/lib.RS"));
+
+        // `*` does not cross a separator
+        assert!(!matches_path_components("*.rs", 0, "src/lib.rs"));
+        assert!(matches_path_components("*/*.rs", 0, "src/lib.rs"));
+        assert!(!matches_path_components("s?c?lib.rs", 0, "src/lib.rs"));
+    }
+
+    #[test]
+    fn TEST_matches_path_components_ABSOLUTE_1() {
+        assert!(matches_path_components("/usr/*/bin", 0, "/usr/local/bin"));
+        assert!(!matches_path_components("/usr/*/bin", 0, "usr/local/bin"));
+        assert!(!matches_path_components("usr/*/bin", 0, "/usr/local/bin"));
+        assert!(matches_path_components("/", 0, "/"));
+        assert!(!matches_path_components("/", 0, "/usr"));
+    }
+
+    #[test]
+    fn TEST_matches_path_components_ANY_COMPONENTS_1() {
+        assert!(matches_path_components("src/**/*.rs", 0, "src/lib.rs"));
+        assert!(matches_path_components("src/**/*.rs", 0, "src/a/lib.rs"));
+        assert!(matches_path_components("src/**/*.rs", 0, "src/a/b/c/lib.rs"));
+        assert!(!matches_path_components("src/**/*.rs", 0, "src/a/b/c/lib.c"));
+        assert!(!matches_path_components("src/**/*.rs", 0, "tests/a/lib.rs"));
+
+        assert!(matches_path_components("**", 0, ""));
+        assert!(matches_path_components("**", 0, "a/b/c"));
+        assert!(matches_path_components("**/b/**", 0, "a/b/c"));
+        assert!(matches_path_components("**/b/**", 0, "b"));
+        assert!(!matches_path_components("**/b/**", 0, "a/c"));
+        assert!(matches_path_components("a/**/b/**/c", 0, "a/x/b/y/b/z/c"));
+        assert!(!matches_path_components("a/**/b/**/c", 0, "a/x/c/y/z"));
+
+        // `**` within a segment is not special
+        assert!(!matches_path_components("src**.rs", 0, "src/lib.rs"));
+        assert!(matches_path_components("src**.rs", 0, "src-lib.rs"));
+    }
+
+    #[test]
+    fn TEST_matches_path_components_PARENT_DIR_1() {
+        assert!(matches_path_components("../*/lib.rs", 0, "../src/lib.rs"));
+        assert!(matches_path_components("?\\./*/lib.rs", 0, "../src/lib.rs"));
+        assert!(!matches_path_components("*/lib.rs", 0, "../src/lib.rs"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn TEST_matches_path_components_NON_UTF8_1() {
+        use std::{
+            ffi::OsStr,
+            os::unix::ffi::OsStrExt,
+        };
+
+        let path = Path::new(OsStr::from_bytes(b"logs/app-\xff.log"));
+
+        let matcher = CompiledMatcher::from_pattern_and_flags("logs/app-?.log", 0).unwrap();
+
+        assert!(matcher.matches_path_components(path));
+        assert!(matcher.matches_path(path));
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
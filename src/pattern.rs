@@ -0,0 +1,1052 @@
+// src/pattern.rs : the parsed (abstract syntax tree) form of a pattern
+
+// ///////////////////////////////////////////////
+// imports
+
+use crate::CompiledMatcher;
+
+use std::{
+    borrow::Cow,
+    fmt as std_fmt,
+    matches as std_matches,
+};
+
+
+// ///////////////////////////////////////////////
+// PatternElement
+
+/// An element of a parsed [`Pattern`].
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Eq, PartialEq)]
+#[derive(Hash)]
+pub enum PatternElement {
+    /// A literal, which is a non-empty, variable-length string, with any
+    /// escapes resolved, e.g. `"a?b"` for `r"a\?b"`.
+    Literal(String),
+    /// `?`, which matches exactly one character.
+    Wild1,
+    /// `*`, which matches any number (0 or more) of characters.
+    WildN,
+    /// A range, e.g. `"[a-c]"`, or, when `negated`, a not-range, e.g.
+    /// `"[^a-c]"`.
+    Range {
+        /// The characters of the range, in the order specified, with any
+        /// continuum, e.g. `a-c`, expanded, e.g. to `'a'`, `'b'`, `'c'`.
+        chars :   Vec<char>,
+        /// Whether the range is a not-range.
+        negated : bool,
+    },
+}
+
+// Non-mutating methods
+impl PatternElement {
+    /// Invokes the method of `visitor` corresponding to the element.
+    pub fn accept<V>(
+        &self,
+        visitor : &mut V,
+    ) where
+        V : Visitor + ?Sized,
+    {
+        match self {
+            Self::Literal(literal) => visitor.visit_literal(literal),
+            Self::Wild1 => visitor.visit_wild1(),
+            Self::WildN => visitor.visit_wild_n(),
+            Self::Range {
+                chars,
+                negated,
+            } => visitor.visit_range(chars, *negated),
+        };
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// Visitor
+
+/// Defines behaviour for walking the elements of a [`Pattern`], by way of
+/// [`Pattern::accept()`].
+///
+/// Each method has an empty default implementation, so that an
+/// implementation need define only those of interest.
+///
+/// # Examples:
+///
+/// ```
+/// use shwild::Visitor;
+///
+/// #[derive(Default)]
+/// struct WildcardCounter(usize);
+///
+/// impl Visitor for WildcardCounter {
+///     fn visit_wild1(&mut self) {
+///         self.0 += 1;
+///     }
+///
+///     fn visit_wild_n(&mut self) {
+///         self.0 += 1;
+///     }
+/// }
+///
+/// let pattern = shwild::parse("*.[ch]??", 0).unwrap();
+///
+/// let mut counter = WildcardCounter::default();
+///
+/// pattern.accept(&mut counter);
+///
+/// assert_eq!(3, counter.0);
+/// ```
+pub trait Visitor {
+    /// Visits a literal, with any escapes resolved.
+    fn visit_literal(
+        &mut self,
+        literal : &str,
+    ) {
+        let _ = literal;
+    }
+
+    /// Visits a `?`.
+    fn visit_wild1(&mut self) {
+    }
+
+    /// Visits a `*`.
+    fn visit_wild_n(&mut self) {
+    }
+
+    /// Visits a range or, when `negated`, a not-range.
+    fn visit_range(
+        &mut self,
+        chars : &[char],
+        negated : bool,
+    ) {
+        let _ = (chars, negated);
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// Pattern
+
+/// A parsed pattern, as obtained from [`parse()`](crate::parse), which
+/// may be inspected, walked by a [`Visitor`], and compiled into a
+/// [`CompiledMatcher`].
+///
+/// # Examples:
+///
+/// ```
+/// use shwild::PatternElement;
+///
+/// let pattern = shwild::parse(r"a\?[^bc]*", 0).unwrap();
+///
+/// assert_eq!(
+///     &[
+///         PatternElement::Literal("a?".into()),
+///         PatternElement::Range {
+///             chars :   vec!['b', 'c'],
+///             negated : true,
+///         },
+///         PatternElement::WildN,
+///     ],
+///     pattern.elements()
+/// );
+///
+/// assert!(pattern.compile().matches("a?d-and-more"));
+/// ```
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Eq, PartialEq)]
+pub struct Pattern {
+    elements : Vec<PatternElement>,
+    flags :    i64,
+}
+
+// API functions
+impl Pattern {
+    pub(crate) fn new(
+        elements : Vec<PatternElement>,
+        flags : i64,
+    ) -> Self {
+        Self {
+            elements,
+            flags,
+        }
+    }
+}
+
+// Non-mutating methods
+impl Pattern {
+    /// The elements of the pattern, in pattern order.
+    #[inline]
+    pub fn elements(&self) -> &[PatternElement] {
+        &self.elements
+    }
+
+    /// The flags with which the pattern was parsed, and with which it will
+    /// be compiled.
+    #[inline]
+    pub fn flags(&self) -> i64 {
+        self.flags
+    }
+
+    /// Walks the elements of the pattern, in pattern order, invoking for
+    /// each the corresponding method of `visitor`.
+    pub fn accept<V>(
+        &self,
+        visitor : &mut V,
+    ) where
+        V : Visitor + ?Sized,
+    {
+        for element in &self.elements {
+            element.accept(visitor);
+        }
+    }
+
+    /// Compiles the pattern into a matcher.
+    #[inline]
+    pub fn compile(&self) -> CompiledMatcher {
+        CompiledMatcher::from_elements_(self.elements.clone(), self.flags)
+    }
+}
+
+// Mutating methods
+impl Pattern {
+    /// Obtains the elements of the pattern, consuming the instance.
+    #[inline]
+    pub fn into_elements(self) -> Vec<PatternElement> {
+        self.elements
+    }
+}
+
+// Trait implementations
+
+impl From<Pattern> for CompiledMatcher {
+    fn from(pattern : Pattern) -> Self {
+        Self::from_elements_(pattern.elements, pattern.flags)
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// PatternBuilder
+
+/// Builds a pattern programmatically, element by element, such that
+/// literal fragments need not be escaped by the caller.
+///
+/// The resulting pattern may be obtained as a [`Pattern`], a
+/// [`CompiledMatcher`], or a (canonical) pattern string, which is
+/// guaranteed to parse back to the same [`Pattern`].
+///
+/// # Examples:
+///
+/// ```
+/// use shwild::PatternBuilder;
+///
+/// let user_fragment = "report[final]?";
+///
+/// let builder = PatternBuilder::new()
+///     .literal(user_fragment)
+///     .wild_n()
+///     .literal(".")
+///     .range("ch".chars());
+///
+/// assert_eq!(r"report\[final\]\?*.[ch]", builder.to_pattern_string());
+///
+/// let matcher = builder.compile();
+///
+/// assert!(matcher.matches("report[final]?-v2.c"));
+/// assert!(!matcher.matches("report-v2.c"));
+/// ```
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+pub struct PatternBuilder {
+    elements : Vec<PatternElement>,
+    flags :    i64,
+}
+
+// API functions
+impl PatternBuilder {
+    /// Creates an empty builder, with no flags.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+// Mutating methods
+impl PatternBuilder {
+    /// Specifies the flags with which the pattern is to be compiled.
+    pub fn with_flags(
+        mut self,
+        flags : i64,
+    ) -> Self {
+        self.flags = flags;
+
+        self
+    }
+
+    /// Appends a literal, which is matched exactly (subject to the flags),
+    /// such that, e.g., `"*"` matches only an asterisk. An empty literal
+    /// has no effect.
+    pub fn literal(
+        mut self,
+        literal : &str,
+    ) -> Self {
+        self.push_literal_(literal);
+
+        self
+    }
+
+    /// Appends a `?`.
+    pub fn wild1(mut self) -> Self {
+        self.elements.push(PatternElement::Wild1);
+
+        self
+    }
+
+    /// Appends a `*`.
+    pub fn wild_n(mut self) -> Self {
+        self.elements.push(PatternElement::WildN);
+
+        self
+    }
+
+    /// Appends a range, which matches any one of `chars`.
+    pub fn range<I>(
+        mut self,
+        chars : I,
+    ) -> Self
+    where
+        I : IntoIterator<Item = char>,
+    {
+        self.elements.push(PatternElement::Range {
+            chars :   chars.into_iter().collect(),
+            negated : false,
+        });
+
+        self
+    }
+
+    /// Appends a not-range, which matches any one character other than
+    /// `chars`.
+    pub fn not_range<I>(
+        mut self,
+        chars : I,
+    ) -> Self
+    where
+        I : IntoIterator<Item = char>,
+    {
+        self.elements.push(PatternElement::Range {
+            chars :   chars.into_iter().collect(),
+            negated : true,
+        });
+
+        self
+    }
+
+    /// Appends the elements of `pattern` (but not its flags).
+    pub fn pattern(
+        mut self,
+        pattern : &Pattern,
+    ) -> Self {
+        for element in pattern.elements() {
+            match element {
+                PatternElement::Literal(literal) => self.push_literal_(literal),
+                _ => self.elements.push(element.clone()),
+            };
+        }
+
+        self
+    }
+}
+
+// Non-mutating methods
+impl PatternBuilder {
+    /// Obtains the pattern.
+    pub fn build(&self) -> Pattern {
+        Pattern::new(self.elements.clone(), self.flags)
+    }
+
+    /// Obtains the pattern compiled into a matcher.
+    pub fn compile(&self) -> CompiledMatcher {
+        CompiledMatcher::from_elements_(self.elements.clone(), self.flags)
+    }
+
+    /// Obtains the canonical pattern string, which parses back to the
+    /// same elements as are obtained by [`build()`](Self::build).
+    pub fn to_pattern_string(&self) -> String {
+        let mut s = String::new();
+
+        // writing to a `String` cannot fail
+        let _ = write_elements(&mut s, &self.elements);
+
+        s
+    }
+}
+
+// Implementation
+impl PatternBuilder {
+    /// Appends `literal`, merging it with any immediately preceding
+    /// literal, as the parser would.
+    fn push_literal_(
+        &mut self,
+        literal : &str,
+    ) {
+        if literal.is_empty() {
+            return;
+        }
+
+        match self.elements.last_mut() {
+            Some(PatternElement::Literal(prior)) => prior.push_str(literal),
+            _ => self.elements.push(PatternElement::Literal(literal.into())),
+        };
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// canonical form
+
+/// Characters that are escaped in a literal in canonical form.
+const LITERAL_SPECIAL_CHARACTERS : &[char] = &['*', '?', '[', '\\', ']'];
+
+/// Characters that are escaped in a range in canonical form.
+const RANGE_SPECIAL_CHARACTERS : &[char] = &['-', '[', '\\', ']', '^'];
+
+/// Writes the canonical form of `elements`, in which every special
+/// character in a literal or range is escaped, and each range character
+/// is written individually.
+pub(crate) fn write_elements<W>(
+    w : &mut W,
+    elements : &[PatternElement],
+) -> std_fmt::Result
+where
+    W : std_fmt::Write,
+{
+    for element in elements {
+        match element {
+            PatternElement::Literal(literal) => write_escaped_(w, literal, LITERAL_SPECIAL_CHARACTERS)?,
+            PatternElement::Wild1 => w.write_char('?')?,
+            PatternElement::WildN => w.write_char('*')?,
+            PatternElement::Range {
+                chars,
+                negated,
+            } => {
+                w.write_str(if *negated { "[^" } else { "[" })?;
+
+                for &c in chars {
+                    write_escaped_(w, c.encode_utf8(&mut [0; 4]), RANGE_SPECIAL_CHARACTERS)?;
+                }
+
+                w.write_char(']')?;
+            },
+        };
+    }
+
+    Ok(())
+}
+
+/// Writes the normalised form of `elements`, which is the canonical form
+/// but for that: each run of `?`s and `*`s is written as its `?`s
+/// followed by a single `*`, if it has any; and the characters of each
+/// range are sorted and deduplicated, if `sorts_ranges`, with each run of
+/// three or more consecutive same-case ASCII letters written as a
+/// continuum.
+///
+/// Adjacent literals are (necessarily) written as one.
+pub(crate) fn write_normalised_elements<W>(
+    w : &mut W,
+    elements : &[PatternElement],
+    sorts_ranges : bool,
+) -> std_fmt::Result
+where
+    W : std_fmt::Write,
+{
+    let mut elements = elements.iter().peekable();
+
+    while let Some(element) = elements.next() {
+        match element {
+            PatternElement::Literal(literal) => write_escaped_(w, literal, LITERAL_SPECIAL_CHARACTERS)?,
+            PatternElement::Wild1 | PatternElement::WildN => {
+                let mut num_wild1s = 0;
+                let mut has_wild_n = false;
+                let mut element = element;
+
+                loop {
+                    match element {
+                        PatternElement::Wild1 => num_wild1s += 1,
+                        _ => has_wild_n = true,
+                    };
+
+                    match elements.next_if(|e| std_matches!(e, PatternElement::Wild1 | PatternElement::WildN)) {
+                        Some(next) => element = next,
+                        None => break,
+                    };
+                }
+
+                for _ in 0..num_wild1s {
+                    w.write_char('?')?;
+                }
+
+                if has_wild_n {
+                    w.write_char('*')?;
+                }
+            },
+            PatternElement::Range {
+                chars,
+                negated,
+            } => {
+                let mut chars = chars.clone();
+
+                if sorts_ranges {
+                    chars.sort_unstable();
+                    chars.dedup();
+                }
+
+                w.write_str(if *negated { "[^" } else { "[" })?;
+
+                let mut from = 0;
+
+                while from < chars.len() {
+                    let first = chars[from];
+                    let mut to = from + 1;
+
+                    if first.is_ascii_alphabetic() {
+                        while to < chars.len()
+                            && chars[to].is_ascii_alphabetic()
+                            && chars[to].is_ascii_lowercase() == first.is_ascii_lowercase()
+                            && chars[to] as u32 == chars[to - 1] as u32 + 1
+                        {
+                            to += 1;
+                        }
+                    }
+
+                    if to - from >= 3 {
+                        w.write_char(first)?;
+                        w.write_char('-')?;
+                        w.write_char(chars[to - 1])?;
+                    } else {
+                        for &c in &chars[from..to] {
+                            write_escaped_(w, c.encode_utf8(&mut [0; 4]), RANGE_SPECIAL_CHARACTERS)?;
+                        }
+                    }
+
+                    from = to;
+                }
+
+                w.write_char(']')?;
+            },
+        };
+    }
+
+    Ok(())
+}
+
+fn write_escaped_<W>(
+    w : &mut W,
+    s : &str,
+    special_characters : &[char],
+) -> std_fmt::Result
+where
+    W : std_fmt::Write,
+{
+    for c in s.chars() {
+        if special_characters.contains(&c) {
+            w.write_char('\\')?;
+        }
+
+        w.write_char(c)?;
+    }
+
+    Ok(())
+}
+
+/// Obtains the canonical (escaped) form of the literal `text`, borrowing
+/// `text` where it contains no special characters.
+pub(crate) fn escape_literal(text : &str) -> Cow<'_, str> {
+    if text.contains(LITERAL_SPECIAL_CHARACTERS) {
+        let mut escaped = String::with_capacity(text.len() + 4);
+
+        // writing to a `String` cannot fail
+        let _ = write_escaped_(&mut escaped, text, LITERAL_SPECIAL_CHARACTERS);
+
+        Cow::Owned(escaped)
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Obtains the text matched by the (entirely) literal `pattern`, resolving
+/// escapes as does the parser, or `None` if `pattern` contains an
+/// unescaped `?`, `*`, or `[`, or a trailing slash.
+pub(crate) fn unescape_literal(pattern : &str) -> Option<Cow<'_, str>> {
+    if !pattern.contains(['*', '?', '[', '\\']) {
+        return Some(Cow::Borrowed(pattern));
+    }
+
+    let mut text = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' | '?' | '[' => {
+                return None;
+            },
+            '\\' => {
+                let c = match chars.next()? {
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    c => c,
+                };
+
+                text.push(c);
+            },
+            _ => {
+                text.push(c);
+            },
+        };
+    }
+
+    Some(Cow::Owned(text))
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use crate::{
+        parse,
+        test_utils::TestRandom,
+        CompiledMatcher,
+        Error,
+        IGNORE_CASE,
+        PURE_BYTES,
+    };
+
+
+    /// Records each visit as a string.
+    #[derive(Default)]
+    struct Recorder {
+        visits : Vec<String>,
+    }
+
+    impl Visitor for Recorder {
+        fn visit_literal(
+            &mut self,
+            literal : &str,
+        ) {
+            self.visits.push(format!("literal({literal})"));
+        }
+
+        fn visit_wild1(&mut self) {
+            self.visits.push("wild1".into());
+        }
+
+        fn visit_wild_n(&mut self) {
+            self.visits.push("wildN".into());
+        }
+
+        fn visit_range(
+            &mut self,
+            chars : &[char],
+            negated : bool,
+        ) {
+            self.visits.push(format!(
+                "{}({})",
+                if negated { "not-range" } else { "range" },
+                String::from_iter(chars)
+            ));
+        }
+    }
+
+
+    #[test]
+    fn TEST_parse_ELEMENTS_1() {
+        let pattern = parse(r"[A-C]:\\?*.[^ch]", IGNORE_CASE).unwrap();
+
+        assert_eq!(IGNORE_CASE, pattern.flags());
+        assert_eq!(
+            &[
+                PatternElement::Range {
+                    chars :   vec!['A', 'B', 'C'],
+                    negated : false,
+                },
+                PatternElement::Literal(r":\".into()),
+                PatternElement::Wild1,
+                PatternElement::WildN,
+                PatternElement::Literal(".".into()),
+                PatternElement::Range {
+                    chars :   vec!['c', 'h'],
+                    negated : true,
+                },
+            ],
+            pattern.elements()
+        );
+    }
+
+    #[test]
+    fn TEST_parse_EMPTY_PATTERN_1() {
+        let pattern = parse("", 0).unwrap();
+
+        assert!(pattern.elements().is_empty());
+        assert!(pattern.compile().matches(""));
+        assert!(!pattern.compile().matches("a"));
+    }
+
+    #[test]
+    fn TEST_parse_INVALID_PATTERN_1() {
+        assert_eq!(
+            Err(Error::ParseError {
+                line :    0,
+                column :  3,
+                message : "incomplete range".into(),
+            }),
+            parse("[ab", 0)
+        );
+    }
+
+    #[test]
+    fn TEST_Pattern_accept_1() {
+        let pattern = parse(r"a\*[^b]?[cd]*", 0).unwrap();
+
+        let mut recorder = Recorder::default();
+
+        pattern.accept(&mut recorder);
+
+        assert_eq!(
+            vec!["literal(a*)", "not-range(b)", "wild1", "range(cd)", "wildN"],
+            recorder.visits
+        );
+    }
+
+    #[test]
+    fn TEST_Pattern_accept_DEFAULT_METHODS_1() {
+        struct Nothing;
+
+        impl Visitor for Nothing {
+        }
+
+        parse("a?[b]*", 0).unwrap().accept(&mut Nothing);
+    }
+
+    #[test]
+    fn TEST_Pattern_compile_1() {
+        let pattern = parse("*.[ch]", IGNORE_CASE).unwrap();
+
+        let matcher = pattern.compile();
+
+        assert!(matcher.matches("lib.c"));
+        assert!(matcher.matches("LIB.H"));
+        assert!(!matcher.matches("lib.rs"));
+
+        let matcher = CompiledMatcher::from(pattern.clone());
+
+        assert!(matcher.matches("LIB.H"));
+        assert_eq!(pattern.clone().into_elements(), pattern.elements());
+    }
+
+    #[test]
+    fn TEST_Pattern_compile_AGREES_WITH_from_pattern_and_flags_1() {
+        let patterns = ["", "abc", "a*c", "[A-Z]:\\\\?*.[ce][ox][em]", "*🐻[!?]", "[^a-c]?"];
+        let inputs = ["", "abc", "ABC", "abbbbc", r"C:\dir\file.exe", "teddy-🐻!", "d!"];
+
+        for pattern in patterns {
+            for flags in [0, IGNORE_CASE] {
+                let compiled = parse(pattern, flags).unwrap().compile();
+                let matcher = CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                for input in inputs {
+                    assert_eq!(matcher.matches(input), compiled.matches(input));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn TEST_PatternBuilder_1() {
+        let builder = PatternBuilder::new()
+            .literal("IMG_")
+            .wild_n()
+            .literal(".")
+            .not_range(['g', 'p'])
+            .wild1()
+            .range("eg".chars());
+
+        assert_eq!("IMG_*.[^gp]?[eg]", builder.to_pattern_string());
+
+        let matcher = builder.compile();
+
+        assert!(matcher.matches("IMG_1234.jpe"));
+        assert!(!matcher.matches("IMG_1234.gif"));
+        assert_eq!(parse("IMG_*.[^gp]?[eg]", 0).unwrap(), builder.build());
+    }
+
+    #[test]
+    fn TEST_PatternBuilder_ESCAPING_1() {
+        let builder = PatternBuilder::new()
+            .literal(r"*?[]\^-")
+            .range(['^', '-', ']', '\\', 'a']);
+
+        assert_eq!(r"\*\?\[\]\\^-[\^\-\]\\a]", builder.to_pattern_string());
+
+        let matcher = builder.compile();
+
+        assert!(matcher.matches(r"*?[]\^-^"));
+        assert!(matcher.matches(r"*?[]\^--"));
+        assert!(matcher.matches(r"*?[]\^-\"));
+        assert!(!matcher.matches(r"*?[]\^-b"));
+        assert!(!matcher.matches(r"x?[]\^-a"));
+    }
+
+    #[test]
+    fn TEST_PatternBuilder_LITERALS_ARE_MERGED_1() {
+        let builder = PatternBuilder::new()
+            .literal("ab")
+            .literal("")
+            .literal("cd")
+            .wild1()
+            .literal("e");
+
+        assert_eq!(
+            &[
+                PatternElement::Literal("abcd".into()),
+                PatternElement::Wild1,
+                PatternElement::Literal("e".into()),
+            ],
+            builder.build().elements()
+        );
+    }
+
+    #[test]
+    fn TEST_PatternBuilder_SUB_PATTERNS_1() {
+        let sub_pattern = parse("c*[de]", IGNORE_CASE).unwrap();
+
+        let builder = PatternBuilder::new().literal("ab").pattern(&sub_pattern).literal("f");
+
+        assert_eq!("abc*[de]f", builder.to_pattern_string());
+        assert_eq!(0, builder.build().flags());
+        assert_eq!(PatternElement::Literal("abc".into()), builder.build().elements()[0]);
+
+        let builder = builder.with_flags(IGNORE_CASE);
+
+        assert_eq!(IGNORE_CASE, builder.build().flags());
+        assert!(builder.compile().matches("ABC-DF"));
+    }
+
+    #[test]
+    fn TEST_PatternBuilder_EMPTY_RANGES_1() {
+        let builder = PatternBuilder::new().range([]).not_range([]);
+
+        assert_eq!("[][^]", builder.to_pattern_string());
+        assert_eq!(parse("[][^]", 0).unwrap(), builder.build());
+    }
+
+    #[test]
+    fn TEST_PatternBuilder_ROUND_TRIP_1() {
+        let mut rng = TestRandom::new(1);
+
+        let alphabet = ['a', 'n', 't', '*', '?', '[', ']', '\\', '^', '-', '\n', 'é', '🐻'];
+
+        for _ in 0..1_000 {
+            let builder = rng.pattern_builder(&alphabet, 8, 4);
+
+            let pattern_string = builder.to_pattern_string();
+
+            assert_eq!(
+                Ok(builder.build()),
+                parse(&pattern_string, 0),
+                "pattern string '{pattern_string}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_escape_1() {
+        assert!(std_matches!(crate::escape("abc.txt"), Cow::Borrowed("abc.txt")));
+        assert!(std_matches!(crate::escape(""), Cow::Borrowed("")));
+        assert!(std_matches!(crate::escape("a^b-c"), Cow::Borrowed("a^b-c")));
+
+        assert_eq!(r"\*\?\[\]\\", crate::escape(r"*?[]\"));
+        assert_eq!(r"report\[final\]\*.txt", crate::escape("report[final]*.txt"));
+    }
+
+    #[test]
+    fn TEST_unescape_1() {
+        assert!(std_matches!(crate::unescape("abc.txt"), Some(Cow::Borrowed("abc.txt"))));
+        assert!(std_matches!(crate::unescape("a]^-b"), Some(Cow::Borrowed("a]^-b"))));
+
+        assert_eq!(Some(r"*?[]\".into()), crate::unescape(r"\*\?\[\]\\"));
+        assert_eq!(Some("a\tb\nc\rd".into()), crate::unescape(r"a\tb\nc\rd"));
+        assert_eq!(Some("abc".into()), crate::unescape(r"\a\b\c"));
+
+        assert_eq!(None, crate::unescape("a*"));
+        assert_eq!(None, crate::unescape("a?"));
+        assert_eq!(None, crate::unescape("[a]"));
+        assert_eq!(None, crate::unescape(r"a\"));
+    }
+
+    #[test]
+    fn TEST_unescape_AGREES_WITH_parse_1() {
+        let patterns = ["", "abc", r"a\*b", r"\n\\", "a]b", "a^", "a-b", "a*", "[ab]", r"a\"];
+
+        for pattern in patterns {
+            let literal = match parse(pattern, 0) {
+                Ok(pattern) => {
+                    match pattern.elements() {
+                        [] => Some(String::new()),
+                        [PatternElement::Literal(literal)] => Some(literal.clone()),
+                        _ => None,
+                    }
+                },
+                Err(_) => None,
+            };
+
+            assert_eq!(
+                literal.as_deref(),
+                crate::unescape(pattern).as_deref(),
+                "pattern '{pattern}'"
+            );
+        }
+    }
+
+    #[test]
+    fn TEST_escape_ROUND_TRIP_1() {
+        let mut rng = TestRandom::new(2);
+
+        let alphabet = [
+            'a',
+            'B',
+            'n',
+            't',
+            '*',
+            '?',
+            '[',
+            ']',
+            '\\',
+            '^',
+            '-',
+            '\n',
+            '\t',
+            ' ',
+            'é',
+            '🐻',
+            '\u{301}',
+        ];
+
+        let all_flags = [
+            0,
+            IGNORE_CASE,
+            PURE_BYTES,
+            #[cfg(feature = "grapheme-clusters")]
+            crate::GRAPHEME_CLUSTERS,
+        ];
+
+        for _ in 0..1_000 {
+            let text = rng.string(&alphabet, 12);
+
+            let pattern = crate::escape(&text);
+
+            for &flags in &all_flags {
+                assert_eq!(
+                    Ok(true),
+                    crate::matches(&pattern, &text, flags),
+                    "text '{text}', pattern '{pattern}', flags {flags:#x}"
+                );
+            }
+
+            assert_eq!(Some(text.as_str()), crate::unescape(&pattern).as_deref());
+        }
+    }
+
+    #[test]
+    fn TEST_CompiledMatcher_Display_1() {
+        let normalised = |pattern : &str| CompiledMatcher::from_pattern_and_flags(pattern, 0).unwrap().to_string();
+
+        assert_eq!("", normalised(""));
+        assert_eq!("abc", normalised(r"a\bc"));
+        assert_eq!(r"a\*b\?c\[d\]e\\f^g-", normalised(r"a\*b\?c\[d]e\\f^g-"));
+        assert_eq!("*", normalised("***"));
+        assert_eq!("??*", normalised("*?**?*"));
+        assert_eq!("a??b", normalised("a??b"));
+        assert_eq!("[Za-c]", normalised("[cbaZa]"));
+        assert_eq!("[^A-Ca-c]", normalised("[^a-C]"));
+        assert_eq!("[ab]", normalised("[ba]"));
+        assert_eq!(r"[\-\]\^az]", normalised(r"[z\]a\^-]"));
+        assert_eq!("[Zxy]", normalised("[xyZ]"));
+        assert_eq!("[]", normalised("[]"));
+        assert_eq!("[^🐻🐼]", normalised("[^🐼🐻🐼]"));
+    }
+
+    #[test]
+    fn TEST_CompiledMatcher_Display_IS_EQUIVALENT_AND_IDEMPOTENT_1() {
+        let mut rng = TestRandom::new(3);
+
+        let alphabet = ['a', 'b', 'c', 'd', 'B', 'C', '-', '^', ']', '\\', '*', '?'];
+
+        for _ in 0..500 {
+            let builder = rng.pattern_builder(&alphabet, 6, 5);
+
+            let matcher = builder.compile();
+            let normalised = matcher.to_string();
+            let renormalised = CompiledMatcher::from_pattern_and_flags(&normalised, 0).unwrap();
+
+            assert_eq!(normalised, renormalised.to_string());
+
+            for _ in 0..20 {
+                let input = rng.string(&alphabet, 7);
+
+                assert_eq!(
+                    matcher.matches(&input),
+                    renormalised.matches(&input),
+                    "pattern '{}', normalised '{normalised}', input '{input}'",
+                    builder.to_pattern_string()
+                );
+            }
+        }
+    }
+
+    #[cfg(feature = "grapheme-clusters")]
+    #[test]
+    fn TEST_CompiledMatcher_Display_HAVING__GRAPHEME_CLUSTERS__1() {
+        use crate::GRAPHEME_CLUSTERS;
+
+        let matcher = CompiledMatcher::from_pattern_and_flags("[e\u{301}x]", GRAPHEME_CLUSTERS).unwrap();
+        let normalised = matcher.to_string();
+        let renormalised = CompiledMatcher::from_pattern_and_flags(&normalised, GRAPHEME_CLUSTERS).unwrap();
+
+        assert_eq!("[e\u{301}x]", normalised);
+
+        for input in ["e\u{301}", "x", "x\u{301}", "e"] {
+            assert_eq!(matcher.matches(input), renormalised.matches(input), "input '{input}'");
+        }
+
+        let mut rng = TestRandom::new(6);
+
+        let alphabet = ['a', 'e', 'x', '\u{301}', '\u{200d}', '🐻', '-', '^'];
+
+        for _ in 0..500 {
+            let matcher = rng
+                .pattern_builder(&alphabet, 5, 5)
+                .with_flags(GRAPHEME_CLUSTERS)
+                .compile();
+            let normalised = matcher.to_string();
+            let renormalised = CompiledMatcher::from_pattern_and_flags(&normalised, GRAPHEME_CLUSTERS).unwrap();
+
+            for _ in 0..20 {
+                let input = rng.string(&alphabet, 6);
+
+                assert_eq!(
+                    matcher.matches(&input),
+                    renormalised.matches(&input),
+                    "normalised '{normalised}', input '{input}'"
+                );
+            }
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,484 @@
+// src/pattern_set.rs : matching of one input against many patterns at once
+
+// ///////////////////////////////////////////////
+// imports
+
+use crate::{
+    constants,
+    CompiledMatcher,
+    Result,
+};
+
+use std::collections::VecDeque;
+
+
+// ///////////////////////////////////////////////
+// PatternSet
+
+/// A set of patterns against which an input may be matched at once,
+/// obtaining the indexes of all the patterns that match it
+/// ([`matches_all()`](Self::matches_all)) or of the first
+/// ([`first_match()`](Self::first_match)).
+///
+/// The input is first scanned, in a single pass, for the longest required
+/// literal of each pattern (see
+/// [`CompiledMatcher::required_literals()`]), and only those patterns
+/// whose literal occurs (and those that have none) are evaluated, such
+/// that the cost is largely independent of the number of patterns.
+///
+/// # Examples:
+///
+/// ```
+/// use shwild::PatternSet;
+///
+/// let set = PatternSet::from_patterns_and_flags(
+///     ["*.rs", "src/*", "*.md", "*/lib.rs"],
+///     0,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(vec![0, 1, 3], set.matches_all("src/lib.rs"));
+/// assert_eq!(Some(2), set.first_match("README.md"));
+/// assert_eq!(None, set.first_match("Cargo.toml"));
+/// ```
+#[derive(Debug)]
+pub struct PatternSet {
+    matchers :            Vec<CompiledMatcher>,
+    /// The prefilter for patterns that are case-sensitive.
+    prefilter :           Prefilter,
+    /// The prefilter for patterns that ignore case, whose literals are
+    /// upper-cased, and against which the upper-cased input is scanned.
+    prefilter_uppercase : Prefilter,
+    /// The patterns that have no required literal, which are always
+    /// evaluated.
+    unfiltered :          Vec<usize>,
+}
+
+// API functions
+impl PatternSet {
+    /// Creates an instance from the given `patterns`, each parsed
+    /// according to `flags`.
+    ///
+    /// # Returns:
+    /// The set, or the error of the first pattern that is invalid.
+    pub fn from_patterns_and_flags<I, S>(
+        patterns : I,
+        flags : i64,
+    ) -> Result<Self>
+    where
+        I : IntoIterator<Item = S>,
+        S : AsRef<str>,
+    {
+        let matchers = patterns
+            .into_iter()
+            .map(|pattern| CompiledMatcher::from_pattern_and_flags(pattern.as_ref(), flags))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::from_matchers(matchers))
+    }
+
+    /// Creates an instance from the given `matchers`, each of which may
+    /// have different flags.
+    pub fn from_matchers<I>(matchers : I) -> Self
+    where
+        I : IntoIterator<Item = CompiledMatcher>,
+    {
+        let matchers : Vec<CompiledMatcher> = matchers.into_iter().collect();
+
+        let mut literals = vec![];
+        let mut literals_uppercase = vec![];
+        let mut unfiltered = vec![];
+
+        for (index, matcher) in matchers.iter().enumerate() {
+            let literal = matcher
+                .required_literals()
+                .into_iter()
+                .max_by_key(|literal| literal.len());
+
+            match literal {
+                Some(literal) if 0 != (matcher.flags & constants::IGNORE_CASE) => {
+                    literals_uppercase.push((literal.to_uppercase(), index));
+                },
+                Some(literal) => {
+                    literals.push((literal.to_string(), index));
+                },
+                None => {
+                    unfiltered.push(index);
+                },
+            };
+        }
+
+        Self {
+            matchers,
+            prefilter : Prefilter::new(literals),
+            prefilter_uppercase : Prefilter::new(literals_uppercase),
+            unfiltered,
+        }
+    }
+}
+
+// Non-mutating methods
+impl PatternSet {
+    /// The number of patterns.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.matchers.len()
+    }
+
+    /// Indicates whether there are no patterns.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.matchers.is_empty()
+    }
+
+    /// The matchers, in the order of their indexes.
+    #[inline]
+    pub fn matchers(&self) -> &[CompiledMatcher] {
+        &self.matchers
+    }
+
+    /// Obtains the (ascending) indexes of all the patterns that match
+    /// `input`.
+    pub fn matches_all(
+        &self,
+        input : &str,
+    ) -> Vec<usize> {
+        self.candidates_(input)
+            .into_iter()
+            .filter(|&index| self.matchers[index].matches(input))
+            .collect()
+    }
+
+    /// Obtains the index of the first (i.e. lowest-indexed) pattern that
+    /// matches `input`, or `None` if none does.
+    pub fn first_match(
+        &self,
+        input : &str,
+    ) -> Option<usize> {
+        self.candidates_(input)
+            .into_iter()
+            .find(|&index| self.matchers[index].matches(input))
+    }
+
+    /// Indicates whether any of the patterns matches `input`.
+    pub fn is_match(
+        &self,
+        input : &str,
+    ) -> bool {
+        self.first_match(input).is_some()
+    }
+}
+
+// Implementation
+impl PatternSet {
+    /// The (ascending) indexes of the patterns that may match `input`.
+    fn candidates_(
+        &self,
+        input : &str,
+    ) -> Vec<usize> {
+        let mut candidates = self.unfiltered.clone();
+
+        self.prefilter.scan(input.as_bytes(), &mut candidates);
+
+        if !self.prefilter_uppercase.is_empty() {
+            self.prefilter_uppercase
+                .scan(input.to_uppercase().as_bytes(), &mut candidates);
+        }
+
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        candidates
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// Prefilter
+
+/// An Aho–Corasick automaton over the bytes of a number of literals, each
+/// associated with the index of a pattern.
+#[derive(Debug)]
+struct Prefilter {
+    nodes : Vec<PrefilterNode>,
+}
+
+#[derive(Debug)]
+#[derive(Default)]
+struct PrefilterNode {
+    /// The (sorted) transitions to child nodes.
+    children : Vec<(u8, u32)>,
+    /// The node of the longest proper suffix that is in the trie.
+    failure :  u32,
+    /// The pattern indexes of the literals that end at this node.
+    outputs :  Vec<usize>,
+    /// The nearest node on the failure chain that has outputs, if any.
+    output :   Option<u32>,
+}
+
+// API functions
+impl Prefilter {
+    fn new(literals : Vec<(String, usize)>) -> Self {
+        let mut nodes = vec![PrefilterNode::default()];
+
+        for (literal, index) in literals {
+            let mut node = 0;
+
+            for &b in literal.as_bytes() {
+                node = match nodes[node].children.binary_search_by_key(&b, |&(b, _)| b) {
+                    Ok(i) => nodes[node].children[i].1 as usize,
+                    Err(i) => {
+                        let child = nodes.len();
+
+                        nodes.push(PrefilterNode::default());
+                        nodes[node].children.insert(i, (b, child as u32));
+
+                        child
+                    },
+                };
+            }
+
+            nodes[node].outputs.push(index);
+        }
+
+        // breadth-first, so that each failure node is complete before use
+        let mut queue : VecDeque<usize> = nodes[0].children.iter().map(|&(_, child)| child as usize).collect();
+
+        while let Some(node) = queue.pop_front() {
+            for i in 0..nodes[node].children.len() {
+                let (b, child) = nodes[node].children[i];
+                let child = child as usize;
+
+                let mut failure = nodes[node].failure as usize;
+
+                let child_failure = loop {
+                    if let Some(next) = Self::child_(&nodes, failure, b) {
+                        break next;
+                    }
+
+                    if 0 == failure {
+                        break 0;
+                    }
+
+                    failure = nodes[failure].failure as usize;
+                };
+
+                nodes[child].failure = child_failure as u32;
+                nodes[child].output = if nodes[child_failure].outputs.is_empty() {
+                    nodes[child_failure].output
+                } else {
+                    Some(child_failure as u32)
+                };
+
+                queue.push_back(child);
+            }
+        }
+
+        Self {
+            nodes,
+        }
+    }
+}
+
+// Non-mutating methods
+impl Prefilter {
+    fn is_empty(&self) -> bool {
+        self.nodes[0].children.is_empty()
+    }
+
+    /// Adds to `indexes` the pattern index of each literal that occurs in
+    /// `haystack` (in no particular order, and possibly repeatedly).
+    fn scan(
+        &self,
+        haystack : &[u8],
+        indexes : &mut Vec<usize>,
+    ) {
+        if self.is_empty() {
+            return;
+        }
+
+        let mut node = 0;
+
+        for &b in haystack {
+            node = loop {
+                if let Some(next) = Self::child_(&self.nodes, node, b) {
+                    break next;
+                }
+
+                if 0 == node {
+                    break 0;
+                }
+
+                node = self.nodes[node].failure as usize;
+            };
+
+            indexes.extend_from_slice(&self.nodes[node].outputs);
+
+            let mut output = self.nodes[node].output;
+
+            while let Some(o) = output {
+                indexes.extend_from_slice(&self.nodes[o as usize].outputs);
+
+                output = self.nodes[o as usize].output;
+            }
+        }
+    }
+}
+
+// Implementation
+impl Prefilter {
+    #[inline]
+    fn child_(
+        nodes : &[PrefilterNode],
+        node : usize,
+        b : u8,
+    ) -> Option<usize> {
+        nodes[node]
+            .children
+            .binary_search_by_key(&b, |&(b, _)| b)
+            .ok()
+            .map(|i| nodes[node].children[i].1 as usize)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use crate::IGNORE_CASE;
+
+
+    #[test]
+    fn TEST_Prefilter_1() {
+        let prefilter = Prefilter::new(vec![
+            ("he".into(), 0),
+            ("she".into(), 1),
+            ("his".into(), 2),
+            ("hers".into(), 3),
+            ("e".into(), 4),
+        ]);
+
+        let scan = |haystack : &str| {
+            let mut indexes = vec![];
+
+            prefilter.scan(haystack.as_bytes(), &mut indexes);
+
+            indexes.sort_unstable();
+            indexes.dedup();
+
+            indexes
+        };
+
+        assert_eq!(vec![0, 1, 3, 4], scan("ushers"));
+        assert_eq!(vec![2], scan("this"));
+        assert_eq!(Vec::<usize>::new(), scan("abc"));
+        assert_eq!(Vec::<usize>::new(), scan(""));
+        assert_eq!(vec![4], scan("e"));
+    }
+
+    #[test]
+    fn TEST_PatternSet_1() {
+        let set = PatternSet::from_patterns_and_flags(["*.rs", "src/*", "*.md", "*/lib.rs", "*", "?"], 0).unwrap();
+
+        assert_eq!(6, set.len());
+        assert!(!set.is_empty());
+        assert_eq!(vec![0, 1, 3, 4], set.matches_all("src/lib.rs"));
+        assert_eq!(Some(0), set.first_match("src/lib.rs"));
+        assert_eq!(Some(2), set.first_match("README.md"));
+        assert_eq!(Some(4), set.first_match("Cargo.toml"));
+        assert_eq!(vec![4, 5], set.matches_all("x"));
+        assert!(set.is_match(""));
+    }
+
+    #[test]
+    fn TEST_PatternSet_EMPTY_1() {
+        let set = PatternSet::from_patterns_and_flags(Vec::<&str>::new(), 0).unwrap();
+
+        assert!(set.is_empty());
+        assert!(set.matches_all("abc").is_empty());
+        assert_eq!(None, set.first_match("abc"));
+        assert!(!set.is_match("abc"));
+    }
+
+    #[test]
+    fn TEST_PatternSet_INVALID_PATTERN_1() {
+        assert!(PatternSet::from_patterns_and_flags(["*.rs", "[0-9]"], 0).is_err());
+    }
+
+    #[test]
+    fn TEST_PatternSet_MIXED_FLAGS_1() {
+        let set = PatternSet::from_matchers([
+            CompiledMatcher::from_pattern_and_flags("*.LOG", IGNORE_CASE).unwrap(),
+            CompiledMatcher::from_pattern_and_flags("*.log", 0).unwrap(),
+            CompiledMatcher::from_pattern_and_flags("*ÉTÉ*", IGNORE_CASE).unwrap(),
+        ]);
+
+        assert_eq!(vec![0, 1], set.matches_all("app.log"));
+        assert_eq!(vec![0], set.matches_all("APP.Log"));
+        assert_eq!(vec![2], set.matches_all("l'été"));
+        assert!(set.matches_all("app.txt").is_empty());
+    }
+
+    #[test]
+    fn TEST_PatternSet_AGREES_WITH_CompiledMatcher_1() {
+        let patterns = [
+            "*.rs",
+            "*.RS",
+            "src/*",
+            "*/lib.rs",
+            "*a*b*",
+            "a?c",
+            "[ab]*",
+            "*[^x]",
+            "*.tar.gz",
+            "*mod*",
+            "",
+            "*",
+            "?*",
+            "README*",
+            "*é*",
+            "*🐻",
+        ];
+        let inputs = [
+            "",
+            "a",
+            "abc",
+            "src/lib.rs",
+            "src/MOD.RS",
+            "x.tar.gz",
+            "README.md",
+            "éa",
+            "🐻",
+            "xx",
+            "b/a/b",
+            "ac",
+        ];
+
+        for flags in [0, IGNORE_CASE] {
+            let set = PatternSet::from_patterns_and_flags(patterns, flags).unwrap();
+
+            for input in inputs {
+                let expected : Vec<usize> = set
+                    .matchers()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, matcher)| matcher.matches(input))
+                    .map(|(index, _)| index)
+                    .collect();
+
+                assert_eq!(expected, set.matches_all(input), "input '{input}', flags {flags:#x}");
+                assert_eq!(
+                    expected.first().copied(),
+                    set.first_match(input),
+                    "input '{input}', flags {flags:#x}"
+                );
+            }
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
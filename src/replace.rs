@@ -0,0 +1,591 @@
+// src/replace.rs : substitution of wildcard captures into templates, and
+//                  batch rename plans
+
+// ///////////////////////////////////////////////
+// imports
+
+use crate::{
+    Error,
+    Result,
+};
+
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+
+// ///////////////////////////////////////////////
+// Template
+
+/// A part of a parsed template.
+#[derive(Debug)]
+enum TemplatePart {
+    Literal(String),
+    /// A (0-based) capture index.
+    Capture(usize),
+}
+
+/// A parsed substitution template, such as `"photo-#1.#2"`, in which:
+/// - `#N` or `$N` refers to the `N`th (1-based) wildcard capture;
+/// - `#{N}` or `${N}` does likewise, where the reference must be followed
+///   by a digit;
+/// - `\` escapes the following character;
+#[derive(Debug)]
+pub(crate) struct Template {
+    parts : Vec<TemplatePart>,
+}
+
+// API functions
+impl Template {
+    /// Parses `template`, verifying that each reference is to one of the
+    /// `num_captures` captures.
+    pub(crate) fn parse(
+        template : &str,
+        num_captures : usize,
+    ) -> Result<Self> {
+        let mut parts = vec![];
+        let mut literal = String::new();
+
+        let mut line = 0;
+        let mut column = 0;
+
+        let mut chars = template.chars().peekable();
+
+        let error = |line, column, message : String| {
+            Error::TemplateError {
+                line,
+                column,
+                message,
+            }
+        };
+
+        while let Some(c) = chars.next() {
+            let (reference_line, reference_column) = (line, column);
+
+            column += 1;
+
+            match c {
+                '\\' => {
+                    match chars.next() {
+                        Some(c) => {
+                            literal.push(c);
+
+                            column += 1;
+                        },
+                        None => return Err(error(line, column, "trailing slash".into())),
+                    };
+                },
+                '#' | '$' => {
+                    let braced = chars.next_if_eq(&'{').is_some();
+
+                    if braced {
+                        column += 1;
+                    }
+
+                    let mut digits = String::new();
+
+                    while let Some(d) = chars.next_if(char::is_ascii_digit) {
+                        digits.push(d);
+
+                        column += 1;
+                    }
+
+                    if digits.is_empty() {
+                        return Err(error(
+                            reference_line,
+                            reference_column,
+                            format!("expected capture number after '{c}'"),
+                        ));
+                    }
+
+                    if braced {
+                        if chars.next_if_eq(&'}').is_none() {
+                            return Err(error(line, column, "expected '}' after capture number".into()));
+                        }
+
+                        column += 1;
+                    }
+
+                    let number = digits.parse::<usize>().unwrap_or(usize::MAX);
+
+                    if 0 == number {
+                        return Err(error(
+                            reference_line,
+                            reference_column,
+                            "capture numbers begin at 1".into(),
+                        ));
+                    }
+
+                    if number > num_captures {
+                        return Err(error(
+                            reference_line,
+                            reference_column,
+                            format!("capture {number} does not exist, as the pattern has {num_captures} wildcard(s)"),
+                        ));
+                    }
+
+                    if !literal.is_empty() {
+                        parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                    }
+
+                    parts.push(TemplatePart::Capture(number - 1));
+                },
+                _ => {
+                    literal.push(c);
+
+                    if '\n' == c {
+                        line += 1;
+                        column = 0;
+                    }
+                },
+            };
+        }
+
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(literal));
+        }
+
+        Ok(Self {
+            parts,
+        })
+    }
+}
+
+// Non-mutating methods
+impl Template {
+    /// Expands the template with the given `captures`, which must be at
+    /// least as many as were specified to [`parse()`](Self::parse).
+    pub(crate) fn expand<'i, F>(
+        &self,
+        captures : F,
+    ) -> String
+    where
+        F : Fn(usize) -> &'i str,
+    {
+        let mut r = String::new();
+
+        for part in &self.parts {
+            match part {
+                TemplatePart::Literal(literal) => r.push_str(literal),
+                TemplatePart::Capture(index) => r.push_str(captures(*index)),
+            };
+        }
+
+        r
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// RenamePlan
+
+/// A single rename within a [`RenamePlan`].
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Eq, PartialEq)]
+pub struct Rename {
+    /// The original name.
+    pub from : String,
+    /// The new name.
+    pub to :   String,
+}
+
+/// A collision identified by [`CompiledMatcher::rename_plan()`].
+///
+/// [`CompiledMatcher::rename_plan()`]: crate::CompiledMatcher::rename_plan
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Eq, PartialEq)]
+pub enum Collision {
+    /// Two or more names would be renamed to the same `target`.
+    SameTarget {
+        target :  String,
+        /// The names that would be renamed to `target`, in the order given.
+        sources : Vec<String>,
+    },
+    /// A name, `source`, would be renamed to `target`, which is one of the
+    /// given names that is not itself renamed.
+    ExistingTarget { target : String, source : String },
+    /// The names would each be renamed to the next (and the last to the
+    /// first), such as `"a"` to `"b"` and `"b"` to `"a"`, which cannot be
+    /// carried out by renames alone.
+    Cycle { names : Vec<String> },
+}
+
+/// The renames obtained by applying a pattern and template to a list of
+/// names, as obtained from [`CompiledMatcher::rename_plan()`], along with
+/// any collisions between them.
+///
+/// [`CompiledMatcher::rename_plan()`]: crate::CompiledMatcher::rename_plan
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Eq, PartialEq)]
+pub struct RenamePlan {
+    renames :    Vec<Rename>,
+    collisions : Vec<Collision>,
+}
+
+// API functions
+impl RenamePlan {
+    /// Creates a plan from the given `renames` (which exclude any names
+    /// that would be unchanged), identifying the collisions between them
+    /// and the (all) given `names`, and ordering them such that each may
+    /// be carried out once those that rename its target have been.
+    pub(crate) fn new(
+        names : &[&str],
+        renames : Vec<Rename>,
+    ) -> Self {
+        let names : HashSet<&str> = names.iter().copied().collect();
+
+        let mut index_by_source : HashMap<&str, usize> = HashMap::new();
+        let mut sources_by_target : HashMap<&str, Vec<&str>> = HashMap::new();
+        let mut targets = vec![];
+
+        for (index, rename) in renames.iter().enumerate() {
+            index_by_source.entry(rename.from.as_str()).or_insert(index);
+
+            let sources = sources_by_target.entry(rename.to.as_str()).or_default();
+
+            if sources.is_empty() {
+                targets.push(rename.to.as_str());
+            }
+
+            sources.push(rename.from.as_str());
+        }
+
+        let mut collisions = vec![];
+
+        for target in targets {
+            let sources = &sources_by_target[target];
+
+            if sources.len() > 1 {
+                collisions.push(Collision::SameTarget {
+                    target :  target.into(),
+                    sources : sources.iter().map(|&source| source.into()).collect(),
+                });
+            } else if names.contains(target) && !index_by_source.contains_key(target) {
+                collisions.push(Collision::ExistingTarget {
+                    target : target.into(),
+                    source : sources[0].into(),
+                });
+            }
+        }
+
+        // each rename depends on (at most) the rename of its target, so the
+        // dependencies form chains, which are followed (from each rename
+        // in turn) and carried out from their ends, and cycles
+
+        #[derive(Clone)]
+        #[derive(Copy)]
+        #[derive(Eq, PartialEq)]
+        enum State {
+            Unvisited,
+            OnChain,
+            Ordered,
+        }
+
+        let mut states = vec![State::Unvisited; renames.len()];
+        let mut order = Vec::with_capacity(renames.len());
+
+        for first in 0..renames.len() {
+            let mut chain = vec![];
+            let mut index = first;
+
+            let cycle_start = loop {
+                match states[index] {
+                    State::Unvisited => {},
+                    State::OnChain => break Some(index),
+                    State::Ordered => break None,
+                };
+
+                states[index] = State::OnChain;
+                chain.push(index);
+
+                match index_by_source.get(renames[index].to.as_str()) {
+                    Some(&next) => index = next,
+                    None => break None,
+                };
+            };
+
+            if let Some(cycle_start) = cycle_start {
+                let position = chain.iter().position(|&i| i == cycle_start).unwrap_or_default();
+
+                collisions.push(Collision::Cycle {
+                    names : chain[position..].iter().map(|&i| renames[i].from.clone()).collect(),
+                });
+            }
+
+            for &index in chain.iter().rev() {
+                states[index] = State::Ordered;
+                order.push(index);
+            }
+        }
+
+        let mut renames : Vec<Option<Rename>> = renames.into_iter().map(Some).collect();
+
+        Self {
+            renames : order.into_iter().filter_map(|index| renames[index].take()).collect(),
+            collisions,
+        }
+    }
+}
+
+// Non-mutating methods
+impl RenamePlan {
+    /// The renames, in an order in which they may be carried out, such that
+    /// a name is renamed before another name is renamed to it (and
+    /// otherwise in the order in which the names were given). Names that
+    /// do not match the pattern, or that would be unchanged, are omitted.
+    #[inline]
+    pub fn renames(&self) -> &[Rename] {
+        &self.renames
+    }
+
+    /// The collisions: those of the same and of existing targets, in the
+    /// order in which their targets first occur in the names given; and
+    /// then any cycles.
+    #[inline]
+    pub fn collisions(&self) -> &[Collision] {
+        &self.collisions
+    }
+
+    /// Indicates whether there are any collisions, in which case the plan
+    /// should not be carried out.
+    #[inline]
+    pub fn has_collisions(&self) -> bool {
+        !self.collisions.is_empty()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use crate::CompiledMatcher;
+
+
+    fn replace(
+        pattern : &str,
+        input : &str,
+        template : &str,
+    ) -> Result<Option<String>> {
+        CompiledMatcher::from_pattern_and_flags(pattern, 0)
+            .unwrap()
+            .replace(input, template)
+    }
+
+    fn rename(
+        from : &str,
+        to : &str,
+    ) -> Rename {
+        Rename {
+            from : from.into(),
+            to :   to.into(),
+        }
+    }
+
+
+    #[test]
+    fn TEST_replace_1() {
+        assert_eq!(
+            Ok(Some("photo-1234.jpg".into())),
+            replace("IMG_*.jpeg", "IMG_1234.jpeg", "photo-#1.jpg")
+        );
+        assert_eq!(
+            Ok(Some("photo-1234.jpg".into())),
+            replace("IMG_*.jpeg", "IMG_1234.jpeg", "photo-$1.jpg")
+        );
+        assert_eq!(Ok(None), replace("IMG_*.jpeg", "IMG_1234.png", "photo-#1.jpg"));
+        assert_eq!(Ok(Some("c.b-a".into())), replace("*-*.?", "a-b.c", "#3.#2-#1"));
+        assert_eq!(Ok(Some("a-a".into())), replace("*", "a", "#1-$1"));
+        assert_eq!(Ok(Some("fixed".into())), replace("*", "a", "fixed"));
+        assert_eq!(Ok(Some("".into())), replace("*", "a", ""));
+    }
+
+    #[test]
+    fn TEST_replace_BRACES_AND_ESCAPES_1() {
+        assert_eq!(Ok(Some("a0".into())), replace("*", "a", "#{1}0"));
+        assert_eq!(Ok(Some("a0".into())), replace("*", "a", "${1}0"));
+        assert_eq!(Ok(Some("#1 costs $1".into())), replace("*", "a", r"\#1 costs \$1"));
+        assert_eq!(Ok(Some(r"a\b".into())), replace("*\\\\*", r"a\b", r"#1\\#2"));
+    }
+
+    #[test]
+    fn TEST_replace_MULTI_DIGIT_CAPTURE_NUMBERS_1() {
+        let pattern = "???????????";
+
+        assert_eq!(Ok(Some("k-a0".into())), replace(pattern, "abcdefghijk", "#11-#{1}0"));
+    }
+
+    #[test]
+    fn TEST_replace_INVALID_TEMPLATE_1() {
+        let error = |column, message : &str| {
+            Err(Error::TemplateError {
+                line : 0,
+                column,
+                message : message.into(),
+            })
+        };
+
+        assert_eq!(
+            error(6, "capture 2 does not exist, as the pattern has 1 wildcard(s)"),
+            replace("IMG_*.jpeg", "IMG_1.jpeg", "photo-#2.jpg")
+        );
+        assert_eq!(error(1, "expected capture number after '$'"), replace("*", "a", "x$y"));
+        assert_eq!(error(0, "capture numbers begin at 1"), replace("*", "a", "#0"));
+        assert_eq!(error(3, "expected '}' after capture number"), replace("*", "a", "#{1"));
+        assert_eq!(error(3, "trailing slash"), replace("*", "a", "#1\\"));
+
+        // the template is verified even when the input does not match
+        assert!(replace("*.c", "a.h", "#2").is_err());
+
+        assert_eq!(
+            "template syntax error (at 0:1): expected capture number after '$'",
+            replace("*", "a", "x$y").unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn TEST_rename_plan_1() {
+        let matcher = CompiledMatcher::from_pattern_and_flags("IMG_*.jpeg", 0).unwrap();
+
+        let plan = matcher
+            .rename_plan(["IMG_1.jpeg", "notes.txt", "IMG_2.jpeg"], "photo-#1.jpg")
+            .unwrap();
+
+        assert_eq!(
+            &[rename("IMG_1.jpeg", "photo-1.jpg"), rename("IMG_2.jpeg", "photo-2.jpg")],
+            plan.renames()
+        );
+        assert!(plan.collisions().is_empty());
+        assert!(!plan.has_collisions());
+    }
+
+    #[test]
+    fn TEST_rename_plan_COLLISIONS_1() {
+        let matcher = CompiledMatcher::from_pattern_and_flags("*_?.txt", 0).unwrap();
+
+        let names = vec![
+            String::from("a_1.txt"),
+            String::from("a_2.txt"),
+            String::from("b_1.txt"),
+            String::from("c.txt"),
+            String::from("c_1.txt"),
+        ];
+
+        let plan = matcher.rename_plan(&names, "#1.txt").unwrap();
+
+        assert_eq!(4, plan.renames().len());
+        assert!(plan.has_collisions());
+        assert_eq!(
+            &[
+                Collision::SameTarget {
+                    target :  "a.txt".into(),
+                    sources : vec!["a_1.txt".into(), "a_2.txt".into()],
+                },
+                Collision::ExistingTarget {
+                    target : "c.txt".into(),
+                    source : "c_1.txt".into(),
+                },
+            ],
+            plan.collisions()
+        );
+    }
+
+    #[test]
+    fn TEST_rename_plan_CHAINS_AND_UNCHANGED_NAMES_1() {
+        // "a.old" is itself renamed, so renaming "a.old.old" to it is not
+        // a collision, provided that "a.old" is renamed first
+        let matcher = CompiledMatcher::from_pattern_and_flags("*.old", 0).unwrap();
+
+        let plan = matcher.rename_plan(["a.old.old", "a.old"], "#1").unwrap();
+
+        assert_eq!(&[rename("a.old", "a"), rename("a.old.old", "a.old")], plan.renames());
+        assert!(!plan.has_collisions());
+
+        let plan = matcher
+            .rename_plan(["b.old", "a.old.old", "a.old.old.old", "a.old"], "#1")
+            .unwrap();
+
+        assert_eq!(
+            &[
+                rename("b.old", "b"),
+                rename("a.old", "a"),
+                rename("a.old.old", "a.old"),
+                rename("a.old.old.old", "a.old.old"),
+            ],
+            plan.renames()
+        );
+        assert!(!plan.has_collisions());
+
+        // "b.txt" would be unchanged, so renaming "a.txt" to it is a
+        // collision
+        let matcher = CompiledMatcher::from_pattern_and_flags("?.txt", 0).unwrap();
+
+        let plan = matcher.rename_plan(["a.txt", "b.txt"], "b.txt").unwrap();
+
+        assert_eq!(&[rename("a.txt", "b.txt")], plan.renames());
+        assert_eq!(
+            &[Collision::ExistingTarget {
+                target : "b.txt".into(),
+                source : "a.txt".into(),
+            }],
+            plan.collisions()
+        );
+
+        let matcher = CompiledMatcher::from_pattern_and_flags("[ab].txt", 0).unwrap();
+
+        let plan = matcher.rename_plan(["a.txt", "b.txt"], "#1#1.txt").unwrap();
+
+        assert!(!plan.has_collisions());
+
+        let plan = matcher.rename_plan(["a.txt", "b.txt", "bb.txt"], "#1#1.txt").unwrap();
+
+        assert_eq!(
+            &[Collision::ExistingTarget {
+                target : "bb.txt".into(),
+                source : "b.txt".into(),
+            }],
+            plan.collisions()
+        );
+    }
+
+    #[test]
+    fn TEST_rename_plan_CYCLES_1() {
+        let matcher = CompiledMatcher::from_pattern_and_flags("*-*", 0).unwrap();
+
+        let plan = matcher.rename_plan(["p-q", "x-y", "y-x", "z-z"], "#2-#1").unwrap();
+
+        assert_eq!(3, plan.renames().len());
+        assert_eq!(
+            &[Collision::Cycle {
+                names : vec!["x-y".into(), "y-x".into()],
+            }],
+            plan.collisions()
+        );
+
+        let matcher = CompiledMatcher::from_pattern_and_flags("?", 0).unwrap();
+
+        let plan = matcher.rename_plan(["a", "b", "c"], "b").unwrap();
+
+        assert!(!plan
+            .collisions()
+            .iter()
+            .any(|collision| std::matches!(collision, Collision::Cycle { .. })));
+    }
+
+    #[test]
+    fn TEST_rename_plan_INVALID_TEMPLATE_1() {
+        let matcher = CompiledMatcher::from_pattern_and_flags("*", 0).unwrap();
+
+        assert!(matcher.rename_plan(["a"], "#2").is_err());
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
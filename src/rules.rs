@@ -0,0 +1,540 @@
+// src/rules.rs : include/exclude rule lists, in the manner of gitignore
+
+
+// ///////////////////////////////////////////////
+// imports
+
+use crate::{
+    parse,
+    CompiledMatcher,
+    Error,
+    Pattern,
+    PatternBuilder,
+    PatternElement,
+    Result,
+};
+
+use std::path::{
+    Component,
+    Path,
+    PathBuf,
+};
+
+
+// ///////////////////////////////////////////////
+// RuleMatch
+
+/// The outcome of evaluating a path against a [`RuleSet`], identifying
+/// the (index of the) rule that decided it.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(Eq, PartialEq)]
+#[derive(Hash)]
+pub enum RuleMatch {
+    /// The path is included, by a negated (`!`) rule.
+    Included(usize),
+    /// The path is excluded, by a (non-negated) rule, or because one of
+    /// its parent directories is excluded.
+    Excluded(usize),
+    /// No rule matches the path (or any of its parent directories).
+    Unmatched,
+}
+
+// Non-mutating methods
+impl RuleMatch {
+    /// Indicates whether the path is excluded.
+    #[inline]
+    pub fn is_excluded(&self) -> bool {
+        std::matches!(self, Self::Excluded(_))
+    }
+
+    /// The index of the rule that decided the outcome, if any.
+    #[inline]
+    pub fn rule(&self) -> Option<usize> {
+        match self {
+            Self::Included(index) | Self::Excluded(index) => Some(*index),
+            Self::Unmatched => None,
+        }
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// Rule
+
+/// A single rule of a [`RuleSet`].
+#[derive(Debug)]
+pub struct Rule {
+    text :           String,
+    negated :        bool,
+    directory_only : bool,
+    anchored :       bool,
+    /// The matcher, or `None` if the rule has an empty pattern, and so
+    /// matches nothing.
+    matcher :        Option<CompiledMatcher>,
+}
+
+// API functions
+impl Rule {
+    /// Parses `text` as a rule, according to `flags`.
+    fn from_text_(
+        text : &str,
+        flags : i64,
+    ) -> Result<Self> {
+        let (negated, body) = match text.strip_prefix('!') {
+            Some(body) => (true, body),
+            None => (false, text),
+        };
+
+        let (directory_only, body) = match body.strip_suffix('/') {
+            Some(body) if !body.ends_with('\\') => (true, body),
+            _ => (false, body),
+        };
+
+        let (rooted, body) = match body.strip_prefix('/') {
+            Some(body) => (true, body),
+            None => (false, body),
+        };
+
+        // the number of (single-byte) characters removed from the start
+        let offset = text.len() - body.len() - usize::from(directory_only);
+
+        let pattern = parse(body, flags).map_err(|error| {
+            match error {
+                Error::ParseError {
+                    line,
+                    column,
+                    message,
+                } => {
+                    Error::ParseError {
+                        line,
+                        column : column + offset,
+                        message,
+                    }
+                },
+                _ => error,
+            }
+        })?;
+
+        // a separator other than at the end anchors the rule to the root
+        let anchored = rooted
+            || pattern
+                .elements()
+                .iter()
+                .any(|element| std::matches!(element, PatternElement::Literal(literal) if literal.contains('/')));
+
+        Ok(Self::from_parts_(
+            text,
+            negated,
+            directory_only,
+            anchored,
+            Some(pattern),
+        ))
+    }
+
+    /// Creates an instance from its constituent parts, where `pattern` is
+    /// the pattern of the rule without any leading `'/'` or trailing
+    /// `'/'`, or `None` if the rule matches nothing.
+    pub(crate) fn from_parts_(
+        text : &str,
+        negated : bool,
+        directory_only : bool,
+        anchored : bool,
+        pattern : Option<Pattern>,
+    ) -> Self {
+        let matcher = match pattern {
+            Some(pattern) if !pattern.elements().is_empty() => {
+                let elements = pattern.elements();
+                let mut builder = PatternBuilder::new().with_flags(pattern.flags());
+
+                if !anchored {
+                    builder = builder.wild_n().wild_n().literal("/");
+                }
+
+                builder = builder.pattern(&pattern);
+
+                // a trailing `/**` matches only what is inside a directory
+                if std::matches!(
+                    elements,
+                    [.., PatternElement::Literal(literal), PatternElement::WildN, PatternElement::WildN] if literal.ends_with('/')
+                ) {
+                    builder = builder.literal("/").wild_n();
+                }
+
+                Some(builder.compile())
+            },
+            _ => None,
+        };
+
+        Self {
+            text : text.into(),
+            negated,
+            directory_only,
+            anchored,
+            matcher,
+        }
+    }
+}
+
+// Non-mutating methods
+impl Rule {
+    /// The text of the rule, as given.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Indicates whether the rule is negated, i.e. begins with `'!'`, and
+    /// so includes rather than excludes what it matches.
+    #[inline]
+    pub fn is_negated(&self) -> bool {
+        self.negated
+    }
+
+    /// Indicates whether the rule applies only to directories, i.e. ends
+    /// with `'/'`.
+    #[inline]
+    pub fn is_directory_only(&self) -> bool {
+        self.directory_only
+    }
+
+    /// Indicates whether the rule is anchored to the root, i.e. has a
+    /// `'/'` at its start or within it, rather than matching at any
+    /// depth.
+    #[inline]
+    pub fn is_anchored(&self) -> bool {
+        self.anchored
+    }
+
+    /// Determines whether the rule matches `path`.
+    pub fn matches(
+        &self,
+        path : &Path,
+        is_dir : bool,
+    ) -> bool {
+        if self.directory_only && !is_dir {
+            return false;
+        }
+
+        match &self.matcher {
+            Some(matcher) => matcher.matches_path_components(path),
+            None => false,
+        }
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// RuleSet
+
+/// An ordered list of include/exclude rules, in the manner of
+/// **gitignore**, in which:
+/// - each rule is a pattern that excludes any path that it matches, or,
+///   if it begins with `'!'`, includes it;
+/// - a later rule overrides an earlier one (i.e. the last match wins);
+/// - a rule that ends with `'/'` matches only directories;
+/// - a rule that begins with, or contains, `'/'` is anchored to the
+///   root, whereas one that does not matches a path at any depth;
+/// - a trailing `/**` matches everything inside a directory;
+/// - a path whose parent directory is excluded is excluded, whatever the
+///   rules for the path itself;
+///
+/// Paths are evaluated relative to the root, and component-wise, as by
+/// [`CompiledMatcher::matches_path_components()`].
+///
+/// # Examples:
+///
+/// ```
+/// use shwild::{
+///     RuleMatch,
+///     RuleSet,
+/// };
+/// use std::path::Path;
+///
+/// let rules = ["*.log", "!keep.log", "build/"];
+/// let rules = RuleSet::from_rules(rules, 0).unwrap();
+///
+/// let evaluate = |path : &str, is_dir : bool| {
+///     rules.evaluate(Path::new(path), is_dir)
+/// };
+///
+/// assert_eq!(RuleMatch::Excluded(0), evaluate("a/b.log", false));
+/// assert_eq!(RuleMatch::Included(1), evaluate("a/keep.log", false));
+/// assert_eq!(RuleMatch::Excluded(2), evaluate("build", true));
+/// assert_eq!(RuleMatch::Excluded(2), evaluate("build/a.o", false));
+/// assert_eq!(RuleMatch::Unmatched, evaluate("src/lib.rs", false));
+/// ```
+#[derive(Debug)]
+#[derive(Default)]
+pub struct RuleSet {
+    rules : Vec<Rule>,
+}
+
+// API functions
+impl RuleSet {
+    /// Creates an instance from the given `rules`, each parsed according
+    /// to `flags`. Empty rules are permitted, and match nothing, so that
+    /// rule indexes may correspond to line indexes.
+    ///
+    /// # Returns:
+    /// The rule set, or the error of the first rule that is invalid, in
+    /// which the (0-based) `line` is the index of the rule.
+    pub fn from_rules<I, S>(
+        rules : I,
+        flags : i64,
+    ) -> Result<Self>
+    where
+        I : IntoIterator<Item = S>,
+        S : AsRef<str>,
+    {
+        let rules = rules
+            .into_iter()
+            .enumerate()
+            .map(|(index, rule)| {
+                Rule::from_text_(rule.as_ref(), flags).map_err(|error| {
+                    match error {
+                        Error::ParseError {
+                            line,
+                            column,
+                            message,
+                        } => {
+                            Error::ParseError {
+                                line : line + index,
+                                column,
+                                message,
+                            }
+                        },
+                        _ => error,
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::from_rules_(rules))
+    }
+
+    /// Creates an instance from the given (already parsed) `rules`.
+    pub(crate) fn from_rules_(rules : Vec<Rule>) -> Self {
+        Self {
+            rules,
+        }
+    }
+}
+
+// Non-mutating methods
+impl RuleSet {
+    /// The rules, in the order of their indexes.
+    #[inline]
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Evaluates `path`, which is a directory if `is_dir`, against the
+    /// rules, taking into account its parent directories.
+    pub fn evaluate(
+        &self,
+        path : &Path,
+        is_dir : bool,
+    ) -> RuleMatch {
+        let mut prefix = PathBuf::new();
+        let mut components = path
+            .components()
+            .filter(|component| !std::matches!(component, Component::CurDir));
+        let mut next = components.next();
+
+        while let Some(component) = next {
+            prefix.push(component);
+
+            next = components.next();
+
+            if next.is_none() {
+                break;
+            }
+
+            if let r @ RuleMatch::Excluded(_) = self.evaluate_single(&prefix, true) {
+                return r;
+            }
+        }
+
+        self.evaluate_single(path, is_dir)
+    }
+
+    /// Evaluates `path`, which is a directory if `is_dir`, against the
+    /// rules, without regard to its parent directories.
+    pub fn evaluate_single(
+        &self,
+        path : &Path,
+        is_dir : bool,
+    ) -> RuleMatch {
+        self.rules
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, rule)| rule.matches(path, is_dir))
+            .map_or(RuleMatch::Unmatched, |(index, rule)| {
+                if rule.negated {
+                    RuleMatch::Included(index)
+                } else {
+                    RuleMatch::Excluded(index)
+                }
+            })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use crate::IGNORE_CASE;
+
+
+    fn evaluate(
+        rules : &RuleSet,
+        path : &str,
+        is_dir : bool,
+    ) -> RuleMatch {
+        rules.evaluate(Path::new(path), is_dir)
+    }
+
+
+    #[test]
+    fn TEST_Rule_1() {
+        let rule = Rule::from_text_("!/build/", 0).unwrap();
+
+        assert_eq!("!/build/", rule.text());
+        assert!(rule.is_negated());
+        assert!(rule.is_directory_only());
+        assert!(rule.is_anchored());
+
+        let rule = Rule::from_text_("*.log", 0).unwrap();
+
+        assert!(!rule.is_negated());
+        assert!(!rule.is_directory_only());
+        assert!(!rule.is_anchored());
+
+        assert!(Rule::from_text_("doc/*.md", 0).unwrap().is_anchored());
+    }
+
+    #[test]
+    fn TEST_RuleSet_LAST_MATCH_WINS_1() {
+        let rules = RuleSet::from_rules(["*.log", "!keep.log", "keep*"], 0).unwrap();
+
+        assert_eq!(RuleMatch::Excluded(0), evaluate(&rules, "a.log", false));
+        assert_eq!(RuleMatch::Excluded(2), evaluate(&rules, "keep.log", false));
+        assert_eq!(RuleMatch::Excluded(2), evaluate(&rules, "keep.txt", false));
+        assert_eq!(RuleMatch::Unmatched, evaluate(&rules, "a.txt", false));
+
+        let rules = RuleSet::from_rules(["*.log", "!keep.log"], 0).unwrap();
+
+        assert_eq!(RuleMatch::Included(1), evaluate(&rules, "keep.log", false));
+        assert_eq!(RuleMatch::Included(1), evaluate(&rules, "x/y/keep.log", false));
+    }
+
+    #[test]
+    fn TEST_RuleSet_DIRECTORY_ONLY_1() {
+        let rules = RuleSet::from_rules(["build/"], 0).unwrap();
+
+        assert_eq!(RuleMatch::Excluded(0), evaluate(&rules, "build", true));
+        assert_eq!(RuleMatch::Unmatched, evaluate(&rules, "build", false));
+        assert_eq!(RuleMatch::Excluded(0), evaluate(&rules, "a/build", true));
+        assert_eq!(RuleMatch::Excluded(0), evaluate(&rules, "a/build/x/y.o", false));
+        assert_eq!(RuleMatch::Unmatched, evaluate(&rules, "a/build.rs", false));
+    }
+
+    #[test]
+    fn TEST_RuleSet_ANCHORED_1() {
+        let rules = RuleSet::from_rules(["/target", "doc/*.md"], 0).unwrap();
+
+        assert_eq!(RuleMatch::Excluded(0), evaluate(&rules, "target", true));
+        assert_eq!(RuleMatch::Excluded(0), evaluate(&rules, "./target", false));
+        assert_eq!(RuleMatch::Unmatched, evaluate(&rules, "a/target", true));
+        assert_eq!(RuleMatch::Excluded(1), evaluate(&rules, "doc/x.md", false));
+        assert_eq!(RuleMatch::Unmatched, evaluate(&rules, "a/doc/x.md", false));
+        assert_eq!(RuleMatch::Unmatched, evaluate(&rules, "doc/a/x.md", false));
+    }
+
+    #[test]
+    fn TEST_RuleSet_ANY_COMPONENTS_1() {
+        let rules = RuleSet::from_rules(["**/tmp", "a/**/b", "c/**"], 0).unwrap();
+
+        assert_eq!(RuleMatch::Excluded(0), evaluate(&rules, "tmp", true));
+        assert_eq!(RuleMatch::Excluded(0), evaluate(&rules, "x/y/tmp", false));
+        assert_eq!(RuleMatch::Excluded(1), evaluate(&rules, "a/b", false));
+        assert_eq!(RuleMatch::Excluded(1), evaluate(&rules, "a/x/y/b", false));
+        assert_eq!(RuleMatch::Unmatched, evaluate(&rules, "c", true));
+        assert_eq!(RuleMatch::Excluded(2), evaluate(&rules, "c/d", false));
+        assert_eq!(RuleMatch::Excluded(2), evaluate(&rules, "c/d/e", false));
+    }
+
+    #[test]
+    fn TEST_RuleSet_EXCLUDED_PARENT_1() {
+        let rules = RuleSet::from_rules(["logs/", "!logs/keep.log", "!important/"], 0).unwrap();
+
+        // a file cannot be re-included if its parent directory is excluded
+        assert_eq!(RuleMatch::Excluded(0), evaluate(&rules, "logs/keep.log", false));
+        assert_eq!(RuleMatch::Included(2), evaluate(&rules, "important", true));
+        assert_eq!(RuleMatch::Unmatched, evaluate(&rules, "important/a", false));
+
+        assert_eq!(
+            RuleMatch::Included(1),
+            rules.evaluate_single(Path::new("logs/keep.log"), false)
+        );
+    }
+
+    #[test]
+    fn TEST_RuleSet_EMPTY_RULES_1() {
+        let rules = RuleSet::from_rules(["", "!", "/", "*.o"], 0).unwrap();
+
+        assert_eq!(4, rules.rules().len());
+        assert_eq!(RuleMatch::Unmatched, evaluate(&rules, "", false));
+        assert_eq!(RuleMatch::Unmatched, evaluate(&rules, "a", false));
+        assert_eq!(RuleMatch::Excluded(3), evaluate(&rules, "a.o", false));
+
+        assert_eq!(RuleMatch::Unmatched, evaluate(&RuleSet::default(), "a", false));
+    }
+
+    #[test]
+    fn TEST_RuleSet_IGNORE_CASE_1() {
+        let rules = RuleSet::from_rules(["*.LOG"], IGNORE_CASE).unwrap();
+
+        assert_eq!(RuleMatch::Excluded(0), evaluate(&rules, "a/b.log", false));
+    }
+
+    #[test]
+    fn TEST_RuleSet_INVALID_RULE_1() {
+        match RuleSet::from_rules(["*.log", "", "[abc"], 0) {
+            Err(Error::ParseError {
+                line, ..
+            }) => assert_eq!(2, line),
+            r => panic!("unexpected result {r:?}"),
+        };
+
+        let column_of = |rule : &str| {
+            match RuleSet::from_rules([rule], 0) {
+                Err(Error::ParseError {
+                    column, ..
+                }) => column,
+                r => panic!("unexpected result {r:?}"),
+            }
+        };
+
+        assert_eq!(column_of("a[b-9]"), column_of("!/a[b-9]") - 2);
+        assert_eq!(column_of("a[b-9]"), column_of("a[b-9]/"));
+    }
+
+    #[test]
+    fn TEST_RuleMatch_1() {
+        assert!(RuleMatch::Excluded(3).is_excluded());
+        assert!(!RuleMatch::Included(3).is_excluded());
+        assert!(!RuleMatch::Unmatched.is_excluded());
+        assert_eq!(Some(3), RuleMatch::Included(3).rule());
+        assert_eq!(None, RuleMatch::Unmatched.rule());
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
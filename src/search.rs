@@ -0,0 +1,459 @@
+// src/search.rs : unanchored search for occurrences of a pattern
+
+// ///////////////////////////////////////////////
+// imports
+
+use crate::{
+    automaton::{
+        Atom,
+        Nfa,
+    },
+    CompiledMatcher,
+};
+
+use std::ops::Range;
+
+
+// ///////////////////////////////////////////////
+// FindMode
+
+/// Determines which of the candidate occurrences of a pattern is found by
+/// [`CompiledMatcher::find_with_mode()`] and
+/// [`CompiledMatcher::find_iter_with_mode()`].
+///
+/// In both modes the occurrence that begins earliest in the input is
+/// preferred; the modes differ only in where that occurrence ends.
+#[derive(Clone)]
+#[derive(Copy)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Eq, PartialEq)]
+pub enum FindMode {
+    /// The longest of the occurrences that begin earliest, such that, for
+    /// example, `"a*"` finds `"abc"` in `"abc"`.
+    #[default]
+    LeftmostLongest,
+    /// The shortest of the occurrences that begin earliest, such that, for
+    /// example, `"a*"` finds `"a"` in `"abc"`.
+    LeftmostShortest,
+}
+
+
+// ///////////////////////////////////////////////
+// FindIter
+
+/// Iterator over the successive non-overlapping occurrences of a pattern
+/// in an input string, as obtained from [`CompiledMatcher::find_iter()`]
+/// and [`CompiledMatcher::find_iter_with_mode()`].
+///
+/// Each item is the byte range of an occurrence. An empty occurrence that
+/// begins where the preceding occurrence ends is not reported.
+#[derive(Debug)]
+pub struct FindIter<'m, 'i> {
+    matcher :    &'m CompiledMatcher,
+    input :      &'i str,
+    mode :       FindMode,
+    /// The offset from which to search, or `None` once exhausted.
+    at :         Option<usize>,
+    last_end :   Option<usize>,
+    /// The boundaries between which occurrences are sought, if by the
+    /// interpreter, which are obtained once for all occurrences.
+    boundaries : Option<Vec<usize>>,
+}
+
+// API functions
+impl<'m, 'i> FindIter<'m, 'i> {
+    pub(crate) fn new(
+        matcher : &'m CompiledMatcher,
+        input : &'i str,
+        mode : FindMode,
+    ) -> Self {
+        Self {
+            matcher,
+            input,
+            mode,
+            at : Some(0),
+            last_end : None,
+            boundaries : matcher.find_boundaries_(input),
+        }
+    }
+}
+
+// Trait implementations
+
+impl Iterator for FindIter<'_, '_> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let at = self.at?;
+
+            let found = match self
+                .matcher
+                .find_at_(self.input, at, self.mode, self.boundaries.as_deref())
+            {
+                Some(found) => found,
+                None => {
+                    self.at = None;
+
+                    return None;
+                },
+            };
+
+            if found.is_empty() && Some(found.start) == self.last_end {
+                self.at = self.matcher.next_boundary_(self.input, found.start);
+
+                continue;
+            }
+
+            self.at = if found.is_empty() {
+                self.matcher.next_boundary_(self.input, found.end)
+            } else {
+                Some(found.end)
+            };
+            self.last_end = Some(found.end);
+
+            return Some(found);
+        }
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// search functions
+
+/// Sentinel denoting a position that is not (currently) occupied.
+const NONE : usize = usize::MAX;
+
+/// Finds the first occurrence, according to `mode`, of the pattern
+/// represented by `nfa` in `input` at or after the byte offset `at`.
+///
+/// The automaton is simulated in a single pass over the input, a new
+/// thread being started at each character boundary until an occurrence is
+/// found. Each position records the earliest start offset of the threads
+/// that occupy it, since the future of a thread depends only on its
+/// position.
+pub(crate) fn find_at_by_nfa(
+    nfa : &Nfa,
+    input : &str,
+    at : usize,
+    mode : FindMode,
+) -> Option<Range<usize>> {
+    let accepting = nfa.num_positions() - 1;
+
+    let mut current = vec![NONE; nfa.num_positions()];
+    let mut next = vec![NONE; nfa.num_positions()];
+    let mut best : Option<Range<usize>> = None;
+
+    let mut chars = input[at..].chars();
+    let mut offset = at;
+
+    loop {
+        if best.is_none() {
+            add_thread_(nfa, &mut current, 0, offset);
+        }
+
+        let start = current[accepting];
+
+        if NONE != start {
+            // offsets only increase, so an occurrence with the same start
+            // as the best is longer (and never shorter)
+            let is_better = match (&best, mode) {
+                (None, _) => true,
+                (Some(best), FindMode::LeftmostLongest) => start <= best.start,
+                (Some(best), FindMode::LeftmostShortest) => start < best.start,
+            };
+
+            if is_better {
+                best = Some(start..offset);
+            }
+        }
+
+        if let Some(best) = &best {
+            // discard the threads that cannot improve on the best
+            for start in current.iter_mut() {
+                let is_worse = match mode {
+                    FindMode::LeftmostLongest => *start > best.start,
+                    FindMode::LeftmostShortest => *start >= best.start,
+                };
+
+                if is_worse {
+                    *start = NONE;
+                }
+            }
+
+            if current.iter().all(|&start| NONE == start) {
+                break;
+            }
+        }
+
+        let c = match chars.next() {
+            Some(c) => c,
+            None => break,
+        };
+
+        next.fill(NONE);
+
+        for (p, &start) in current.iter().enumerate() {
+            if NONE == start {
+                continue;
+            }
+
+            match nfa.atom(p) {
+                Some(Atom::One(char_set)) if char_set.contains(c) => {
+                    add_thread_(nfa, &mut next, p + 1, start);
+                },
+                Some(Atom::Star) => {
+                    add_thread_(nfa, &mut next, p, start);
+                },
+                _ => {},
+            };
+        }
+
+        std::mem::swap(&mut current, &mut next);
+
+        offset += c.len_utf8();
+    }
+
+    best
+}
+
+/// Finds the first occurrence, according to `mode`, at or after the
+/// offset `at`, by evaluating `is_match` against each pair of the given
+/// (ascending) `boundaries`.
+pub(crate) fn find_at_by_boundaries<F>(
+    boundaries : &[usize],
+    at : usize,
+    mode : FindMode,
+    is_match : F,
+) -> Option<Range<usize>>
+where
+    F : Fn(Range<usize>) -> bool,
+{
+    let first = boundaries.partition_point(|&boundary| boundary < at);
+
+    for (index, &start) in boundaries.iter().enumerate().skip(first) {
+        let ends = &boundaries[index..];
+
+        let end = match mode {
+            FindMode::LeftmostLongest => ends.iter().rev().find(|&&end| is_match(start..end)),
+            FindMode::LeftmostShortest => ends.iter().find(|&&end| is_match(start..end)),
+        };
+
+        if let Some(&end) = end {
+            return Some(start..end);
+        }
+    }
+
+    None
+}
+
+/// Places a thread with the given `start` offset at position `p`, and at
+/// all positions reachable from it by skipping over `*` atoms.
+fn add_thread_(
+    nfa : &Nfa,
+    positions : &mut [usize],
+    p : usize,
+    start : usize,
+) {
+    let mut p = p;
+
+    loop {
+        if positions[p] <= start {
+            // the position, and its closure, already hold an earlier start
+            return;
+        }
+
+        positions[p] = start;
+
+        match nfa.atom(p) {
+            Some(Atom::Star) => p += 1,
+            _ => return,
+        };
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use crate::IGNORE_CASE;
+
+
+    fn find(
+        pattern : &str,
+        flags : i64,
+        mode : FindMode,
+        input : &str,
+    ) -> Option<Range<usize>> {
+        CompiledMatcher::from_pattern_and_flags(pattern, flags)
+            .unwrap()
+            .find_with_mode(input, mode)
+    }
+
+    fn find_all(
+        pattern : &str,
+        mode : FindMode,
+        input : &str,
+    ) -> Vec<Range<usize>> {
+        CompiledMatcher::from_pattern_and_flags(pattern, 0)
+            .unwrap()
+            .find_iter_with_mode(input, mode)
+            .collect()
+    }
+
+    /// Obtains the first occurrence by evaluating every substring with
+    /// the (anchored) interpreter.
+    fn find_by_brute_force(
+        matcher : &CompiledMatcher,
+        mode : FindMode,
+        input : &str,
+    ) -> Option<Range<usize>> {
+        let mut boundaries : Vec<usize> = input.char_indices().map(|(index, _)| index).collect();
+
+        boundaries.push(input.len());
+
+        find_at_by_boundaries(&boundaries, 0, mode, |range| matcher.matches(&input[range]))
+    }
+
+
+    #[test]
+    fn TEST_find_LEFTMOST_LONGEST_1() {
+        let mode = FindMode::LeftmostLongest;
+
+        assert_eq!(Some(4..7), find("abc", 0, mode, "xyz abc abc"));
+        assert_eq!(None, find("abd", 0, mode, "xyz abc abc"));
+        assert_eq!(Some(0..3), find("a*", 0, mode, "abc"));
+        assert_eq!(Some(0..6), find("a*c", 0, mode, "abcabcx"));
+        assert_eq!(Some(3..6), find("[bc]?d", 0, mode, "a bcxd"));
+        assert_eq!(Some(0..0), find("", 0, mode, "abc"));
+        assert_eq!(Some(0..0), find("*", 0, mode, ""));
+        assert_eq!(Some(4..7), find("ABC", IGNORE_CASE, mode, "xyz abc"));
+
+        // as by `matches()`, with case variants of different lengths
+        assert_eq!(Some(1..3), find("ß", IGNORE_CASE, mode, "xssx"));
+        assert_eq!(Some(4..6), find("ss", IGNORE_CASE, mode, "Straße"));
+        assert_eq!(Some(0..2), find("s", IGNORE_CASE, mode, "ſ"));
+    }
+
+    #[test]
+    fn TEST_find_LEFTMOST_SHORTEST_1() {
+        let mode = FindMode::LeftmostShortest;
+
+        assert_eq!(Some(4..7), find("abc", 0, mode, "xyz abc abc"));
+        assert_eq!(Some(0..1), find("a*", 0, mode, "abc"));
+        assert_eq!(Some(0..3), find("a*c", 0, mode, "abcabcx"));
+        assert_eq!(Some(0..0), find("*", 0, mode, "abc"));
+
+        // the shortest is that of the occurrences that begin earliest
+        assert_eq!(Some(0..5), find("?b?*e", 0, mode, "abxbe"));
+        assert_eq!(Some(1..5), find("b*c", 0, mode, "abxbcbc"));
+        assert_eq!(Some(1..7), find("b*c", 0, FindMode::LeftmostLongest, "abxbcbc"));
+    }
+
+    #[test]
+    fn TEST_find_NON_ASCII_1() {
+        let input = "Where are the 🐼s and the 🐻s?";
+
+        let start = input.find('🐻').unwrap();
+
+        assert_eq!(
+            Some(start..start + '🐻'.len_utf8() + 1),
+            find("🐻?", 0, FindMode::LeftmostLongest, input)
+        );
+        assert_eq!(Some(14..32), find("[🐼🐻]*[🐼🐻]", 0, FindMode::LeftmostLongest, input));
+    }
+
+    #[test]
+    fn TEST_find_AGREES_WITH_BRUTE_FORCE_1() {
+        let patterns = [
+            "",
+            "*",
+            "a",
+            "a*",
+            "*a",
+            "a*b",
+            "a?b",
+            "?*?",
+            "[ab]*[^ab]",
+            "a**b*c",
+            "b*b",
+            "ß",
+            "s?",
+            "*ss",
+        ];
+        let inputs = [
+            "",
+            "a",
+            "b",
+            "ab",
+            "ba",
+            "aab",
+            "abab",
+            "acbab",
+            "xaybzc",
+            "bbbabb",
+            "🐻a🐼b",
+            "Straße",
+            "STRASSE ß",
+            "ſs",
+        ];
+
+        for pattern in patterns {
+            for flags in [0, IGNORE_CASE] {
+                let matcher = CompiledMatcher::from_pattern_and_flags(pattern, flags).unwrap();
+
+                for input in inputs {
+                    for mode in [FindMode::LeftmostLongest, FindMode::LeftmostShortest] {
+                        assert_eq!(
+                            find_by_brute_force(&matcher, mode, input),
+                            matcher.find_with_mode(input, mode),
+                            "pattern '{pattern}', flags {flags}, mode {mode:?}, input '{input}'"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn TEST_find_iter_1() {
+        assert_eq!(
+            vec![0..1, 4..5, 8..9],
+            find_all("a", FindMode::LeftmostLongest, "abc abc abc")
+        );
+        assert_eq!(vec![0..3, 4..7], find_all("a?c", FindMode::LeftmostLongest, "abc abc"));
+        assert_eq!(vec![0..7], find_all("a*c", FindMode::LeftmostLongest, "abc abc"));
+        assert_eq!(vec![0..3, 4..7], find_all("a*c", FindMode::LeftmostShortest, "abc abc"));
+        assert!(find_all("x", FindMode::LeftmostLongest, "abc").is_empty());
+    }
+
+    #[test]
+    fn TEST_find_iter_EMPTY_OCCURRENCES_1() {
+        assert_eq!(vec![0..0, 1..1, 2..2], find_all("", FindMode::LeftmostLongest, "ab"));
+        assert_eq!(vec![0..2], find_all("*", FindMode::LeftmostLongest, "ab"));
+        assert_eq!(vec![0..0, 1..1, 2..2], find_all("*", FindMode::LeftmostShortest, "ab"));
+        assert_eq!(vec![0..0, 4..4], find_all("", FindMode::LeftmostLongest, "🐻"));
+    }
+
+    #[test]
+    fn TEST_find_PURE_BYTES_1() {
+        let matcher = CompiledMatcher::from_pattern_and_flags("f?-", crate::PURE_BYTES).unwrap();
+
+        assert_eq!(None, matcher.find("xfé-"));
+
+        let matcher = CompiledMatcher::from_pattern_and_flags("f??-", crate::PURE_BYTES).unwrap();
+
+        assert_eq!(Some(1..5), matcher.find("xfé-"));
+
+        let matcher = CompiledMatcher::from_pattern_and_flags("?é", crate::PURE_BYTES).unwrap();
+
+        assert_eq!(vec![0..3, 3..6], matcher.find_iter("xéyé").collect::<Vec<_>>());
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
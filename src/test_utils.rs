@@ -0,0 +1,214 @@
+// src/test_utils.rs : utilities shared by the tests of several modules
+
+// ///////////////////////////////////////////////
+// imports
+
+use crate::{
+    PatternBuilder,
+    RandomSource as _,
+    SeededRandom,
+};
+
+use std::{
+    fs,
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::atomic::{
+        AtomicUsize,
+        Ordering,
+    },
+};
+
+
+// ///////////////////////////////////////////////
+// TestRandom
+
+/// A deterministic generator of test data, such that randomised tests are
+/// reproducible.
+pub(crate) struct TestRandom {
+    rng : SeededRandom,
+}
+
+// API functions
+impl TestRandom {
+    pub(crate) fn new(seed : u64) -> Self {
+        Self {
+            rng : SeededRandom::new(seed),
+        }
+    }
+}
+
+// Mutating methods
+impl TestRandom {
+    /// A random number in `[0, n)`, where `n` is non-zero.
+    pub(crate) fn below(
+        &mut self,
+        n : usize,
+    ) -> usize {
+        (self.rng.next_u64() % n as u64) as usize
+    }
+
+    /// A random element of the (non-empty) `items`.
+    pub(crate) fn pick<T : Copy>(
+        &mut self,
+        items : &[T],
+    ) -> T {
+        items[self.below(items.len())]
+    }
+
+    /// A random string of fewer than `max_len` characters of `alphabet`.
+    pub(crate) fn string(
+        &mut self,
+        alphabet : &[char],
+        max_len : usize,
+    ) -> String {
+        (0..self.below(max_len)).map(|_| self.pick(alphabet)).collect()
+    }
+
+    /// A builder of a random pattern of fewer than `max_elements` elements,
+    /// each a literal, range, or not-range of fewer than `max_chars`
+    /// characters of `alphabet`, or a `?` or `*`.
+    pub(crate) fn pattern_builder(
+        &mut self,
+        alphabet : &[char],
+        max_elements : usize,
+        max_chars : usize,
+    ) -> PatternBuilder {
+        let mut builder = PatternBuilder::new();
+
+        for _ in 0..self.below(max_elements) {
+            let chars = self.string(alphabet, max_chars);
+
+            builder = match self.below(5) {
+                0 => builder.literal(&chars),
+                1 => builder.wild1(),
+                2 => builder.wild_n(),
+                3 => builder.range(chars.chars()),
+                _ => builder.not_range(chars.chars()),
+            };
+        }
+
+        builder
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// TempTree
+
+/// A directory tree under the temporary directory, which is removed on
+/// drop.
+pub(crate) struct TempTree {
+    pub(crate) root : PathBuf,
+}
+
+// API functions
+impl TempTree {
+    /// Creates a tree in which each of `paths` is created as an empty
+    /// file, or, if it ends with `'/'`, as a directory.
+    pub(crate) fn new(
+        name : &str,
+        paths : &[&str],
+    ) -> Self {
+        static COUNT : AtomicUsize = AtomicUsize::new(0);
+
+        let count = COUNT.fetch_add(1, Ordering::Relaxed);
+        let root = std::env::temp_dir().join(format!("shwild-{name}-{}-{count}", std::process::id()));
+
+        let _ = fs::remove_dir_all(&root);
+
+        fs::create_dir_all(&root).unwrap();
+
+        let tree = Self {
+            root,
+        };
+
+        for path in paths {
+            if path.ends_with('/') {
+                fs::create_dir_all(tree.root.join(path)).unwrap();
+            } else {
+                tree.write(path, "");
+            }
+        }
+
+        tree
+    }
+
+    /// Creates a tree of `breadth` directories, named `d0`, `d1`, ..., at
+    /// each of `depth` levels, each directory (including the root)
+    /// containing `a.rs` and `b.txt`.
+    pub(crate) fn with_levels(
+        name : &str,
+        breadth : usize,
+        depth : usize,
+    ) -> Self {
+        let mut dirs = vec![String::new()];
+        let mut paths = vec![];
+
+        for level in 0..=depth {
+            for dir in &dirs {
+                paths.push(format!("{dir}a.rs"));
+                paths.push(format!("{dir}b.txt"));
+            }
+
+            if level < depth {
+                dirs = dirs
+                    .iter()
+                    .flat_map(|dir| (0..breadth).map(move |i| format!("{dir}d{i}/")))
+                    .collect();
+            }
+        }
+
+        let paths : Vec<&str> = paths.iter().map(String::as_str).collect();
+
+        Self::new(name, &paths)
+    }
+}
+
+// Non-mutating methods
+impl TempTree {
+    /// Writes `contents` to the file at `path`, relative to the root,
+    /// creating any missing directories.
+    pub(crate) fn write(
+        &self,
+        path : &str,
+        contents : &str,
+    ) {
+        let full = self.root.join(path);
+
+        fs::create_dir_all(full.parent().unwrap()).unwrap();
+        fs::write(&full, contents).unwrap();
+    }
+
+    /// `pattern` prefixed by the root, with `'/'` separators.
+    pub(crate) fn pattern(
+        &self,
+        pattern : &str,
+    ) -> String {
+        format!("{}/{pattern}", self.root.to_str().unwrap().replace('\\', "/"))
+    }
+
+    /// `path`, relative to the root, with `'/'` separators.
+    pub(crate) fn relative(
+        &self,
+        path : &Path,
+    ) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(Path::new(""))
+            .to_str()
+            .unwrap()
+            .replace('\\', "/")
+    }
+}
+
+// Trait implementations
+impl Drop for TempTree {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
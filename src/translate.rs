@@ -0,0 +1,1029 @@
+// src/translate.rs : translation of patterns into other pattern languages
+
+// ///////////////////////////////////////////////
+// imports
+
+use std::matches as std_matches;
+
+use crate::{
+    case,
+    constants,
+    pattern,
+    Error,
+    PatternBuilder,
+    PatternElement,
+    Result,
+};
+
+
+// ///////////////////////////////////////////////
+// regular expressions
+
+/// Characters that are escaped in a regular expression, whether or not
+/// in a character class.
+const REGEX_SPECIAL_CHARACTERS : &[char] = &[
+    '#',
+    '$',
+    '&',
+    '(',
+    ')',
+    '*',
+    '+',
+    '-',
+    '.',
+    '?',
+    '[',
+    '\\',
+    ']',
+    '^',
+    '{',
+    '|',
+    '}',
+    '~',
+];
+
+/// The maximum number of alternatives by which a literal that, with
+/// `IGNORE_CASE`, may match a sequence of characters of a different
+/// length, e.g. `"ss"`, which matches `"ß"`, is expressed.
+const MAX_FOLDED_ALTERNATIVES : usize = 256;
+
+/// Translates `elements`, parsed according to `flags`, into an equivalent
+/// anchored regular expression.
+pub(crate) fn to_regex_string(
+    elements : &[PatternElement],
+    flags : i64,
+) -> Result<String> {
+    let ignore_case = 0 != (flags & constants::IGNORE_CASE);
+
+    let mut re = String::from("(?s)^");
+
+    for element in elements {
+        match element {
+            PatternElement::Literal(literal) if ignore_case && case::has_multi_char_folding(literal) => {
+                push_regex_folded_literal_(&mut re, literal)?;
+            },
+            PatternElement::Literal(literal) => {
+                for c in literal.chars() {
+                    let variants = if ignore_case { case::case_variants(c) } else { vec![c] };
+
+                    if let [c] = variants[..] {
+                        push_regex_escaped_(&mut re, c);
+                    } else {
+                        re.push('[');
+                        variants.into_iter().for_each(|c| push_regex_escaped_(&mut re, c));
+                        re.push(']');
+                    }
+                }
+            },
+            PatternElement::Wild1 => re.push('.'),
+            PatternElement::WildN => re.push_str(".*"),
+            PatternElement::Range {
+                chars,
+                negated,
+            } => {
+                let mut chars = if ignore_case {
+                    // as for matching, range characters are case-folded
+                    // only if ASCII
+                    chars
+                        .iter()
+                        .flat_map(|c| [c.to_ascii_lowercase(), c.to_ascii_uppercase()])
+                        .collect()
+                } else {
+                    chars.clone()
+                };
+
+                chars.sort_unstable();
+                chars.dedup();
+
+                match (chars.is_empty(), negated) {
+                    // an empty range matches nothing
+                    (true, false) => re.push_str(r"[^\x00-\x{10FFFF}]"),
+                    // an empty not-range matches any character
+                    (true, true) => re.push('.'),
+                    (false, _) => {
+                        re.push_str(if *negated { "[^" } else { "[" });
+                        chars.into_iter().for_each(|c| push_regex_escaped_(&mut re, c));
+                        re.push(']');
+                    },
+                };
+            },
+        };
+    }
+
+    re.push('$');
+
+    Ok(re)
+}
+
+/// Writes the regular expression for the literal `literal` with
+/// `IGNORE_CASE`, which matches each sequence of characters whose
+/// upper-case is that of `literal` - as does the interpreter - by
+/// alternation wherever the upper-case may be divided among the input's
+/// characters in more than one way, e.g. `"(?:[Ssſ][Ssſ]|ß)"` for
+/// `"ss"`.
+fn push_regex_folded_literal_(
+    re : &mut String,
+    literal : &str,
+) -> Result<()> {
+    let uppercase : Vec<char> = literal.to_uppercase().chars().collect();
+
+    // the characters (of the input) by which each part of the upper-case
+    // - a character or an expansion - from each position may be matched,
+    // paired with the end of the part
+    let steps : Vec<Vec<(usize, Vec<char>)>> = (0..uppercase.len())
+        .map(|from| {
+            let single = (
+                from + 1,
+                case::uppercase_preimages(uppercase[from].encode_utf8(&mut [0; 4])),
+            );
+
+            let expansions = case::expansions().filter_map(|expansion| {
+                let n = expansion.chars().count();
+
+                uppercase[from..]
+                    .iter()
+                    .take(n)
+                    .copied()
+                    .eq(expansion.chars())
+                    .then(|| (from + n, case::uppercase_preimages(expansion)))
+            });
+
+            std::iter::once(single)
+                .chain(expansions)
+                .filter(|(_, chars)| !chars.is_empty())
+                .collect()
+        })
+        .collect();
+
+    // the upper-case is divided into sections at each position that is
+    // not within a part of more than one character, so that alternation
+    // is required only within a section
+
+    let mut from = 0;
+
+    while from < uppercase.len() {
+        let mut to = from + 1;
+        let mut i = from;
+
+        while i < to {
+            to = steps[i].iter().map(|&(end, _)| end).fold(to, usize::max);
+            i += 1;
+        }
+
+        // the number of alternatives from each position of the section
+        let mut counts = vec![0_usize; to - from + 1];
+
+        counts[to - from] = 1;
+
+        for i in (from..to).rev() {
+            counts[i - from] = steps[i]
+                .iter()
+                .filter(|&&(end, _)| end <= to)
+                .map(|&(end, _)| counts[end - from])
+                .fold(0, usize::saturating_add);
+        }
+
+        if counts[0] > MAX_FOLDED_ALTERNATIVES {
+            return Err(Error::TranslationError {
+                message : format!(
+                    "the literal '{}' has more than {MAX_FOLDED_ALTERNATIVES} case-insensitive forms",
+                    pattern::escape_literal(literal)
+                ),
+            });
+        }
+
+        push_regex_folded_section_(re, &steps, from, to);
+
+        from = to;
+    }
+
+    Ok(())
+}
+
+/// Writes the regular expression for the section `[from, to)` of the
+/// upper-case of a literal, given the `steps` from each of its positions.
+fn push_regex_folded_section_(
+    re : &mut String,
+    steps : &[Vec<(usize, Vec<char>)>],
+    from : usize,
+    to : usize,
+) {
+    if from == to {
+        return;
+    }
+
+    let alternatives : Vec<&(usize, Vec<char>)> = steps[from].iter().filter(|&&(end, _)| end <= to).collect();
+
+    let push_step = |re : &mut String, (end, chars) : &(usize, Vec<char>)| {
+        if let [c] = chars[..] {
+            push_regex_escaped_(re, c);
+        } else {
+            re.push('[');
+            chars.iter().for_each(|&c| push_regex_escaped_(re, c));
+            re.push(']');
+        }
+
+        push_regex_folded_section_(re, steps, *end, to);
+    };
+
+    match alternatives[..] {
+        // no sequence of characters has the upper-case
+        [] => re.push_str(r"[^\x00-\x{10FFFF}]"),
+        [alternative] => push_step(re, alternative),
+        _ => {
+            re.push_str("(?:");
+
+            for (i, &alternative) in alternatives.iter().enumerate() {
+                if 0 != i {
+                    re.push('|');
+                }
+
+                push_step(re, alternative);
+            }
+
+            re.push(')');
+        },
+    };
+}
+
+
+// ///////////////////////////////////////////////
+// SQL
+
+/// Translates `elements` into an equivalent SQL `LIKE` expression, with
+/// literal `%`, `_`, and `escape_char` characters escaped by
+/// `escape_char`.
+pub(crate) fn to_sql_like(
+    elements : &[PatternElement],
+    escape_char : char,
+) -> Result<String> {
+    if '%' == escape_char || '_' == escape_char {
+        return Err(Error::TranslationError {
+            message : format!("the escape character may not be '{escape_char}'"),
+        });
+    }
+
+    let mut like = String::new();
+
+    for element in elements {
+        match element {
+            PatternElement::Literal(literal) => {
+                for c in literal.chars() {
+                    if '%' == c || '_' == c || escape_char == c {
+                        like.push(escape_char);
+                    }
+
+                    like.push(c);
+                }
+            },
+            PatternElement::Wild1 => like.push('_'),
+            PatternElement::WildN => like.push('%'),
+            PatternElement::Range {
+                ..
+            } => {
+                return Err(Error::TranslationError {
+                    message : format!(
+                        "the range '{}' cannot be expressed in SQL LIKE",
+                        element_string_(element)
+                    ),
+                });
+            },
+        };
+    }
+
+    Ok(like)
+}
+
+/// Translates `elements`, parsed according to `flags`, into an equivalent
+/// SQLite `GLOB` expression.
+pub(crate) fn to_sqlite_glob(
+    elements : &[PatternElement],
+    flags : i64,
+) -> Result<String> {
+    let ignore_case = 0 != (flags & constants::IGNORE_CASE);
+
+    let mut glob = String::new();
+
+    for element in elements {
+        match element {
+            PatternElement::Literal(literal) if ignore_case && case::has_multi_char_folding(literal) => {
+                return Err(Error::TranslationError {
+                    // e.g. `"ss"`, which matches `"ß"`
+                    message : format!(
+                        "the literal '{}' cannot be expressed case-insensitively in SQLite GLOB",
+                        element_string_(element)
+                    ),
+                });
+            },
+            PatternElement::Literal(literal) => {
+                for c in literal.chars() {
+                    let variants = if ignore_case { case::case_variants(c) } else { vec![c] };
+
+                    match variants[..] {
+                        ['*'] => glob.push_str("[*]"),
+                        ['?'] => glob.push_str("[?]"),
+                        ['['] => glob.push_str("[[]"),
+                        [c] => glob.push(c),
+                        _ => push_glob_range_(&mut glob, variants, false),
+                    };
+                }
+            },
+            PatternElement::Wild1 => glob.push('?'),
+            PatternElement::WildN => glob.push('*'),
+            PatternElement::Range {
+                chars,
+                negated,
+            } => {
+                let chars = if ignore_case {
+                    chars
+                        .iter()
+                        .flat_map(|c| [c.to_ascii_lowercase(), c.to_ascii_uppercase()])
+                        .collect()
+                } else {
+                    chars.clone()
+                };
+
+                match (chars.is_empty(), negated) {
+                    (true, false) => {
+                        return Err(Error::TranslationError {
+                            message : "the empty range '[]' cannot be expressed in SQLite GLOB".into(),
+                        });
+                    },
+                    // an empty not-range matches any character
+                    (true, true) => glob.push('?'),
+                    (false, _) => push_glob_range_(&mut glob, chars, *negated),
+                };
+            },
+        };
+    }
+
+    Ok(glob)
+}
+
+/// Writes a (non-empty) `GLOB` range of `chars`, which has no escapes,
+/// and so relies on position: `]` is literal only when first, `-` when
+/// first or last, and `^` when not first.
+fn push_glob_range_(
+    glob : &mut String,
+    mut chars : Vec<char>,
+    negated : bool,
+) {
+    debug_assert!(!chars.is_empty());
+
+    chars.sort_unstable();
+    chars.dedup();
+
+    if !negated && ['^'] == chars[..] {
+        glob.push('^');
+
+        return;
+    }
+
+    let has = |c : char| chars.contains(&c);
+    let others = chars.iter().copied().filter(|&c| !['-', ']', '^'].contains(&c));
+
+    let mut ordered : Vec<char> = Vec::with_capacity(chars.len());
+
+    if has(']') {
+        ordered.push(']');
+    }
+
+    ordered.extend(others);
+
+    if has('^') {
+        if ordered.is_empty() && !negated {
+            // `-` is also literal when first, so may precede `^`
+            ordered.push('-');
+        }
+
+        ordered.push('^');
+    }
+
+    if has('-') && !ordered.contains(&'-') {
+        ordered.push('-');
+    }
+
+    glob.push_str(if negated { "[^" } else { "[" });
+    glob.extend(ordered);
+    glob.push(']');
+}
+
+/// The canonical form of `element`, for use in error messages.
+fn element_string_(element : &PatternElement) -> String {
+    let mut s = String::new();
+
+    // writing to a `String` cannot fail
+    let _ = pattern::write_elements(&mut s, std::slice::from_ref(element));
+
+    s
+}
+
+
+// ///////////////////////////////////////////////
+// regular expression subset
+
+/// The maximum number of characters in a regular expression class range,
+/// e.g. `"0-9"`, which is expanded into its characters.
+const MAX_CLASS_RANGE_LENGTH : u32 = 256;
+
+/// Converts the regular expression `regex`, which must be within the
+/// subset expressible as a pattern, into the (normalised) pattern.
+pub(crate) fn from_regex_subset(regex : &str) -> Result<String> {
+    let mut parser = RegexSubsetParser {
+        chars :  regex.chars().collect(),
+        column : 0,
+    };
+
+    let builder = parser.parse_()?;
+
+    let mut pattern = String::new();
+
+    // writing to a `String` cannot fail
+    let _ = pattern::write_normalised_elements(&mut pattern, builder.build().elements(), true);
+
+    Ok(pattern)
+}
+
+/// A single forward pass over the characters of a regular expression.
+struct RegexSubsetParser {
+    chars :  Vec<char>,
+    column : usize,
+}
+
+// Implementation
+impl RegexSubsetParser {
+    fn parse_(&mut self) -> Result<PatternBuilder> {
+        let mut builder = PatternBuilder::new();
+
+        // `.` matches a newline, as do `?` and `*`, only with the `s` flag
+        let dot_matches_newline = self.take_if_(&['(', '?', 's', ')']);
+
+        if !self.take_if_(&['^']) && !self.take_if_(&['\\', 'A']) {
+            builder = builder.wild_n();
+        }
+
+        let mut anchored_end = false;
+
+        while let Some(c) = self.peek_() {
+            let column = self.column;
+
+            self.column += 1;
+
+            builder = match c {
+                '$' | '\\' if '$' == c || Some('z') == self.peek_() => {
+                    if '\\' == c {
+                        self.column += 1;
+                    }
+
+                    if self.peek_().is_some() {
+                        return Err(self.error_(column, "end anchor other than at the end"));
+                    }
+
+                    anchored_end = true;
+
+                    builder
+                },
+                '.' if !dot_matches_newline => {
+                    return Err(self.error_(
+                        column,
+                        "'.' does not match a newline, other than after a leading '(?s)'",
+                    ));
+                },
+                '.' => {
+                    match self.peek_() {
+                        Some('*') => {
+                            self.column += 1;
+                            self.take_if_(&['?']);
+
+                            builder.wild_n()
+                        },
+                        Some('+') => {
+                            self.column += 1;
+                            self.take_if_(&['?']);
+
+                            builder.wild1().wild_n()
+                        },
+                        _ => builder.wild1(),
+                    }
+                },
+                '[' => {
+                    let (chars, negated) = self.parse_class_(column)?;
+
+                    if negated {
+                        builder.not_range(chars)
+                    } else {
+                        builder.range(chars)
+                    }
+                },
+                '\\' => {
+                    let c = self.parse_escape_(column)?;
+
+                    builder.literal(c.encode_utf8(&mut [0; 4]))
+                },
+                '^' => {
+                    return Err(self.error_(column, "start anchor other than at the start"));
+                },
+                '|' => {
+                    return Err(self.error_(column, "alternation ('|') is not supported"));
+                },
+                '(' => {
+                    let rest = &self.chars[self.column..];
+
+                    let message = if rest.starts_with(&['?', '='])
+                        || rest.starts_with(&['?', '!'])
+                        || rest.starts_with(&['?', '<', '='])
+                        || rest.starts_with(&['?', '<', '!'])
+                    {
+                        "lookarounds are not supported"
+                    } else if rest.starts_with(&['?'])
+                        && !rest.starts_with(&['?', ':'])
+                        && !rest.starts_with(&['?', 'P'])
+                    {
+                        "flags are not supported, other than a leading '(?s)'"
+                    } else {
+                        "groups are not supported"
+                    };
+
+                    return Err(self.error_(column, message));
+                },
+                ')' => {
+                    return Err(self.error_(column, "groups are not supported"));
+                },
+                '*' | '+' | '?' | '{' => {
+                    return Err(self.error_(
+                        column,
+                        &format!("quantifier '{c}' is not supported, other than in '.*' and '.+'"),
+                    ));
+                },
+                c => builder.literal(c.encode_utf8(&mut [0; 4])),
+            };
+        }
+
+        if !anchored_end {
+            builder = builder.wild_n();
+        }
+
+        Ok(builder)
+    }
+
+    /// Parses the class that begins (with `'['`) at `column`, obtaining its
+    /// characters and whether it is negated.
+    fn parse_class_(
+        &mut self,
+        column : usize,
+    ) -> Result<(Vec<char>, bool)> {
+        let negated = self.take_if_(&['^']);
+
+        let mut chars = vec![];
+        let mut first = true;
+
+        loop {
+            let item_column = self.column;
+
+            let lo = match self.peek_() {
+                None => return Err(self.error_(column, "unterminated class")),
+                Some(']') if !first => {
+                    self.column += 1;
+
+                    break;
+                },
+                Some('[') => {
+                    return Err(self.error_(item_column, "nested and POSIX classes are not supported"));
+                },
+                Some('&' | '~' | '-') if self.chars.get(self.column + 1) == self.peek_().as_ref() => {
+                    return Err(self.error_(item_column, "class set operations are not supported"));
+                },
+                Some('\\') => {
+                    self.column += 1;
+
+                    self.parse_escape_(item_column)?
+                },
+                Some(c) => {
+                    self.column += 1;
+
+                    c
+                },
+            };
+
+            first = false;
+
+            let is_class_range =
+                Some('-') == self.peek_() && !std_matches!(self.chars.get(self.column + 1), None | Some(']'));
+
+            if !is_class_range {
+                chars.push(lo);
+
+                continue;
+            }
+
+            self.column += 1;
+
+            let hi_column = self.column;
+
+            let hi = match self.peek_() {
+                Some('\\') => {
+                    self.column += 1;
+
+                    self.parse_escape_(hi_column)?
+                },
+                Some('[') => {
+                    return Err(self.error_(hi_column, "nested and POSIX classes are not supported"));
+                },
+                Some(c) => {
+                    self.column += 1;
+
+                    c
+                },
+                None => return Err(self.error_(column, "unterminated class")),
+            };
+
+            if hi < lo {
+                return Err(self.error_(item_column, &format!("the class range {lo}-{hi} is inverted")));
+            }
+
+            if hi as u32 - lo as u32 >= MAX_CLASS_RANGE_LENGTH {
+                return Err(self.error_(
+                    item_column,
+                    &format!("the class range {lo}-{hi} has more than {MAX_CLASS_RANGE_LENGTH} characters"),
+                ));
+            }
+
+            chars.extend((lo..=hi).filter(|c| !chars.contains(c)).collect::<Vec<_>>());
+        }
+
+        Ok((chars, negated))
+    }
+
+    /// Parses the escape that begins (with `'\\'`) at `column`, obtaining
+    /// the literal character it represents.
+    fn parse_escape_(
+        &mut self,
+        column : usize,
+    ) -> Result<char> {
+        let c = match self.peek_() {
+            Some(c) => c,
+            None => return Err(self.error_(column, "trailing slash")),
+        };
+
+        self.column += 1;
+
+        match c {
+            'n' => Ok('\n'),
+            'r' => Ok('\r'),
+            't' => Ok('\t'),
+            'x' => self.parse_hex_escape_(column),
+            '0'..='9' => Err(self.error_(column, "backreferences are not supported")),
+            c if c.is_ascii_punctuation() || ' ' == c => Ok(c),
+            c => Err(self.error_(column, &format!("the escape '\\{c}' is not supported"))),
+        }
+    }
+
+    /// Parses the remainder of a `\xHH` or `\x{H...}` escape.
+    fn parse_hex_escape_(
+        &mut self,
+        column : usize,
+    ) -> Result<char> {
+        let digits : String = if self.take_if_(&['{']) {
+            let digits : String = self.chars[self.column..].iter().take_while(|&&c| '}' != c).collect();
+
+            self.column += digits.chars().count();
+
+            if !self.take_if_(&['}']) {
+                return Err(self.error_(column, "unterminated hexadecimal escape"));
+            }
+
+            digits
+        } else {
+            let digits : String = self.chars[self.column..].iter().take(2).collect();
+
+            self.column += digits.chars().count();
+
+            digits
+        };
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or_else(|| self.error_(column, "invalid hexadecimal escape"))
+    }
+
+    fn peek_(&self) -> Option<char> {
+        self.chars.get(self.column).copied()
+    }
+
+    /// Consumes `s` if it is next.
+    fn take_if_(
+        &mut self,
+        s : &[char],
+    ) -> bool {
+        if self.chars[self.column..].starts_with(s) {
+            self.column += s.len();
+
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error_(
+        &self,
+        column : usize,
+        message : &str,
+    ) -> Error {
+        Error::TranslationError {
+            message : format!("{message} (at column {column})"),
+        }
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// helpers
+
+fn push_regex_escaped_(
+    re : &mut String,
+    c : char,
+) {
+    if REGEX_SPECIAL_CHARACTERS.contains(&c) {
+        re.push('\\');
+    }
+
+    re.push(c);
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use crate::{
+        from_regex_subset,
+        CompiledMatcher,
+        Error,
+        IGNORE_CASE,
+    };
+
+
+    fn to_regex_string(
+        pattern : &str,
+        flags : i64,
+    ) -> String {
+        CompiledMatcher::from_pattern_and_flags(pattern, flags)
+            .unwrap()
+            .to_regex_string(0)
+            .unwrap()
+    }
+
+
+    #[test]
+    fn TEST_to_regex_string_1() {
+        assert_eq!("(?s)^$", to_regex_string("", 0));
+        assert_eq!("(?s)^abc$", to_regex_string("abc", 0));
+        assert_eq!(r"(?s)^IMG_.*\.jpe.$", to_regex_string("IMG_*.jpe?", 0));
+        assert_eq!(
+            r"(?s)^\*\?\[\]\\\^\$\.\+\(\)\{\}\|$",
+            to_regex_string(r"\*\?\[\]\\^$.+(){}|", 0)
+        );
+        assert_eq!(r"(?s)^[abc][^\-\]\^]$", to_regex_string(r"[a-c][^\-\]^]", 0));
+        assert_eq!(r"(?s)^[^\x00-\x{10FFFF}].$", to_regex_string("[][^]", 0));
+    }
+
+    #[test]
+    fn TEST_to_regex_string_IGNORE_CASE_1() {
+        assert_eq!("(?s)^[Aa][Bb]1[Éé]$", to_regex_string("aB1é", IGNORE_CASE));
+        assert_eq!("(?s)^[ABab][^Zz🐻]$", to_regex_string("[ab][^z🐻]", IGNORE_CASE));
+        assert_eq!("(?s)^[Ssſ][Kk]\u{212A}$", to_regex_string("sk\u{212A}", IGNORE_CASE));
+        assert_eq!("(?s)^[Iiı]$", to_regex_string("I", IGNORE_CASE));
+    }
+
+    #[test]
+    fn TEST_to_regex_string_IGNORE_CASE_MULTI_CHAR_FOLDING_1() {
+        assert_eq!("(?s)^(?:[Ssſ][Ssſ]|ß)$", to_regex_string("ß", IGNORE_CASE));
+        assert_eq!("(?s)^(?:[Ssſ][Ssſ]|ß)$", to_regex_string("ss", IGNORE_CASE));
+        assert_eq!("(?s)^(?:[Ff][Iiı]|ﬁ)$", to_regex_string("ﬁ", IGNORE_CASE));
+        assert_eq!("(?s)^[Cc](?:[Ssſ][Ssſ]|ß)$", to_regex_string("css", IGNORE_CASE));
+        assert_eq!(
+            "(?s)^(?:[Ssſ](?:[Ssſ][Ssſ]|ß)|ß[Ssſ])$",
+            to_regex_string("sss", IGNORE_CASE)
+        );
+        assert_eq!("(?s)^ß$", to_regex_string("ß", 0));
+
+        // `"s"` * 16 has 1,597 forms
+        let matcher = CompiledMatcher::from_pattern_and_flags(&"s".repeat(16), IGNORE_CASE).unwrap();
+
+        assert!(std::matches!(
+            matcher.to_regex_string(0),
+            Err(Error::TranslationError { .. })
+        ));
+    }
+
+    #[test]
+    fn TEST_to_regex_string_WITH_FLAGS_1() {
+        let matcher = CompiledMatcher::from_pattern_and_flags("aB[c]", 0).unwrap();
+
+        assert_eq!(Ok("(?s)^aB[c]$".into()), matcher.to_regex_string(0));
+        assert_eq!(Ok("(?s)^[Aa][Bb][Cc]$".into()), matcher.to_regex_string(IGNORE_CASE));
+    }
+
+    #[test]
+    fn TEST_from_regex_subset_1() {
+        assert_eq!(Ok("*".into()), from_regex_subset(""));
+        assert_eq!(Ok("".into()), from_regex_subset("^$"));
+        assert_eq!(Ok("foo*.log".into()), from_regex_subset(r"(?s)^foo.*\.log$"));
+        assert_eq!(Ok("foo*".into()), from_regex_subset("^foo"));
+        assert_eq!(Ok("*foo".into()), from_regex_subset("foo$"));
+        assert_eq!(Ok("*foo*".into()), from_regex_subset("foo"));
+        assert_eq!(Ok("a?b".into()), from_regex_subset(r"(?s)\Aa.b\z"));
+        assert_eq!(Ok("a?*b".into()), from_regex_subset("(?s)^a.+b$"));
+        assert_eq!(Ok("a?*b*".into()), from_regex_subset("(?s)^a.+?b.*?$"));
+        assert_eq!(Ok("?".into()), from_regex_subset("(?s)^.$"));
+        assert_eq!(Ok(r"\*\?\[\]\\^$ ".into()), from_regex_subset(r"^\*\?\[\]\\\^\$\ $"));
+        assert_eq!(
+            Ok("a\tb\nc\rdA🐻".into()),
+            from_regex_subset(r"^a\tb\nc\rd\x41\x{1f43b}$")
+        );
+    }
+
+    #[test]
+    fn TEST_from_regex_subset_CLASSES_1() {
+        assert_eq!(Ok("[a-c]".into()), from_regex_subset("^[abc]$"));
+        assert_eq!(Ok("[^A-Za-z]".into()), from_regex_subset("^[^a-zA-Z]$"));
+        assert_eq!(Ok("[0123456789]".into()), from_regex_subset("^[0-9]$"));
+        assert_eq!(Ok(r"[\-\]a]".into()), from_regex_subset("^[]a-]$"));
+        assert_eq!(Ok(r"[\-\^ab]".into()), from_regex_subset(r"^[a\-b\^]$"));
+        assert_eq!(Ok(r"[^\]]".into()), from_regex_subset("^[^]]$"));
+        assert_eq!(Ok("[🐻🐼]".into()), from_regex_subset("^[🐼🐻]$"));
+    }
+
+    #[test]
+    fn TEST_from_regex_subset_ERRORS_1() {
+        let message = |regex : &str| {
+            match from_regex_subset(regex) {
+                Err(Error::TranslationError {
+                    message,
+                }) => message,
+                result => panic!("unexpected result {result:?} for regex '{regex}'"),
+            }
+        };
+
+        assert_eq!("alternation ('|') is not supported (at column 4)", message("^foo|bar$"));
+        assert_eq!(
+            "'.' does not match a newline, other than after a leading '(?s)' (at column 2)",
+            message("^a.b$")
+        );
+        assert_eq!(
+            "'.' does not match a newline, other than after a leading '(?s)' (at column 4)",
+            message(r"^foo.*\.log$")
+        );
+        assert_eq!("groups are not supported (at column 1)", message("^(foo)$"));
+        assert_eq!("groups are not supported (at column 1)", message("^(?:foo)$"));
+        assert_eq!("lookarounds are not supported (at column 4)", message("^foo(?=bar)"));
+        assert_eq!("lookarounds are not supported (at column 0)", message("(?<!x)foo"));
+        assert_eq!(
+            "flags are not supported, other than a leading '(?s)' (at column 0)",
+            message("(?i)foo")
+        );
+        assert_eq!(
+            "quantifier '+' is not supported, other than in '.*' and '.+' (at column 2)",
+            message("^a+$")
+        );
+        assert_eq!(
+            "quantifier '*' is not supported, other than in '.*' and '.+' (at column 5)",
+            message("^[ab]*$")
+        );
+        assert_eq!(
+            "quantifier '?' is not supported, other than in '.*' and '.+' (at column 6)",
+            message("(?s)^.?$")
+        );
+        assert_eq!(
+            "quantifier '{' is not supported, other than in '.*' and '.+' (at column 2)",
+            message("^a{2}$")
+        );
+        assert_eq!("backreferences are not supported (at column 0)", message(r"\1"));
+        assert_eq!(r"the escape '\d' is not supported (at column 1)", message(r"^\d$"));
+        assert_eq!(r"the escape '\b' is not supported (at column 0)", message(r"\bfoo"));
+        assert_eq!("trailing slash (at column 1)", message(r"a\"));
+        assert_eq!("start anchor other than at the start (at column 1)", message("a^b"));
+        assert_eq!("end anchor other than at the end (at column 1)", message("a$b"));
+        assert_eq!("unterminated class (at column 1)", message("^[ab"));
+        assert_eq!(
+            "nested and POSIX classes are not supported (at column 2)",
+            message("^[[:alpha:]]$")
+        );
+        assert_eq!(
+            "class set operations are not supported (at column 3)",
+            message("^[a&&b]$")
+        );
+        assert_eq!("the class range z-a is inverted (at column 2)", message("^[z-a]$"));
+        assert_eq!(
+            "the class range a-ሴ has more than 256 characters (at column 2)",
+            message(r"^[a-\x{1234}]$")
+        );
+        assert_eq!("invalid hexadecimal escape (at column 0)", message(r"\x{110000}"));
+        assert_eq!("unterminated hexadecimal escape (at column 0)", message(r"\x{41"));
+    }
+
+    #[test]
+    fn TEST_to_sql_like_1() {
+        let to_sql_like = |pattern : &str, escape_char : char| {
+            CompiledMatcher::from_pattern_and_flags(pattern, 0)
+                .unwrap()
+                .to_sql_like(escape_char)
+        };
+
+        assert_eq!(Ok("".into()), to_sql_like("", '\\'));
+        assert_eq!(Ok("abc".into()), to_sql_like("abc", '\\'));
+        assert_eq!(Ok("IMG\\_%._sv".into()), to_sql_like("IMG_*.?sv", '\\'));
+        assert_eq!(Ok(r"100\%\\*?".into()), to_sql_like(r"100%\\\*\?", '\\'));
+        assert_eq!(Ok("100!%!!".into()), to_sql_like("100%!", '!'));
+        assert_eq!(Ok("a\\b".into()), to_sql_like(r"a\\b", '!'));
+    }
+
+    #[test]
+    fn TEST_to_sql_like_ERRORS_1() {
+        let matcher = CompiledMatcher::from_pattern_and_flags("*.[ch]", 0).unwrap();
+
+        assert_eq!(
+            Err(Error::TranslationError {
+                message : "the range '[ch]' cannot be expressed in SQL LIKE".into(),
+            }),
+            matcher.to_sql_like('\\')
+        );
+
+        let matcher = CompiledMatcher::from_pattern_and_flags("*.[^ch]", 0).unwrap();
+
+        assert_eq!(
+            "pattern cannot be translated: the range '[^ch]' cannot be expressed in SQL LIKE",
+            matcher.to_sql_like('\\').unwrap_err().to_string()
+        );
+
+        let matcher = CompiledMatcher::from_pattern_and_flags("*", 0).unwrap();
+
+        assert!(matcher.to_sql_like('%').is_err());
+        assert!(matcher.to_sql_like('_').is_err());
+    }
+
+    #[test]
+    fn TEST_to_sqlite_glob_1() {
+        let to_sqlite_glob = |pattern : &str, flags : i64| {
+            CompiledMatcher::from_pattern_and_flags(pattern, flags)
+                .unwrap()
+                .to_sqlite_glob()
+        };
+
+        assert_eq!(Ok("".into()), to_sqlite_glob("", 0));
+        assert_eq!(Ok("IMG_*.?sv".into()), to_sqlite_glob("IMG_*.?sv", 0));
+        assert_eq!(Ok("[*][?][[]]^-\\".into()), to_sqlite_glob(r"\*\?\[\]^-\\", 0));
+        assert_eq!(Ok("[abc][^def]".into()), to_sqlite_glob("[c-a][^d-f]", 0));
+        assert_eq!(Ok("?".into()), to_sqlite_glob("[^]", 0));
+
+        // positional literals within ranges
+        assert_eq!(Ok("[]a]".into()), to_sqlite_glob(r"[a\]]", 0));
+        assert_eq!(Ok("[a-]".into()), to_sqlite_glob(r"[\-a]", 0));
+        assert_eq!(Ok("[a^]".into()), to_sqlite_glob(r"[\^a]", 0));
+        assert_eq!(Ok("^".into()), to_sqlite_glob(r"[\^]", 0));
+        assert_eq!(Ok("[-^]".into()), to_sqlite_glob(r"[\^\-]", 0));
+        assert_eq!(Ok("[^^-]".into()), to_sqlite_glob(r"[^\^\-]", 0));
+        assert_eq!(Ok("[]a^-]".into()), to_sqlite_glob(r"[\^\-\]a]", 0));
+        assert_eq!(Ok("[^]]".into()), to_sqlite_glob(r"[^\]]", 0));
+    }
+
+    #[test]
+    fn TEST_to_sqlite_glob_IGNORE_CASE_1() {
+        let matcher = CompiledMatcher::from_pattern_and_flags("img_*.[jp]é?", IGNORE_CASE).unwrap();
+
+        assert_eq!(Ok("[Iiı][Mm][Gg]_*.[JPjp][Éé]?".into()), matcher.to_sqlite_glob());
+    }
+
+    #[test]
+    fn TEST_to_sqlite_glob_ERRORS_1() {
+        let matcher = CompiledMatcher::from_pattern_and_flags("a[]", 0).unwrap();
+
+        assert_eq!(
+            Err(Error::TranslationError {
+                message : "the empty range '[]' cannot be expressed in SQLite GLOB".into(),
+            }),
+            matcher.to_sqlite_glob()
+        );
+
+        // a literal that may match a sequence of characters of a different
+        // length when case is ignored
+        for literal in ["ss", "ß", "ﬁ"] {
+            let matcher = CompiledMatcher::from_pattern_and_flags(&format!("*{literal}*"), IGNORE_CASE).unwrap();
+
+            assert_eq!(
+                Err(Error::TranslationError {
+                    message : format!("the literal '{literal}' cannot be expressed case-insensitively in SQLite GLOB"),
+                }),
+                matcher.to_sqlite_glob()
+            );
+
+            let matcher = CompiledMatcher::from_pattern_and_flags(&format!("*{literal}*"), 0).unwrap();
+
+            assert_eq!(Ok(format!("*{literal}*")), matcher.to_sqlite_glob());
+        }
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */
@@ -0,0 +1,889 @@
+// src/walk.rs : walking of the filesystem for the paths matching a pattern
+
+// ///////////////////////////////////////////////
+// imports
+
+use crate::{
+    constants,
+    parse,
+    paths,
+    utils::MatcherSequence,
+    CompiledMatcher,
+    PatternElement,
+    Result,
+};
+
+use std::{
+    collections::VecDeque,
+    ffi::{
+        OsStr,
+        OsString,
+    },
+    fs,
+    io,
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::Arc,
+    vec,
+};
+
+
+// ///////////////////////////////////////////////
+// GlobOptions
+
+/// Options that moderate the walking of the filesystem by
+/// [`glob_with()`](crate::glob_with).
+#[derive(Clone)]
+#[derive(Debug)]
+#[derive(Default)]
+#[derive(Eq, PartialEq)]
+pub struct GlobOptions {
+    /// Flags that moderate the parsing and matching of the pattern, e.g.
+    /// [`IGNORE_CASE`](crate::IGNORE_CASE).
+    pub flags :           i64,
+    /// Whether symbolic links to directories are descended into. A link
+    /// to a directory that contains one of those by which it was reached
+    /// (including by other links) is reported as an error rather than
+    /// being descended into.
+    pub follow_symlinks : bool,
+    /// Whether names that begin with `'.'` are matched by segments that do
+    /// not themselves begin with a literal `'.'`, and descended into by
+    /// `**`.
+    pub include_hidden :  bool,
+    /// Whether the entries of each directory are visited in order of
+    /// name, such that the paths are obtained in (component-wise) sorted
+    /// order.
+    pub sort :            bool,
+}
+
+
+// ///////////////////////////////////////////////
+// GlobPlan
+
+/// A segment of a pattern that is to be walked.
+#[derive(Debug)]
+enum GlobSegment {
+    /// `**`, which matches any number (0 or more) of components.
+    AnyComponents,
+    /// Matches exactly one component.
+    Component {
+        matchers :  MatcherSequence,
+        /// The name, if the segment is a literal that may be looked up
+        /// rather than matched against each entry of a directory.
+        literal :   Option<String>,
+        /// Whether the segment begins with a literal `'.'`, and so may
+        /// match a hidden name.
+        is_dotted : bool,
+    },
+}
+
+/// The (ascending) indexes of the segments at which a walk may be, in
+/// which the index one beyond the last segment denotes a complete match.
+pub(crate) type Positions = Vec<usize>;
+
+/// The canonical paths of the directories by which a directory has been
+/// reached, innermost first, as are tracked where symbolic links are
+/// followed, such that a link back to any of them is detected.
+#[derive(Debug)]
+pub(crate) struct DescentPath {
+    canonical : PathBuf,
+    parent :    Option<Arc<DescentPath>>,
+}
+
+// API functions
+impl DescentPath {
+    /// Creates an instance for the directory `dir`, from which a walk
+    /// begins.
+    pub(crate) fn new(dir : &Path) -> io::Result<Arc<Self>> {
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+
+        let canonical = fs::canonicalize(dir).map_err(|error| with_path_(error, dir))?;
+
+        Ok(Arc::new(Self {
+            canonical,
+            parent : None,
+        }))
+    }
+}
+
+// Non-mutating methods
+impl DescentPath {
+    /// Obtains the descent path of a subdirectory whose canonical path is
+    /// `canonical`.
+    fn child(
+        self: &Arc<Self>,
+        canonical : PathBuf,
+    ) -> Arc<Self> {
+        Arc::new(Self {
+            canonical,
+            parent : Some(Arc::clone(self)),
+        })
+    }
+
+    /// The canonical paths of the directories, innermost first.
+    fn iter(&self) -> impl Iterator<Item = &Path> {
+        std::iter::successors(Some(self), |descent_path| descent_path.parent.as_deref())
+            .map(|descent_path| descent_path.canonical.as_path())
+    }
+}
+
+/// The outcome of visiting an entry of a directory.
+pub(crate) struct Visit {
+    /// The path of the entry.
+    pub(crate) path :         PathBuf,
+    /// Whether the path matches the pattern.
+    pub(crate) is_match :     bool,
+    /// The positions at which to descend into the entry, if it is a
+    /// directory that may contain matches, or the error that prevents it.
+    pub(crate) descend :      Option<io::Result<Positions>>,
+    /// The descent path of the entry, if it is to be descended into and
+    /// symbolic links are followed.
+    pub(crate) descent_path : Option<Arc<DescentPath>>,
+}
+
+/// A pattern split into the literal directory from which it is to be
+/// walked and the segments that are to be matched below it.
+#[derive(Debug)]
+pub(crate) struct GlobPlan {
+    base :     PathBuf,
+    segments : Vec<GlobSegment>,
+    options :  GlobOptions,
+}
+
+// API functions
+impl GlobPlan {
+    pub(crate) fn new(
+        pattern : &str,
+        options : &GlobOptions,
+    ) -> Result<Self> {
+        let flags = options.flags;
+        let ignore_case = 0 != (flags & constants::IGNORE_CASE);
+
+        let pattern = parse(pattern, flags)?;
+
+        let (absolute, segment_elements) = paths::split_segments(pattern.elements());
+
+        let mut base = if absolute { PathBuf::from("/") } else { PathBuf::new() };
+        let mut segments = vec![];
+
+        for elements in segment_elements {
+            // a literal must be matched against the entries of a directory
+            // if case is ignored, except for `.` and `..`, which are not
+            // among them
+            let literal = match elements.as_slice() {
+                [PatternElement::Literal(literal)] if !ignore_case || "." == literal || ".." == literal => {
+                    Some(literal.clone())
+                },
+                _ => None,
+            };
+
+            if segments.is_empty() {
+                if let Some(literal) = &literal {
+                    base.push(literal);
+
+                    continue;
+                }
+            }
+
+            if paths::is_any_components(&elements) {
+                segments.push(GlobSegment::AnyComponents);
+            } else {
+                let is_dotted = std::matches!(
+                    elements.first(),
+                    Some(PatternElement::Literal(literal)) if literal.starts_with('.')
+                );
+
+                segments.push(GlobSegment::Component {
+                    matchers : CompiledMatcher::link_(&elements, flags),
+                    literal,
+                    is_dotted,
+                });
+            }
+        }
+
+        Ok(Self {
+            base,
+            segments,
+            options : options.clone(),
+        })
+    }
+}
+
+// Non-mutating methods
+impl GlobPlan {
+    /// The literal directory (or file) from which the walk begins.
+    pub(crate) fn base(&self) -> &Path {
+        &self.base
+    }
+
+    /// The positions of the walk at the base.
+    pub(crate) fn start_positions(&self) -> Positions {
+        self.close_(vec![0])
+    }
+
+    /// Indicates whether `positions` include a complete match.
+    pub(crate) fn is_final(
+        &self,
+        positions : &[usize],
+    ) -> bool {
+        positions.last() == Some(&self.segments.len())
+    }
+
+    /// Indicates whether `positions` include any at which further
+    /// components may be matched.
+    pub(crate) fn can_descend(
+        &self,
+        positions : &[usize],
+    ) -> bool {
+        positions
+            .first()
+            .is_some_and(|&position| position < self.segments.len())
+    }
+
+    /// Obtains the entries of the directory `dir`, at `positions`, that
+    /// may match, which are looked up directly if all the segments at
+    /// `positions` are literals.
+    pub(crate) fn read_entries(
+        &self,
+        dir : &Path,
+        positions : &[usize],
+    ) -> io::Result<Vec<(OsString, fs::FileType)>> {
+        let literals : Option<Vec<&str>> = positions
+            .iter()
+            .filter(|&&position| position < self.segments.len())
+            .map(|&position| {
+                match &self.segments[position] {
+                    GlobSegment::Component {
+                        literal: Some(literal),
+                        ..
+                    } => Some(literal.as_str()),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        let mut entries = vec![];
+
+        match literals {
+            Some(mut literals) => {
+                literals.sort_unstable();
+                literals.dedup();
+
+                for literal in literals {
+                    let path = dir.join(literal);
+
+                    match fs::symlink_metadata(&path) {
+                        Ok(metadata) => entries.push((OsString::from(literal), metadata.file_type())),
+                        Err(error) if io::ErrorKind::NotFound == error.kind() => {},
+                        Err(error) => return Err(with_path_(error, &path)),
+                    };
+                }
+            },
+            None => {
+                let dir = if dir.as_os_str().is_empty() {
+                    Path::new(".")
+                } else {
+                    dir
+                };
+
+                for entry in fs::read_dir(dir).map_err(|error| with_path_(error, dir))? {
+                    let entry = entry.map_err(|error| with_path_(error, dir))?;
+                    let file_type = entry.file_type().map_err(|error| with_path_(error, &entry.path()))?;
+
+                    entries.push((entry.file_name(), file_type));
+                }
+            },
+        };
+
+        if self.options.sort {
+            entries.sort_by(|(name1, _), (name2, _)| name1.cmp(name2));
+        }
+
+        Ok(entries)
+    }
+
+    /// Obtains the descent path of the base, if symbolic links are
+    /// followed.
+    pub(crate) fn base_descent_path(&self) -> io::Result<Option<Arc<DescentPath>>> {
+        if self.options.follow_symlinks {
+            DescentPath::new(&self.base).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Visits the entry `name`, of type `file_type`, of the directory
+    /// `dir`, at `positions`, where `descent_path` is that of `dir`, if
+    /// symbolic links are followed.
+    ///
+    /// # Returns:
+    /// The outcome, or `None` if the entry neither matches nor may
+    /// contain matches.
+    pub(crate) fn visit(
+        &self,
+        dir : &Path,
+        name : &OsStr,
+        file_type : fs::FileType,
+        positions : &[usize],
+        descent_path : Option<&Arc<DescentPath>>,
+    ) -> Option<Visit> {
+        let positions = self.step_(positions, name);
+
+        if positions.is_empty() {
+            return None;
+        }
+
+        let path = dir.join(name);
+        let is_match = self.is_final(&positions);
+
+        let mut child_descent_path = None;
+
+        let descend = if !self.can_descend(&positions) {
+            None
+        } else if file_type.is_dir() {
+            // the canonical path of a (real) subdirectory of a directory is
+            // obtained without resolution
+            child_descent_path = descent_path.map(|descent_path| {
+                let canonical = descent_path.canonical.join(name);
+
+                descent_path.child(canonical)
+            });
+
+            Some(Ok(positions))
+        } else if let (true, Some(descent_path)) = (file_type.is_symlink(), descent_path) {
+            match fs::metadata(&path) {
+                Ok(metadata) if metadata.is_dir() => {
+                    match check_for_loop_(descent_path, &path) {
+                        Ok(target) => {
+                            child_descent_path = Some(descent_path.child(target));
+
+                            Some(Ok(positions))
+                        },
+                        Err(error) => Some(Err(error)),
+                    }
+                },
+                _ => None,
+            }
+        } else {
+            None
+        };
+
+        if !is_match && descend.is_none() {
+            return None;
+        }
+
+        Some(Visit {
+            path,
+            is_match,
+            descend,
+            descent_path : child_descent_path,
+        })
+    }
+}
+
+// Implementation
+impl GlobPlan {
+    /// Obtains `positions` along with those that follow any `**` among
+    /// them, which may match no components.
+    fn close_(
+        &self,
+        positions : Positions,
+    ) -> Positions {
+        let mut closed = Vec::with_capacity(positions.len());
+
+        for mut position in positions {
+            closed.push(position);
+
+            while let Some(GlobSegment::AnyComponents) = self.segments.get(position) {
+                position += 1;
+
+                closed.push(position);
+            }
+        }
+
+        closed.sort_unstable();
+        closed.dedup();
+
+        closed
+    }
+
+    /// Obtains the positions that follow the component `name` from
+    /// `positions`.
+    fn step_(
+        &self,
+        positions : &[usize],
+        name : &OsStr,
+    ) -> Positions {
+        let is_hidden = !self.options.include_hidden && name.as_encoded_bytes().starts_with(b".");
+
+        let mut next = vec![];
+
+        for &position in positions {
+            match self.segments.get(position) {
+                Some(GlobSegment::AnyComponents) if !is_hidden => {
+                    next.push(position);
+                },
+                Some(GlobSegment::Component {
+                    matchers,
+                    is_dotted,
+                    ..
+                }) if (!is_hidden || *is_dotted) && matchers.matches_bytes(name.as_encoded_bytes()) => {
+                    next.push(position + 1);
+                },
+                _ => {},
+            };
+        }
+
+        self.close_(next)
+    }
+}
+
+/// Obtains `error` with its message prefixed by `path`.
+pub(crate) fn with_path_(
+    error : io::Error,
+    path : &Path,
+) -> io::Error {
+    io::Error::new(error.kind(), format!("{}: {error}", path.display()))
+}
+
+/// Determines whether the symbolic link `path`, in the directory whose
+/// descent path is `descent_path`, refers to a directory that contains
+/// any of those by which it was reached, and so would be walked again.
+///
+/// # Returns:
+/// The canonical path of the target of the link, if there is no loop.
+fn check_for_loop_(
+    descent_path : &DescentPath,
+    path : &Path,
+) -> io::Result<PathBuf> {
+    let target = fs::canonicalize(path).map_err(|error| with_path_(error, path))?;
+
+    if descent_path.iter().any(|dir| dir.starts_with(&target)) {
+        Err(io::Error::other(format!(
+            "{}: file system loop to {}",
+            path.display(),
+            target.display()
+        )))
+    } else {
+        Ok(target)
+    }
+}
+
+
+// ///////////////////////////////////////////////
+// Glob
+
+/// A directory being walked.
+#[derive(Debug)]
+struct GlobFrame {
+    dir :          PathBuf,
+    positions :    Positions,
+    /// The descent path of the directory, if symbolic links are followed.
+    descent_path : Option<Arc<DescentPath>>,
+    /// The remaining entries, or `None` if the directory is yet to be
+    /// read.
+    entries :      Option<vec::IntoIter<(OsString, fs::FileType)>>,
+}
+
+/// An iterator over the paths that match a pattern, as obtained from
+/// [`glob()`](crate::glob) and [`glob_with()`](crate::glob_with).
+///
+/// The walk begins at the literal directory prefix of the pattern - e.g.
+/// `src` for `"src/**/*.rs"` - and descends only into the directories
+/// that may contain matches, each of which is read only when it is
+/// reached. Each directory is obtained before its contents.
+#[derive(Debug)]
+pub struct Glob {
+    plan :    GlobPlan,
+    frames :  Vec<GlobFrame>,
+    pending : VecDeque<io::Result<PathBuf>>,
+    started : bool,
+}
+
+// API functions
+impl Glob {
+    pub(crate) fn new_(
+        pattern : &str,
+        options : &GlobOptions,
+    ) -> Result<Self> {
+        Ok(Self {
+            plan :    GlobPlan::new(pattern, options)?,
+            frames :  vec![],
+            pending : VecDeque::new(),
+            started : false,
+        })
+    }
+}
+
+// Implementation
+impl Glob {
+    fn start_(&mut self) {
+        let base = self.plan.base();
+        let positions = self.plan.start_positions();
+
+        if !base.as_os_str().is_empty() {
+            let metadata = match fs::metadata(base) {
+                Ok(metadata) => metadata,
+                Err(error) if io::ErrorKind::NotFound == error.kind() => return,
+                Err(error) => {
+                    self.pending.push_back(Err(with_path_(error, base)));
+
+                    return;
+                },
+            };
+
+            if self.plan.is_final(&positions) {
+                self.pending.push_back(Ok(base.to_path_buf()));
+            }
+
+            if !metadata.is_dir() {
+                return;
+            }
+        }
+
+        if !self.plan.can_descend(&positions) {
+            return;
+        }
+
+        match self.plan.base_descent_path() {
+            Ok(descent_path) => {
+                self.frames.push(GlobFrame {
+                    dir : base.to_path_buf(),
+                    positions,
+                    descent_path,
+                    entries : None,
+                });
+            },
+            Err(error) => self.pending.push_back(Err(error)),
+        };
+    }
+}
+
+// Trait implementations
+
+impl Iterator for Glob {
+    type Item = io::Result<PathBuf>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+
+            self.start_();
+        }
+
+        loop {
+            if let Some(result) = self.pending.pop_front() {
+                return Some(result);
+            }
+
+            let frame = self.frames.last_mut()?;
+
+            if frame.entries.is_none() {
+                match self.plan.read_entries(&frame.dir, &frame.positions) {
+                    Ok(entries) => frame.entries = Some(entries.into_iter()),
+                    Err(error) => {
+                        self.frames.pop();
+
+                        return Some(Err(error));
+                    },
+                };
+            }
+
+            let Some((name, file_type)) = frame.entries.as_mut().and_then(Iterator::next) else {
+                self.frames.pop();
+
+                continue;
+            };
+
+            let Some(visit) = self.plan.visit(
+                &frame.dir,
+                &name,
+                file_type,
+                &frame.positions,
+                frame.descent_path.as_ref(),
+            ) else {
+                continue;
+            };
+
+            if visit.is_match {
+                self.pending.push_back(Ok(visit.path.clone()));
+            }
+
+            match visit.descend {
+                Some(Ok(positions)) => {
+                    self.frames.push(GlobFrame {
+                        dir : visit.path,
+                        positions,
+                        descent_path : visit.descent_path,
+                        entries : None,
+                    });
+                },
+                Some(Err(error)) => self.pending.push_back(Err(error)),
+                None => {},
+            };
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    #![allow(non_snake_case)]
+
+    use super::*;
+
+    use crate::{
+        test_utils::TempTree,
+        IGNORE_CASE,
+    };
+
+
+    /// The paths matching `pattern` in `tree`, relative to its root, with
+    /// `'/'` separators.
+    fn glob(
+        tree : &TempTree,
+        pattern : &str,
+        options : &GlobOptions,
+    ) -> Vec<String> {
+        Glob::new_(&tree.pattern(pattern), options)
+            .unwrap()
+            .map(|path| tree.relative(&path.unwrap()))
+            .collect()
+    }
+
+    const TREE : &[&str] = &[
+        "Cargo.toml",
+        "README.md",
+        ".hidden/x.rs",
+        "src/lib.rs",
+        "src/.dot.rs",
+        "src/walk.rs",
+        "src/a/b/deep.rs",
+        "src/a/b/deep.txt",
+        "src/a/mod.rs",
+        "src/empty/",
+        "tests/t.rs",
+    ];
+
+    fn sorted() -> GlobOptions {
+        GlobOptions {
+            sort : true,
+            ..Default::default()
+        }
+    }
+
+
+    #[test]
+    fn TEST_GlobPlan_BASE_1() {
+        let base = |pattern : &str| GlobPlan::new(pattern, &GlobOptions::default()).unwrap().base;
+
+        assert_eq!(Path::new("src"), base("src/**/*.rs"));
+        assert_eq!(Path::new("/usr/lib"), base("/usr/lib/*.so"));
+        assert_eq!(Path::new("./a/../b"), base("./a/../b/?"));
+        assert_eq!(Path::new(""), base("*/x"));
+        assert_eq!(Path::new("Cargo.toml"), base("Cargo.toml"));
+
+        let options = GlobOptions {
+            flags : IGNORE_CASE,
+            ..Default::default()
+        };
+
+        assert_eq!(Path::new("../.."), GlobPlan::new("../../src/*", &options).unwrap().base);
+    }
+
+    #[test]
+    fn TEST_Glob_1() {
+        let tree = TempTree::new("walk-1", TREE);
+
+        assert_eq!(vec!["src/lib.rs", "src/walk.rs"], glob(&tree, "src/*.rs", &sorted()));
+        assert_eq!(
+            vec!["src/a/b/deep.rs", "src/a/mod.rs", "src/lib.rs", "src/walk.rs"],
+            glob(&tree, "src/**/*.rs", &sorted())
+        );
+        assert_eq!(
+            vec!["Cargo.toml", "README.md", "src", "tests"],
+            glob(&tree, "*", &sorted())
+        );
+        assert_eq!(vec!["src/a/b/deep.txt"], glob(&tree, "*/*/?/*.txt", &sorted()));
+        assert_eq!(vec!["Cargo.toml"], glob(&tree, "Cargo.toml", &sorted()));
+        assert!(glob(&tree, "Cargo.lock", &sorted()).is_empty());
+        assert!(glob(&tree, "nowhere/**/*", &sorted()).is_empty());
+    }
+
+    #[test]
+    fn TEST_Glob_BASE_ERROR_1() {
+        let tree = TempTree::new("walk-base-error", TREE);
+
+        // a missing base is not an error, but one that cannot be examined
+        // (here, because a file is treated as a directory) is
+        let results : Vec<_> = Glob::new_(&tree.pattern("nowhere/**/*"), &sorted()).unwrap().collect();
+
+        assert!(results.is_empty());
+
+        let results : Vec<_> = Glob::new_(&tree.pattern("Cargo.toml/a/*"), &sorted())
+            .unwrap()
+            .collect();
+
+        assert_eq!(1, results.len());
+        assert!(results[0].is_err());
+    }
+
+    #[test]
+    fn TEST_Glob_ANY_COMPONENTS_1() {
+        let tree = TempTree::new("walk-any", TREE);
+
+        assert_eq!(
+            vec![
+                "src",
+                "src/a",
+                "src/a/b",
+                "src/a/b/deep.rs",
+                "src/a/b/deep.txt",
+                "src/a/mod.rs",
+                "src/empty",
+                "src/lib.rs",
+                "src/walk.rs"
+            ],
+            glob(&tree, "src/**", &sorted())
+        );
+        assert_eq!(vec!["src/a/b"], glob(&tree, "**/b", &sorted()));
+
+        // no duplicates, however many ways a path matches
+        assert_eq!(vec!["src/a/b/deep.rs"], glob(&tree, "**/a/**/deep.rs", &sorted()));
+        assert_eq!(glob(&tree, "**/*.rs", &sorted()), glob(&tree, "**/**/*.rs", &sorted()));
+    }
+
+    #[test]
+    fn TEST_Glob_HIDDEN_1() {
+        let tree = TempTree::new("walk-hidden", TREE);
+
+        let with_hidden = GlobOptions {
+            include_hidden : true,
+            sort : true,
+            ..Default::default()
+        };
+
+        assert_eq!(vec!["src/lib.rs", "src/walk.rs"], glob(&tree, "src/*.rs", &sorted()));
+        assert_eq!(vec!["src/.dot.rs"], glob(&tree, "src/.*", &sorted()));
+        assert_eq!(
+            vec!["src/.dot.rs", "src/lib.rs", "src/walk.rs"],
+            glob(&tree, "src/*.rs", &with_hidden)
+        );
+        assert_eq!(vec![".hidden/x.rs"], glob(&tree, ".hidden/*", &sorted()));
+        assert!(!glob(&tree, "**/*.rs", &sorted()).contains(&".hidden/x.rs".to_string()));
+        assert!(glob(&tree, "**/*.rs", &with_hidden).contains(&".hidden/x.rs".to_string()));
+    }
+
+    #[test]
+    fn TEST_Glob_IGNORE_CASE_1() {
+        let tree = TempTree::new("walk-case", TREE);
+
+        let options = GlobOptions {
+            flags : IGNORE_CASE,
+            sort : true,
+            ..Default::default()
+        };
+
+        assert_eq!(vec!["src/lib.rs"], glob(&tree, "SRC/LIB.RS", &options));
+        assert_eq!(vec!["README.md"], glob(&tree, "readme.*", &options));
+    }
+
+    #[test]
+    fn TEST_Glob_AGREES_WITH_matches_path_components_1() {
+        let tree = TempTree::new("walk-agrees", TREE);
+
+        let mut all : Vec<String> = glob(&tree, "**", &sorted());
+
+        all.retain(|path| !path.is_empty());
+
+        for pattern in [
+            "*",
+            "*/*",
+            "src/**/*.rs",
+            "**/*.t?t",
+            "**/a/**",
+            "src/[a-m]*",
+            "*/*/*/*",
+            "**/b/*",
+        ] {
+            let matcher = CompiledMatcher::from_pattern_and_flags(pattern, 0).unwrap();
+
+            let expected : Vec<String> = all
+                .iter()
+                .filter(|path| matcher.matches_path_components(Path::new(path)))
+                .cloned()
+                .collect();
+
+            assert_eq!(expected, glob(&tree, pattern, &sorted()), "pattern '{pattern}'");
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn TEST_Glob_SYMLINKS_1() {
+        let tree = TempTree::new("walk-symlinks", &["real/f.rs"]);
+
+        std::os::unix::fs::symlink(tree.root.join("real"), tree.root.join("link")).unwrap();
+        std::os::unix::fs::symlink(&tree.root, tree.root.join("real/up")).unwrap();
+
+        assert_eq!(vec!["real/f.rs"], glob(&tree, "**/*.rs", &sorted()));
+        assert_eq!(vec!["link", "real"], glob(&tree, "*", &sorted()));
+
+        let options = GlobOptions {
+            follow_symlinks : true,
+            sort : true,
+            ..Default::default()
+        };
+
+        let pattern = tree.pattern("**/*.rs");
+        let results : Vec<_> = Glob::new_(&pattern, &options).unwrap().collect();
+
+        // each link to the root is a loop, and so is reported as an error
+        assert_eq!(2, results.iter().filter(|result| result.is_err()).count());
+        assert_eq!(
+            vec![tree.root.join("link/f.rs"), tree.root.join("real/f.rs")],
+            results.into_iter().filter_map(|result| result.ok()).collect::<Vec<_>>()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn TEST_Glob_SYMLINKS_CROSS_LINKED_1() {
+        let tree = TempTree::new("walk-cross-linked", &["a/f.rs", "b/g.rs"]);
+
+        // neither link refers to an ancestor of its own directory, but each
+        // refers to the directory by which the other is reached
+        std::os::unix::fs::symlink(tree.root.join("b"), tree.root.join("a/to-b")).unwrap();
+        std::os::unix::fs::symlink(tree.root.join("a"), tree.root.join("b/to-a")).unwrap();
+
+        let options = GlobOptions {
+            follow_symlinks : true,
+            sort : true,
+            ..Default::default()
+        };
+
+        let pattern = tree.pattern("**/*.rs");
+        let results : Vec<_> = Glob::new_(&pattern, &options).unwrap().collect();
+
+        // `a/to-b/to-a` and `b/to-a/to-b` are loops
+        assert_eq!(2, results.iter().filter(|result| result.is_err()).count());
+        assert_eq!(
+            vec![
+                tree.root.join("a/f.rs"),
+                tree.root.join("a/to-b/g.rs"),
+                tree.root.join("b/g.rs"),
+                tree.root.join("b/to-a/f.rs"),
+            ],
+            results.into_iter().filter_map(|result| result.ok()).collect::<Vec<_>>()
+        );
+    }
+}
+
+
+/* ///////////////////////////// end of file //////////////////////////// */